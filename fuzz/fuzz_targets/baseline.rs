@@ -0,0 +1,144 @@
+// The parser of v1.4 by `from_str_radix()`, kept as the reference of
+// the digit-by-digit parser in `parse_equiv`.
+
+use std::num::{IntErrorKind, ParseIntError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    Invalid,
+    Overflow,
+    Precision,
+}
+
+impl From<ParseIntError> for ParseError {
+    fn from(pie: ParseIntError) -> Self {
+        match pie.kind() {
+            IntErrorKind::Empty => ParseError::Empty,
+            IntErrorKind::InvalidDigit => ParseError::Invalid,
+            _ => ParseError::Overflow,
+        }
+    }
+}
+
+pub trait Baseline: Copy + Eq {
+    const ZERO: Self;
+    const MIN: Self;
+    const NEG_MIN_STR: &'static str;
+
+    fn from_str_radix(s: &str) -> Result<Self, ParseIntError>;
+    fn get_exp(i: usize) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn wrapping_neg(self) -> Self;
+
+    // Parse an string as negative.
+    // We try to parse it as positive first. If fail for overflow,
+    // then it maybe the MIN value.
+    fn parse_int_as_negative(s: &str) -> Result<Self, ParseIntError> {
+        match Self::from_str_radix(s) {
+            Ok(num) => Ok(num.wrapping_neg()),
+            Err(err) => {
+                if err.kind() == &IntErrorKind::PosOverflow
+                    && s.trim_start_matches('0') == Self::NEG_MIN_STR
+                {
+                    Ok(Self::MIN)
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    fn try_from_str(s: &str, scale: i32) -> Result<Self, ParseError> {
+        let (num, raw_scale) = Self::try_from_str_only(s)?;
+        if num == Self::ZERO || raw_scale == scale {
+            Ok(num)
+        } else if raw_scale > scale {
+            Err(ParseError::Precision)
+        } else {
+            Self::get_exp((scale - raw_scale) as usize)
+                .ok_or(ParseError::Precision)?
+                .checked_mul(num)
+                .ok_or(ParseError::Overflow)
+        }
+    }
+
+    // Guess and return the scale by the input string.
+    fn try_from_str_only(s: &str) -> Result<(Self, i32), ParseError> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        if let Some((int_str, frac_str)) = s.split_once('.') {
+            let int_num = Self::from_str_radix(int_str)?;
+
+            let frac_num = if s.as_bytes()[0] == b'-' {
+                Self::parse_int_as_negative(frac_str)?
+            } else {
+                Self::from_str_radix(frac_str)?
+            };
+
+            let inner = if int_num == Self::ZERO {
+                // only fraction part
+                frac_num
+            } else {
+                // exp * integer + fraction
+                Self::get_exp(frac_str.len())
+                    .ok_or(ParseError::Precision)?
+                    .checked_mul(int_num)
+                    .ok_or(ParseError::Overflow)?
+                    .checked_add(frac_num)
+                    .ok_or(ParseError::Overflow)?
+            };
+            Ok((inner, frac_str.len() as i32))
+        } else {
+            // only integer part
+            if s == "0" || s == "-0" || s == "+0" {
+                return Ok((Self::ZERO, 0));
+            }
+            let new_int_str = s.trim_end_matches('0');
+            let diff = s.len() - new_int_str.len();
+            Ok((Self::from_str_radix(new_int_str)?, -(diff as i32)))
+        }
+    }
+}
+
+macro_rules! impl_baseline {
+    ($($t:ty, $neg_min_str:literal;)*) => {$(
+        impl Baseline for $t {
+            const ZERO: Self = 0;
+            const MIN: Self = <$t>::MIN;
+            const NEG_MIN_STR: &'static str = $neg_min_str;
+
+            fn from_str_radix(s: &str) -> Result<Self, ParseIntError> {
+                s.parse()
+            }
+            fn get_exp(i: usize) -> Option<Self> {
+                <$t>::checked_pow(10, i.try_into().ok()?)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn wrapping_neg(self) -> Self {
+                <$t>::wrapping_neg(self)
+            }
+        }
+    )*};
+}
+
+impl_baseline! {
+    i8, "128";
+    i16, "32768";
+    i32, "2147483648";
+    i64, "9223372036854775808";
+    i128, "170141183460469231731687303715884105728";
+    u8, "unreachable";
+    u16, "unreachable";
+    u32, "unreachable";
+    u64, "unreachable";
+    u128, "unreachable";
+}
@@ -0,0 +1,96 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use primitive_fixed_point_decimal::{FpdecInner, ParseErrorKind, ParseOptions};
+
+mod baseline;
+use baseline::Baseline;
+
+#[derive(Debug, arbitrary::Arbitrary)]
+struct Data {
+    bytes: Vec<u8>,
+    scale: i8,
+}
+
+fuzz_target!(|data: Data| {
+    let scale = data.scale as i32;
+    check_all(&data.bytes, scale);
+
+    // map into the number characters to make more valid strings
+    let bytes: Vec<u8> = data
+        .bytes
+        .iter()
+        .map(|b| b"0123456789012345.-+e"[*b as usize % 20])
+        .collect();
+    check_all(&bytes, scale);
+});
+
+fn check_all(bytes: &[u8], scale: i32) {
+    check::<i8>(bytes, scale);
+    check::<i16>(bytes, scale);
+    check::<i32>(bytes, scale);
+    check::<i64>(bytes, scale);
+    check::<i128>(bytes, scale);
+    check::<u8>(bytes, scale);
+    check::<u16>(bytes, scale);
+    check::<u32>(bytes, scale);
+    check::<u64>(bytes, scale);
+    check::<u128>(bytes, scale);
+}
+
+// The fast path in `try_from_ascii()` and `try_from_str()` must be the
+// same with the general path in `try_from_ascii_opts()`.
+fn check<I: FpdecInner + Baseline + std::fmt::Debug>(bytes: &[u8], scale: i32) {
+    let general = I::try_from_ascii_opts(bytes, scale, None, ParseOptions::DEFAULT);
+    assert_eq!(I::try_from_ascii(bytes, scale), general);
+
+    if let Ok(s) = std::str::from_utf8(bytes) {
+        assert_eq!(<I as FpdecInner>::try_from_str(s, scale), general);
+        check_baseline::<I>(s, scale);
+    }
+}
+
+// The new parser must be the same with the baseline one in v1.4, for
+// the plain decimal strings `-?[0-9]+(\.[0-9]+)?`. The baseline one does
+// not check the other strings well, e.g. `"1.-5"`.
+fn check_baseline<I: FpdecInner + Baseline + std::fmt::Debug>(s: &str, scale: i32) {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let is_plain = match digits.split_once('.') {
+        Some((int_str, frac_str)) => is_digits(int_str) && is_digits(frac_str),
+        None => is_digits(digits),
+    };
+    if !is_plain {
+        return;
+    }
+
+    let new = <I as FpdecInner>::try_from_str(s, scale).map_err(|e| e.kind());
+    match <I as Baseline>::try_from_str(s, scale) {
+        Ok(num) => assert_eq!(new, Ok(num), "{s} {scale}"),
+        Err(err) => match new {
+            // the baseline one fails on zeros with no fraction, e.g. `"00"`
+            Ok(num) => {
+                assert!(num.is_zero() && !digits.contains('.'), "{s} {scale}");
+                assert!(digits.bytes().all(|b| b == b'0'), "{s} {scale}");
+            }
+            // The baseline one parses all digits before checking the
+            // scale, and returns `Precision` for too big scale, so the
+            // kinds of out of range are not told apart here.
+            Err(kind) => assert!(
+                matches!(
+                    (err, kind),
+                    (baseline::ParseError::Empty, ParseErrorKind::Empty)
+                        | (baseline::ParseError::Invalid, ParseErrorKind::Invalid)
+                        | (
+                            baseline::ParseError::Overflow | baseline::ParseError::Precision,
+                            ParseErrorKind::IntOverflow
+                                | ParseErrorKind::FracOverflow
+                                | ParseErrorKind::Precision(_)
+                        )
+                ),
+                "{s} {scale} {err:?} {kind:?}"
+            ),
+        },
+    }
+}
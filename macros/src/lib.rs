@@ -0,0 +1,203 @@
+//! Compile-time decimal literals for
+//! [`primitive_fixed_point_decimal`](https://docs.rs/primitive_fixed_point_decimal).
+//!
+//! Do not use this crate directly. Enable the `macros` feature of
+//! `primitive_fixed_point_decimal` instead, which provides `fpdec_const!`
+//! as a wrapper of [`fpdec_const!`] here.
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Build decimal from literal, checked at compile time.
+///
+/// This is called by the `fpdec_const!` macro of `primitive_fixed_point_decimal`
+/// with the path of that crate in brackets ahead, e.g.
+/// `fpdec_const!([$crate] "12.34", 4)`, so the expansion works under a
+/// renamed dependency or a re-export. See the documentation there.
+#[proc_macro]
+pub fn fpdec_const(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(output) => output,
+        Err((msg, span)) => compile_error(msg, span),
+    }
+}
+
+type Error = (&'static str, Span);
+
+fn expand(input: TokenStream) -> Result<TokenStream, Error> {
+    let mut tokens = flatten(input).into_iter();
+
+    // the path of the main crate, in brackets
+    let path = match tokens.next() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => g.stream(),
+        Some(t) => return Err(("expect crate path in brackets", t.span())),
+        None => return Err(("expect crate path in brackets", Span::call_site())),
+    };
+
+    // the literal, with optional `-` ahead
+    let (minus, lit) = match tokens.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '-' => match tokens.next() {
+            Some(TokenTree::Literal(lit)) => (true, lit),
+            _ => return Err(("expect number literal after `-`", p.span())),
+        },
+        Some(TokenTree::Literal(lit)) => (false, lit),
+        Some(t) => return Err(("expect decimal literal", t.span())),
+        None => return Err(("expect decimal literal", Span::call_site())),
+    };
+    let span = lit.span();
+    let (neg, digits, lit_scale) = parse_literal(&lit, minus).map_err(|msg| (msg, span))?;
+
+    // the optional scale for OobScaleFpdec
+    let scale: TokenStream = match tokens.next() {
+        None => TokenStream::new(),
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => tokens.collect(),
+        Some(t) => return Err(("expect `,` before scale", t.span())),
+    };
+
+    // `$crate` can not be parsed from string, so put the path tokens
+    let mut body = TokenStream::new();
+    let call = if scale.is_empty() {
+        format!("::const_literal::<_, {neg}, {digits}, {lit_scale}>()")
+    } else {
+        body.extend("const SCALE: i32 =".parse::<TokenStream>().unwrap());
+        body.extend(scale);
+        body.extend(";".parse::<TokenStream>().unwrap());
+        format!("::const_literal_oob::<_, {neg}, {digits}, {lit_scale}, SCALE>()")
+    };
+    body.extend(path);
+    body.extend(call.parse::<TokenStream>().unwrap());
+
+    let output: TokenStream = "const".parse().unwrap();
+    Ok(output
+        .into_iter()
+        .chain([TokenTree::Group(Group::new(Delimiter::Brace, body))])
+        .collect())
+}
+
+// Unwrap the invisible groups, which come from `$e:expr` in `macro_rules!`.
+fn flatten(input: TokenStream) -> Vec<TokenTree> {
+    let mut tokens = Vec::new();
+    for t in input {
+        match t {
+            TokenTree::Group(g) if g.delimiter() == Delimiter::None => {
+                tokens.extend(flatten(g.stream()))
+            }
+            t => tokens.push(t),
+        }
+    }
+    tokens
+}
+
+// Return (is_neg, digits, scale), where the value is `digits * 10^-scale`.
+fn parse_literal(lit: &Literal, minus: bool) -> Result<(bool, u128, i32), &'static str> {
+    let s = lit.to_string();
+    if let Some(inner) = s.strip_prefix('"') {
+        let inner = inner.strip_suffix('"').ok_or("invalid string literal")?;
+        if minus {
+            return Err("put `-` inside the string");
+        }
+        parse_decimal(inner)
+    } else {
+        let s = s.replace('_', "");
+        if s.starts_with(['+', '-']) {
+            return Err("invalid number literal");
+        }
+        let (neg, digits, scale) = parse_decimal(&s)?;
+        Ok((neg ^ minus && digits != 0, digits, scale))
+    }
+}
+
+// Parse `[+-]int[.frac][(e|E)[+-]exp]`.
+fn parse_decimal(s: &str) -> Result<(bool, u128, i32), &'static str> {
+    let (neg, s) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+
+    let (mant, exp) = match s.find(['e', 'E']) {
+        Some(i) => (
+            &s[..i],
+            s[i + 1..].parse::<i32>().map_err(|_| "invalid exponent")?,
+        ),
+        None => (s, 0),
+    };
+
+    let (int, frac) = mant.split_once('.').unwrap_or((mant, ""));
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(int) || (mant.contains('.') && !is_digits(frac)) {
+        return Err("invalid decimal literal");
+    }
+
+    // strip the tailing zeros, which may overflow u128 but no matter
+    let all = [int, frac].concat();
+    let sig = all.trim_end_matches('0');
+    let mut digits: u128 = 0;
+    for b in sig.bytes() {
+        digits = digits
+            .checked_mul(10)
+            .and_then(|d| d.checked_add((b - b'0') as u128))
+            .ok_or("too many significant digits")?;
+    }
+    if digits == 0 {
+        return Ok((false, 0, 0));
+    }
+
+    let zeros = (all.len() - sig.len()) as i64;
+    let scale = frac.len() as i64 - zeros - exp as i64;
+    let scale = i32::try_from(scale).map_err(|_| "exponent out of range")?;
+    Ok((neg, digits, scale))
+}
+
+fn compile_error(msg: &str, span: Span) -> TokenStream {
+    let mut lit = Literal::string(msg);
+    lit.set_span(span);
+    let mut group = Group::new(Delimiter::Parenthesis, TokenTree::Literal(lit).into());
+    group.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+
+    [
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(group),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("12.3456"), Ok((false, 123456, 4)));
+        assert_eq!(parse_decimal("-12.3400"), Ok((true, 1234, 2)));
+        assert_eq!(parse_decimal("+1200"), Ok((false, 12, -2)));
+        assert_eq!(parse_decimal("0.000"), Ok((false, 0, 0)));
+        assert_eq!(parse_decimal("-0"), Ok((false, 0, 0)));
+        assert_eq!(parse_decimal("1.5e-3"), Ok((false, 15, 4)));
+        assert_eq!(parse_decimal("1.5E+3"), Ok((false, 15, -2)));
+        assert_eq!(parse_decimal("007.10e0"), Ok((false, 71, 1)));
+        assert_eq!(
+            parse_decimal("340282366920938463463374607431768211455"),
+            Ok((false, u128::MAX, 0))
+        );
+        assert_eq!(
+            parse_decimal("340282366920938463463374607431768211455000.000"),
+            Ok((false, u128::MAX, -3))
+        );
+
+        for s in ["", "-", "1.", ".1", "1..2", "1.2.3", "1x", "1 ", "--1"] {
+            assert_eq!(parse_decimal(s), Err("invalid decimal literal"), "{s}");
+        }
+        assert_eq!(parse_decimal("1e"), Err("invalid exponent"));
+        assert_eq!(parse_decimal("1e+"), Err("invalid exponent"));
+        assert_eq!(
+            parse_decimal("340282366920938463463374607431768211456"),
+            Err("too many significant digits")
+        );
+        assert_eq!(parse_decimal("1e-2147483648"), Err("exponent out of range"));
+        assert_eq!(parse_decimal("0e-2147483648"), Ok((false, 0, 0)));
+    }
+}
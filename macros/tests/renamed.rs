@@ -0,0 +1,15 @@
+// The main crate is renamed as `fpd` in dev-dependencies, so the
+// `primitive_fixed_point_decimal` path is not available here.
+use fpd::{fpdec_const, ConstScaleFpdec, OobScaleFpdec};
+
+#[test]
+fn test_renamed_crate() {
+    const FEE: ConstScaleFpdec<i64, 4> = fpdec_const!("0.0015");
+    assert_eq!(FEE.mantissa(), 15);
+
+    const PRICE: OobScaleFpdec<i32> = fpdec_const!(-12.34, 4);
+    assert_eq!(PRICE.mantissa(), -123400);
+
+    let b: ConstScaleFpdec<i16, 2> = fpd::fpdec_const!(1.5e1);
+    assert_eq!(b.mantissa(), 1500);
+}
@@ -0,0 +1,131 @@
+// Support of the `fpdec_const!` macro.
+//
+// The macro parses the literal into `digits * 10^-LS` at compile time,
+// and expands to `const_literal()` or `const_literal_oob()` with them as
+// const arguments. The target type is inferred from the context, and the
+// mantissa is calculated in the associated constant. So any error is
+// reported at compile time.
+
+use crate::const_ops::scale_magnitude;
+use crate::{ConstScaleFpdec, OobScaleFpdec, ParseErrorKind};
+
+/// Decimal types which can be built from literal `digits * 10^-LS`, with
+/// negative sign if `NEG`.
+#[doc(hidden)]
+pub trait ConstLiteral<const NEG: bool, const DIGITS: u128, const LS: i32>: Sized {
+    const VALUE: Self;
+}
+
+/// Same with [`ConstLiteral`], but with the out-of-band scale `S`.
+#[doc(hidden)]
+pub trait ConstLiteralOob<const NEG: bool, const DIGITS: u128, const LS: i32, const S: i32>:
+    Sized
+{
+    const VALUE: Self;
+}
+
+#[doc(hidden)]
+pub const fn const_literal<T, const NEG: bool, const DIGITS: u128, const LS: i32>() -> T
+where
+    T: ConstLiteral<NEG, DIGITS, LS>,
+{
+    T::VALUE
+}
+
+#[doc(hidden)]
+pub const fn const_literal_oob<
+    T,
+    const NEG: bool,
+    const DIGITS: u128,
+    const LS: i32,
+    const S: i32,
+>() -> T
+where
+    T: ConstLiteralOob<NEG, DIGITS, LS, S>,
+{
+    T::VALUE
+}
+
+// Calculate the magnitude of the mantissa of `digits * 10^-lit_scale`
+// under `scale`. Panic if precision lost or overflow.
+const fn magnitude(digits: u128, lit_scale: i32, scale: i32) -> u128 {
+    match scale_magnitude(digits, lit_scale as i64, scale as i64) {
+        Ok(m) => m,
+        Err(ParseErrorKind::Overflow) => panic!("fpdec_const: overflow"),
+        Err(_) => panic!("fpdec_const: precision out of range"),
+    }
+}
+
+macro_rules! impl_const_literal {
+    ($($t:ty),*) => {$(
+        impl<const NEG: bool, const DIGITS: u128, const LS: i32, const S: i32>
+            ConstLiteral<NEG, DIGITS, LS> for ConstScaleFpdec<$t, S>
+        {
+            const VALUE: Self = Self::from_mantissa(mantissa!($t, NEG, DIGITS, LS, S));
+        }
+
+        impl<const NEG: bool, const DIGITS: u128, const LS: i32, const S: i32>
+            ConstLiteralOob<NEG, DIGITS, LS, S> for OobScaleFpdec<$t>
+        {
+            const VALUE: Self = Self::from_mantissa(mantissa!($t, NEG, DIGITS, LS, S));
+        }
+    )*};
+}
+
+macro_rules! mantissa {
+    ($t:ty, $neg:expr, $digits:expr, $ls:expr, $s:expr) => {{
+        let m = magnitude($digits, $ls, $s);
+        if $neg {
+            if m > (<$t>::MIN as i128).unsigned_abs() {
+                panic!("fpdec_const: overflow");
+            }
+            (m as $t).wrapping_neg()
+        } else {
+            if m > <$t>::MAX as u128 {
+                panic!("fpdec_const: overflow");
+            }
+            m as $t
+        }
+    }};
+}
+
+impl_const_literal!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_const_literal() {
+        const A: ConstScaleFpdec<i64, 4> = const_literal::<_, false, 123456, 4>();
+        assert_eq!(A.mantissa(), 123456);
+        const B: ConstScaleFpdec<i64, 4> = const_literal::<_, true, 12, -2>();
+        assert_eq!(B.mantissa(), -12000000);
+        const C: ConstScaleFpdec<i8, 2> = const_literal::<_, true, 128, 2>();
+        assert_eq!(C.mantissa(), i8::MIN);
+        const D: ConstScaleFpdec<u128, 0> = const_literal::<_, false, { u128::MAX }, 0>();
+        assert_eq!(D.mantissa(), u128::MAX);
+        const E: ConstScaleFpdec<i128, 0> = const_literal::<_, true, { 1 << 127 }, 0>();
+        assert_eq!(E.mantissa(), i128::MIN);
+        const F: ConstScaleFpdec<u8, -2> = const_literal::<_, false, 25, -3>();
+        assert_eq!(F.mantissa(), 250);
+        const G: OobScaleFpdec<i16> = const_literal_oob::<_, true, 15, 3, 4>();
+        assert_eq!(G.mantissa(), -150);
+
+        assert_eq!(magnitude(0, 100, -100), 0);
+        assert_eq!(magnitude(1, 0, 38), 10_u128.pow(38));
+        assert_eq!(magnitude(1200, 0, -2), 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "fpdec_const: overflow")]
+    fn test_magnitude_overflow() {
+        magnitude(1, 0, 39);
+    }
+
+    #[test]
+    #[should_panic(expected = "fpdec_const: precision out of range")]
+    fn test_magnitude_precision() {
+        magnitude(1234, 0, -3);
+    }
+}
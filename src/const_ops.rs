@@ -0,0 +1,679 @@
+// Const fn arithmetic for `ConstScaleFpdec` of each inner integer type.
+//
+// The methods of `FpdecInner` can not be called in const context. So here
+// we calculate on the sign and magnitude (as u128) by const fns, and
+// define the `const_*` methods for each inner integer type by macro.
+//
+// The results are same with the non-const methods.
+
+use crate::{ConstScaleFpdec, ParseErrorKind, Rounding};
+
+use core::cmp::Ordering;
+
+// Calculate `a * b` in 256 bits, returning (high, low).
+const fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let low = (p00 & MASK) | (mid << 64);
+    let high = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (high, low)
+}
+
+// Calculate `a * b / c` and the remainder, returning `None` if the
+// quotient overflows. `c` must not be zero.
+const fn mul_div_rem(a: u128, b: u128, c: u128) -> Option<(u128, u128)> {
+    let (high, low) = mul_wide(a, b);
+    if high == 0 {
+        return Some((low / c, low % c));
+    }
+    if high >= c {
+        return None;
+    }
+
+    // long division, bit by bit
+    let mut r = high;
+    let mut q = 0;
+    let mut i = 128;
+    while i > 0 {
+        i -= 1;
+        let carry = r >> 127;
+        r = (r << 1) | ((low >> i) & 1);
+        q <<= 1;
+        if carry != 0 || r >= c {
+            r = r.wrapping_sub(c);
+            q |= 1;
+        }
+    }
+    Some((q, r))
+}
+
+// Same with `fpdec_inner::round_away()`, but `q` is the magnitude of
+// the truncated quotient and `r / c` is the magnitude of the fraction.
+const fn round_away(rounding: Rounding, q: u128, is_neg: bool, r: u128, c: u128) -> bool {
+    let rest = c - r;
+    let half = if r > rest {
+        Ordering::Greater
+    } else if r == rest {
+        Ordering::Equal
+    } else {
+        Ordering::Less
+    };
+    match rounding {
+        Rounding::Floor => is_neg,
+        Rounding::Ceiling => !is_neg,
+        Rounding::TowardsZero => false,
+        Rounding::AwayFromZero => true,
+        Rounding::Round => half.is_ge(),
+        Rounding::HalfTowardsZero => half.is_gt(),
+        Rounding::HalfDown => match half {
+            Ordering::Equal => is_neg,
+            o => o.is_gt(),
+        },
+        Rounding::HalfEven => match half {
+            Ordering::Equal => q % 2 == 1,
+            o => o.is_gt(),
+        },
+        Rounding::HalfOdd => match half {
+            Ordering::Equal => q.is_multiple_of(2),
+            o => o.is_gt(),
+        },
+        Rounding::ZeroFiveUp => q.is_multiple_of(5),
+    }
+}
+
+// Calculate the magnitude of `a * b / c` with rounding, where the exact
+// quotient is negative if `is_neg`. Returning `None` if overflow.
+pub(crate) const fn mul_div(
+    a: u128,
+    b: u128,
+    c: u128,
+    is_neg: bool,
+    rounding: Rounding,
+) -> Option<u128> {
+    let Some((q, r)) = mul_div_rem(a, b, c) else {
+        return None;
+    };
+    if r != 0 && round_away(rounding, q, is_neg, r, c) {
+        q.checked_add(1)
+    } else {
+        Some(q)
+    }
+}
+
+// Calculate the magnitude of the mantissa of `digits * 10^-lit_scale`
+// under `scale`. Returning error of `Overflow` if overflow of u128, or
+// `Precision` if precision lost.
+pub(crate) const fn scale_magnitude(
+    digits: u128,
+    lit_scale: i64,
+    scale: i64,
+) -> Result<u128, ParseErrorKind> {
+    if digits == 0 {
+        return Ok(0);
+    }
+    let mut m = digits;
+    let mut diff = scale - lit_scale;
+    while diff > 0 {
+        m = match m.checked_mul(10) {
+            Some(m) => m,
+            None => return Err(ParseErrorKind::Overflow),
+        };
+        diff -= 1;
+    }
+    let mut extra = 0;
+    while diff < 0 {
+        if !m.is_multiple_of(10) {
+            extra = -diff as u32;
+            break;
+        }
+        m /= 10;
+        diff += 1;
+    }
+    if extra > 0 {
+        Err(ParseErrorKind::Precision(extra))
+    } else {
+        Ok(m)
+    }
+}
+
+// Parse `[+-]int[.frac][(e|E)[+-]exp]` into (is_neg, digits, scale),
+// where the value is `digits * 10^-scale`. Returning `None` if invalid,
+// or the significant digits overflow u128.
+pub(crate) const fn parse_decimal(s: &[u8]) -> Option<(bool, u128, i64)> {
+    let mut i = 0;
+    let is_neg = !s.is_empty() && s[0] == b'-';
+    if !s.is_empty() && (s[0] == b'-' || s[0] == b'+') {
+        i += 1;
+    }
+
+    // the integer and fraction digits
+    let int_start = i;
+    while i < s.len() && s[i].is_ascii_digit() {
+        i += 1;
+    }
+    let int_end = i;
+    if int_end == int_start {
+        return None;
+    }
+    let mut frac_end = i;
+    if i < s.len() && s[i] == b'.' {
+        i += 1;
+        while i < s.len() && s[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == int_end + 1 {
+            return None;
+        }
+        frac_end = i;
+    }
+    let frac_len = (frac_end - int_end).saturating_sub(1) as i64;
+
+    // the exponent
+    let mut exp: i64 = 0;
+    if i < s.len() && (s[i] == b'e' || s[i] == b'E') {
+        i += 1;
+        let exp_neg = i < s.len() && s[i] == b'-';
+        if i < s.len() && (s[i] == b'-' || s[i] == b'+') {
+            i += 1;
+        }
+        let exp_start = i;
+        while i < s.len() && s[i].is_ascii_digit() {
+            exp = exp * 10 + (s[i] - b'0') as i64;
+            if exp > i32::MAX as i64 {
+                return None;
+            }
+            i += 1;
+        }
+        if i == exp_start {
+            return None;
+        }
+        if exp_neg {
+            exp = -exp;
+        }
+    }
+    if i != s.len() {
+        return None;
+    }
+
+    // the significant digits, without the tailing zeros
+    let mut digits: u128 = 0;
+    let mut zeros = 0;
+    let mut j = int_start;
+    while j < frac_end {
+        let b = s[j];
+        j += 1;
+        if b == b'.' {
+            continue;
+        }
+        if b == b'0' {
+            zeros += 1;
+            continue;
+        }
+        while zeros > 0 {
+            digits = match digits.checked_mul(10) {
+                Some(d) => d,
+                None => return None,
+            };
+            zeros -= 1;
+        }
+        digits = match digits.checked_mul(10) {
+            Some(d) => match d.checked_add((b - b'0') as u128) {
+                Some(d) => d,
+                None => return None,
+            },
+            None => return None,
+        };
+    }
+    if digits == 0 {
+        return Some((false, 0, 0));
+    }
+    Some((is_neg, digits, frac_len - zeros - exp))
+}
+
+macro_rules! impl_const_ops {
+    (signed: $($t:ty),*) => {$(
+        impl<const S: i32> ConstScaleFpdec<$t, S> {
+            const fn to_parts(self) -> (bool, u128) {
+                let m = self.mantissa();
+                (m < 0, m.unsigned_abs() as u128)
+            }
+
+            const fn from_parts(is_neg: bool, m: u128) -> Option<Self> {
+                if is_neg {
+                    if m > <$t>::MIN.unsigned_abs() as u128 {
+                        return None;
+                    }
+                    Some(Self::from_mantissa((m as $t).wrapping_neg()))
+                } else {
+                    if m > <$t>::MAX as u128 {
+                        return None;
+                    }
+                    Some(Self::from_mantissa(m as $t))
+                }
+            }
+        }
+        impl_const_ops!(common: $t);
+    )*};
+
+    (unsigned: $($t:ty),*) => {$(
+        impl<const S: i32> ConstScaleFpdec<$t, S> {
+            const fn to_parts(self) -> (bool, u128) {
+                (false, self.mantissa() as u128)
+            }
+
+            const fn from_parts(is_neg: bool, m: u128) -> Option<Self> {
+                if (is_neg && m != 0) || m > <$t>::MAX as u128 {
+                    return None;
+                }
+                Some(Self::from_mantissa(m as $t))
+            }
+        }
+        impl_const_ops!(common: $t);
+    )*};
+
+    (common: $t:ty) => {
+        impl<const S: i32> ConstScaleFpdec<$t, S> {
+            // 10^|diff_scale| as u128, or `None` if out of range
+            // `[-Self::DIGITS, Self::DIGITS]`.
+            const fn const_exp(diff_scale: i64) -> Option<u128> {
+                if diff_scale.unsigned_abs() > <$t>::MAX.ilog10() as u64 {
+                    return None;
+                }
+                Some(10_u128.pow(diff_scale.unsigned_abs() as u32))
+            }
+
+            /// Same with [`Self::checked_add`], but in const context.
+            ///
+            /// Examples:
+            ///
+            /// ```
+            /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+            /// type Fee = ConstScaleFpdec<i64, 4>;
+            ///
+            /// const MIN_FEE: Fee = Fee::const_from_str("0.0002").unwrap();
+            /// const MAX_FEE: Fee = Fee::const_from_str("0.0015").unwrap();
+            /// const RANGE: Fee = MAX_FEE.const_checked_sub(MIN_FEE).unwrap();
+            /// const TICK: Fee = Fee::const_from_int(1).unwrap();
+            /// const FIVE_TICKS: Fee = TICK.const_checked_mul_int(5).unwrap();
+            ///
+            /// assert_eq!(RANGE, fpdec!(0.0013));
+            /// assert_eq!(FIVE_TICKS, fpdec!(5));
+            /// assert_eq!(MAX_FEE.const_checked_add(Fee::MAX), None);
+            /// ```
+            #[must_use]
+            pub const fn const_checked_add(self, rhs: Self) -> Option<Self> {
+                match self.mantissa().checked_add(rhs.mantissa()) {
+                    Some(m) => Some(Self::from_mantissa(m)),
+                    None => None,
+                }
+            }
+
+            /// Same with [`Self::checked_sub`], but in const context.
+            #[must_use]
+            pub const fn const_checked_sub(self, rhs: Self) -> Option<Self> {
+                match self.mantissa().checked_sub(rhs.mantissa()) {
+                    Some(m) => Some(Self::from_mantissa(m)),
+                    None => None,
+                }
+            }
+
+            /// Same with [`Self::checked_mul_int`], but in const context.
+            #[must_use]
+            pub const fn const_checked_mul_int(self, n: $t) -> Option<Self> {
+                match self.mantissa().checked_mul(n) {
+                    Some(m) => Some(Self::from_mantissa(m)),
+                    None => None,
+                }
+            }
+
+            /// Same with [`Self::checked_mul`], but in const context, and
+            /// `rhs` must have the same inner integer type.
+            #[must_use]
+            pub const fn const_checked_mul<const S2: i32, const SR: i32>(
+                self,
+                rhs: ConstScaleFpdec<$t, S2>,
+            ) -> Option<ConstScaleFpdec<$t, SR>> {
+                self.const_checked_mul_ext(rhs, Rounding::Round)
+            }
+
+            /// Same with [`Self::checked_mul_ext`], but in const context, and
+            /// `rhs` must have the same inner integer type.
+            ///
+            /// Examples:
+            ///
+            /// ```
+            /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+            /// type Balance = ConstScaleFpdec<i64, 2>;
+            /// type Rate = ConstScaleFpdec<i64, 4>;
+            ///
+            /// const LIMIT: Balance = Balance::const_from_str("1000").unwrap();
+            /// const RATE: Rate = Rate::const_from_str("0.0333").unwrap();
+            /// const FEE: Balance = LIMIT.const_checked_mul(RATE).unwrap();
+            /// const FEE_CEIL: Balance = LIMIT.const_checked_mul_ext(RATE, Rounding::Ceiling).unwrap();
+            /// assert_eq!(FEE, fpdec!(33.3));
+            /// assert_eq!(FEE, LIMIT.checked_mul(RATE).unwrap());
+            ///
+            /// const MAX_UNITS: Balance = LIMIT.const_checked_div_ext(RATE, Rounding::Floor).unwrap();
+            /// assert_eq!(MAX_UNITS, fpdec!(30030.03));
+            /// ```
+            #[must_use]
+            pub const fn const_checked_mul_ext<const S2: i32, const SR: i32>(
+                self,
+                rhs: ConstScaleFpdec<$t, S2>,
+                rounding: Rounding,
+            ) -> Option<ConstScaleFpdec<$t, SR>> {
+                let (a_neg, a) = self.to_parts();
+                let (b_neg, b) = rhs.to_parts();
+                let is_neg = a_neg != b_neg;
+
+                let diff_scale = S as i64 + S2 as i64 - SR as i64;
+                let Some(exp) = Self::const_exp(diff_scale) else {
+                    return None;
+                };
+                let m = if diff_scale > 0 {
+                    mul_div(a, b, exp, is_neg, rounding)
+                } else {
+                    match mul_div(a, b, 1, is_neg, rounding) {
+                        Some(m) => m.checked_mul(exp),
+                        None => None,
+                    }
+                };
+                match m {
+                    Some(m) => ConstScaleFpdec::<$t, SR>::from_parts(is_neg, m),
+                    None => None,
+                }
+            }
+
+            /// Same with [`Self::checked_div`], but in const context, and
+            /// `rhs` must have the same inner integer type.
+            #[must_use]
+            pub const fn const_checked_div<const S2: i32, const SR: i32>(
+                self,
+                rhs: ConstScaleFpdec<$t, S2>,
+            ) -> Option<ConstScaleFpdec<$t, SR>> {
+                self.const_checked_div_ext(rhs, Rounding::Round)
+            }
+
+            /// Same with [`Self::checked_div_ext`], but in const context, and
+            /// `rhs` must have the same inner integer type.
+            #[must_use]
+            pub const fn const_checked_div_ext<const S2: i32, const SR: i32>(
+                self,
+                rhs: ConstScaleFpdec<$t, S2>,
+                rounding: Rounding,
+            ) -> Option<ConstScaleFpdec<$t, SR>> {
+                let (a_neg, a) = self.to_parts();
+                let (b_neg, b) = rhs.to_parts();
+                let is_neg = a_neg != b_neg;
+                if b == 0 {
+                    return None;
+                }
+
+                let diff_scale = S as i64 - S2 as i64 - SR as i64;
+                let Some(exp) = Self::const_exp(diff_scale) else {
+                    return None;
+                };
+                if diff_scale > 0 {
+                    // self / rhs / exp, rounding twice
+                    let Some(q) = mul_div(a, 1, b, is_neg, rounding) else {
+                        return None;
+                    };
+                    if Self::from_parts(is_neg, q).is_none() {
+                        return None;
+                    }
+                    match mul_div(q, 1, exp, is_neg, rounding) {
+                        Some(m) => ConstScaleFpdec::<$t, SR>::from_parts(is_neg, m),
+                        None => None,
+                    }
+                } else {
+                    // self * exp / rhs
+                    match mul_div(a, exp, b, is_neg, rounding) {
+                        Some(m) => ConstScaleFpdec::<$t, SR>::from_parts(is_neg, m),
+                        None => None,
+                    }
+                }
+            }
+
+            /// Same with [`Self::round`], but in const context, and return
+            /// `None` if overflow occurred.
+            #[must_use]
+            pub const fn const_checked_round(self, scale: i32) -> Option<Self> {
+                self.const_checked_round_ext(scale, Rounding::Round)
+            }
+
+            /// Same with [`Self::round_ext`], but in const context, and
+            /// return `None` if overflow occurred.
+            ///
+            /// Examples:
+            ///
+            /// ```
+            /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+            /// type Price = ConstScaleFpdec<i64, 8>;
+            ///
+            /// const P: Price = Price::const_from_str("12.34567").unwrap();
+            /// const R: Price = P.const_checked_round_ext(2, Rounding::Ceiling).unwrap();
+            /// assert_eq!(R, fpdec!(12.35));
+            /// ```
+            #[must_use]
+            pub const fn const_checked_round_ext(self, scale: i32, rounding: Rounding) -> Option<Self> {
+                let diff_scale = S as i64 - scale as i64;
+                if diff_scale <= 0 {
+                    return Some(self);
+                }
+                let Some(exp) = Self::const_exp(diff_scale) else {
+                    return Some(Self::from_mantissa(0));
+                };
+                let (is_neg, a) = self.to_parts();
+                match mul_div(a, 1, exp, is_neg, rounding) {
+                    Some(q) => Self::from_parts(is_neg, q * exp),
+                    None => None,
+                }
+            }
+
+            /// Build from integer in const context. Return `None` if
+            /// overflow occurred or lossing precision under `S < 0`.
+            #[must_use]
+            pub const fn const_from_int(n: $t) -> Option<Self> {
+                let (is_neg, m) = Self::from_mantissa(n).to_parts();
+                match scale_magnitude(m, 0, S as i64) {
+                    Ok(m) => Self::from_parts(is_neg, m),
+                    Err(_) => None,
+                }
+            }
+
+            /// Build from string in const context. Return `None` if the
+            /// string is invalid, or overflow occurred, or the precision is
+            /// out of range.
+            ///
+            /// It accepts `[+-]int[.frac][(e|E)[+-]exp]`, same with
+            /// [`ParseOptions::DEFAULT`](crate::ParseOptions::DEFAULT).
+            #[must_use]
+            pub const fn const_from_str(s: &str) -> Option<Self> {
+                let Some((is_neg, digits, lit_scale)) = parse_decimal(s.as_bytes()) else {
+                    return None;
+                };
+                match scale_magnitude(digits, lit_scale, S as i64) {
+                    Ok(m) => Self::from_parts(is_neg, m),
+                    Err(_) => None,
+                }
+            }
+        }
+    };
+}
+
+impl_const_ops!(signed: i8, i16, i32, i64, i128);
+impl_const_ops!(unsigned: u8, u16, u32, u64, u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FpdecInner;
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_wide(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+        assert_eq!(mul_wide(1 << 64, 1 << 64), (1, 0));
+        assert_eq!(
+            mul_div_rem(u128::MAX, u128::MAX, u128::MAX),
+            Some((u128::MAX, 0))
+        );
+        assert_eq!(mul_div_rem(u128::MAX, 8, 7), None);
+        assert_eq!(
+            mul_div_rem(u128::MAX, 3, 4),
+            Some((u128::MAX / 4 * 3 + 2, 1))
+        );
+
+        // same with FpdecInner
+        for r in Rounding::ALL {
+            for (a, b, c) in [
+                (7_i128, 3, 10),
+                (-7, 3, 10),
+                (15, 1, 10),
+                (-25, 1, 10),
+                (i128::MAX, 3, 7),
+                (i128::MIN, 5, 9),
+                (i128::MAX / 3, 10_i128.pow(20), 10_i128.pow(20) + 1),
+            ] {
+                let is_neg = (a < 0) != (b < 0);
+                let m = mul_div(a.unsigned_abs(), b.unsigned_abs(), c as u128, is_neg, r);
+                let expect = a.calc_mul_div(b, c, r).map(|q| q.unsigned_abs());
+                assert_eq!(m, expect, "{a} {b} {c} {r:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal(b"12.3456"), Some((false, 123456, 4)));
+        assert_eq!(parse_decimal(b"-12.3400"), Some((true, 1234, 2)));
+        assert_eq!(parse_decimal(b"+1200"), Some((false, 12, -2)));
+        assert_eq!(parse_decimal(b"-0.00"), Some((false, 0, 0)));
+        assert_eq!(parse_decimal(b"1.5e-3"), Some((false, 15, 4)));
+        assert_eq!(parse_decimal(b"10.5E+3"), Some((false, 105, -2)));
+        assert_eq!(parse_decimal(b"0100.0010"), Some((false, 100001, 3)));
+        assert_eq!(
+            parse_decimal(b"340282366920938463463374607431768211455000"),
+            Some((false, u128::MAX, -3))
+        );
+        assert_eq!(
+            parse_decimal(b"340282366920938463463374607431768211456"),
+            None
+        );
+        for s in [
+            "",
+            "-",
+            "1.",
+            ".1",
+            "1..2",
+            "1e",
+            "1e+",
+            "1x",
+            " 1",
+            "--1",
+            "1e9999999999",
+        ] {
+            assert_eq!(parse_decimal(s.as_bytes()), None, "{s}");
+        }
+
+        assert_eq!(scale_magnitude(12, 2, 4), Ok(1200));
+        assert_eq!(scale_magnitude(12, 2, 1), Err(ParseErrorKind::Precision(1)));
+        assert_eq!(
+            scale_magnitude(12, 2, -100),
+            Err(ParseErrorKind::Precision(102))
+        );
+        assert_eq!(scale_magnitude(1, 0, 39), Err(ParseErrorKind::Overflow));
+        assert_eq!(scale_magnitude(0, 0, 100), Ok(0));
+    }
+
+    #[test]
+    fn test_const_ops() {
+        type D8 = ConstScaleFpdec<i8, 1>;
+        type U8 = ConstScaleFpdec<u8, 1>;
+
+        // same with the non-const methods, exhaustively for 8 bits
+        for r in Rounding::ALL {
+            for a in i8::MIN..=i8::MAX {
+                let a = D8::from_mantissa(a);
+                for b in i8::MIN..=i8::MAX {
+                    let b = D8::from_mantissa(b);
+                    assert_eq!(a.const_checked_add(b), a.checked_add(b));
+                    assert_eq!(a.const_checked_sub(b), a.checked_sub(b));
+                    assert_eq!(
+                        a.const_checked_mul_int(b.mantissa()),
+                        a.checked_mul_int(b.mantissa())
+                    );
+
+                    let m: Option<D8> = a.const_checked_mul_ext(b, r);
+                    assert_eq!(m, a.checked_mul_ext(b, r), "{a} * {b} {r:?}");
+                    let m: Option<ConstScaleFpdec<i8, 3>> = a.const_checked_mul_ext(b, r);
+                    assert_eq!(m, a.checked_mul_ext(b, r), "{a} * {b} {r:?}");
+                    let m: Option<ConstScaleFpdec<i8, 0>> = a.const_checked_mul_ext(b, r);
+                    assert_eq!(m, a.checked_mul_ext(b, r), "{a} * {b} {r:?}");
+
+                    let q: Option<D8> = a.const_checked_div_ext(b, r);
+                    assert_eq!(q, a.checked_div_ext(b, r), "{a} / {b} {r:?}");
+                    let q: Option<ConstScaleFpdec<i8, -1>> = a.const_checked_div_ext(b, r);
+                    assert_eq!(q, a.checked_div_ext(b, r), "{a} / {b} {r:?}");
+                    let q: Option<ConstScaleFpdec<i8, 2>> = a.const_checked_div_ext(b, r);
+                    assert_eq!(q, a.checked_div_ext(b, r), "{a} / {b} {r:?}");
+                }
+                let expect = a
+                    .mantissa()
+                    .rounding_div(10, r)
+                    .and_then(|q| q.checked_mul(10));
+                assert_eq!(
+                    a.const_checked_round_ext(0, r),
+                    expect.map(D8::from_mantissa)
+                );
+                if expect.is_some() {
+                    assert_eq!(a.const_checked_round_ext(0, r), Some(a.round_ext(0, r)));
+                }
+                assert_eq!(a.const_checked_round_ext(-5, r), Some(D8::ZERO));
+                assert_eq!(a.const_checked_round_ext(1, r), Some(a));
+            }
+            for a in u8::MIN..=u8::MAX {
+                let a = U8::from_mantissa(a);
+                for b in u8::MIN..=u8::MAX {
+                    let b = U8::from_mantissa(b);
+                    let m: Option<U8> = a.const_checked_mul_ext(b, r);
+                    assert_eq!(m, a.checked_mul_ext(b, r), "{a} * {b} {r:?}");
+                    let q: Option<ConstScaleFpdec<u8, 2>> = a.const_checked_div_ext(b, r);
+                    assert_eq!(q, a.checked_div_ext(b, r), "{a} / {b} {r:?}");
+                }
+            }
+        }
+
+        // 128 bits
+        type D128 = ConstScaleFpdec<i128, 18>;
+        let a = D128::MAX;
+        let b = D128::const_from_str("-0.999999999999999999").unwrap();
+        for r in Rounding::ALL {
+            let m: Option<D128> = a.const_checked_mul_ext(b, r);
+            assert_eq!(m, a.checked_mul_ext(b, r));
+            let q: Option<D128> = b.const_checked_div_ext(a, r);
+            assert_eq!(q, b.checked_div_ext(a, r));
+        }
+
+        // construction
+        assert_eq!(D8::const_from_str("-12.8"), Some(D8::MIN));
+        assert_eq!(D8::const_from_str("12.8"), None);
+        assert_eq!(D8::const_from_str("1.25"), None);
+        assert_eq!(D8::const_from_str("1.2e1"), Some(D8::from_mantissa(120)));
+        assert_eq!(U8::const_from_str("-0"), Some(U8::ZERO));
+        assert_eq!(U8::const_from_str("-1"), None);
+        assert_eq!(D8::const_from_int(12), Some(D8::from_mantissa(120)));
+        assert_eq!(D8::const_from_int(13), None);
+        assert_eq!(
+            ConstScaleFpdec::<i8, -1>::const_from_int(-120),
+            Some(ConstScaleFpdec::from_mantissa(-12))
+        );
+        assert_eq!(ConstScaleFpdec::<i8, -1>::const_from_int(-125), None);
+    }
+}
@@ -1,8 +1,11 @@
-use crate::fpdec_inner::FpdecInner;
-use crate::oob_scale_fpdec::OobScaleFpdec;
-use crate::{IntoRatioInt, ParseError, Rounding};
+use crate::fpdec_inner::{checked_add_mixed, try_into_inner, FpdecInner};
+use crate::oob_scale_fpdec::{DisplayWith, EngFmt, OobFmt, OobScaleFpdec};
+use crate::{
+    ArithmeticError, FmtBuffer, IntoRatioInt, NumberFormat, ParseError, ParseErrorKind,
+    ParseOptions, Rounding, RoundingAccumulator, StochasticRng,
+};
 
-use core::{fmt, num::ParseIntError, ops, str::FromStr};
+use core::{fmt, ops, str::FromStr};
 
 #[allow(unused_imports)]
 use num_traits::float::FloatCore; // used only for `no_std`
@@ -34,148 +37,1240 @@ where
     /// The static scale.
     pub const SCALE: i32 = S;
 
+    /// Checked addition with decimal of different inner integer `J` but
+    /// same scale. Computes `self + rhs`, returning `None` if the result
+    /// overflows `I`.
+    ///
+    /// Use `From` and `TryFrom` to convert between the inner integer types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// type Balance = ConstScaleFpdec<i64, 4>;
+    /// type Amount = ConstScaleFpdec<i32, 4>;
+    /// type Quantity = ConstScaleFpdec<u16, 4>;
+    ///
+    /// let balance: Balance = fpdec!(100);
+    /// let amount: Amount = fpdec!(2.5);
+    /// assert_eq!(balance.checked_add_from(amount), Some(fpdec!(102.5)));
+    /// assert_eq!(balance.checked_sub_from(amount), Some(fpdec!(97.5)));
+    /// assert_eq!(amount.checked_sub_from(balance), Some(fpdec!(-97.5)));
+    /// assert_eq!(amount.checked_add_from(Balance::MAX), None);
+    ///
+    /// // lossless widening, and narrowing with overflow check
+    /// let wide: Balance = amount.into();
+    /// assert_eq!(wide, fpdec!(2.5));
+    /// assert_eq!(Amount::try_from(wide), Ok(amount));
+    /// assert!(Amount::try_from(Balance::MAX).is_err());
+    /// assert_eq!(Quantity::try_from(wide), Ok(fpdec!(2.5)));
+    /// assert!(Quantity::try_from(-wide).is_err());
+    /// ```
+    #[must_use]
+    pub fn checked_add_from<J>(self, rhs: ConstScaleFpdec<J, S>) -> Option<Self>
+    where
+        J: FpdecInner,
+    {
+        checked_add_mixed(self.0, rhs.0, false).map(Self)
+    }
+
+    /// Checked subtraction with decimal of different inner integer `J` but
+    /// same scale. Computes `self - rhs`, returning `None` if the result
+    /// overflows `I`.
+    ///
+    /// See [`Self::checked_add_from`] for examples.
+    #[must_use]
+    pub fn checked_sub_from<J>(self, rhs: ConstScaleFpdec<J, S>) -> Option<Self>
+    where
+        J: FpdecInner,
+    {
+        checked_add_mixed(self.0, rhs.0, true).map(Self)
+    }
+
     /// Checked multiplication.
     ///
-    /// Equivalent to [`Self::checked_mul_ext`] with `Rounding::Round`.
+    /// Equivalent to [`Self::checked_mul_ext`] with `Rounding::Round`.
+    ///
+    /// If you make sure no overflow error, and the result type has the same
+    /// scale (`SR`=`S`), you can use `*` and `*=` instead for convenience.
+    #[must_use]
+    pub fn checked_mul<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> Option<ConstScaleFpdec<I, SR>>
+    where
+        J: FpdecInner,
+    {
+        self.checked_mul_ext(rhs, Rounding::Round)
+    }
+
+    /// Checked multiplication. Computes `self * rhs`, returning `None` if
+    /// overflow occurred or the scale difference `S + S2 - SR` is out of range
+    /// `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// The type of `rhs` can have different inner integer `J` and scale `S2`
+    /// with `self`. The type of result must have the same inner integer `I`
+    /// while have different scale `SR`.
+    ///
+    /// If the scale of the result's type `SR` is less than the sum of
+    /// scales of the two multiplicands `S + S2`, then rounding operations
+    /// are required and precision may be lost.
+    /// You can specify the rounding type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    /// type FeeRate = ConstScaleFpdec<i16, 4>; // different types
+    ///
+    /// let balance: Balance = fpdec!(12.30);
+    /// let rate: FeeRate = fpdec!(0.01);
+    ///
+    /// let fee: Balance = balance.checked_mul(rate).unwrap();
+    /// assert_eq!(fee, fpdec!(0.12));
+    ///
+    /// let fee: Balance = balance.checked_mul_ext(rate, Rounding::Ceiling).unwrap();
+    /// assert_eq!(fee, fpdec!(0.13));
+    /// ```
+    #[must_use]
+    pub fn checked_mul_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> Option<ConstScaleFpdec<I, SR>>
+    where
+        J: FpdecInner,
+    {
+        self.0
+            .checked_mul_ext(I::from(rhs.0)?, S + S2 - SR, rounding)
+            .map(ConstScaleFpdec)
+    }
+
+    /// Checked division.
+    ///
+    /// Equivalent to [`Self::checked_div_ext`] with `Rounding::Round`.
+    ///
+    /// If you make sure no overflow or 0-division error, and the result type
+    /// has the same scale (`SR`=`S`), you can use `/` and `/=` instead for
+    /// convenience.
+    #[must_use]
+    pub fn checked_div<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> Option<ConstScaleFpdec<I, SR>>
+    where
+        J: FpdecInner,
+    {
+        self.checked_div_ext(rhs, Rounding::Round)
+    }
+
+    /// Checked division. Computes `self / rhs`, returning `None` if
+    /// division by 0, or overflow occurred, or the scale difference
+    /// `S - S2 - SR` is out of range `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// The type of `rhs` can have different inner integer `J` and scale `S2`
+    /// with `self`. The type of result must have the same inner integer `I`
+    /// while have different scale `SR`.
+    ///
+    /// You can specify the rounding type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    /// type FeeRate = ConstScaleFpdec<i16, 4>; // different types
+    ///
+    /// let rate: FeeRate = fpdec!(0.03);
+    /// let fee: Balance = fpdec!(0.13);
+    ///
+    /// let balance: Balance = fee.checked_div_ext(rate, Rounding::Ceiling).unwrap();
+    /// assert_eq!(balance, fpdec!(4.34));
+    /// ```
+    #[must_use]
+    pub fn checked_div_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> Option<ConstScaleFpdec<I, SR>>
+    where
+        J: FpdecInner,
+    {
+        self.0
+            .checked_div_ext(I::from(rhs.0)?, S - S2 - SR, rounding)
+            .map(ConstScaleFpdec)
+    }
+
+    /// Multiplication returning [`ArithmeticError`] on failure.
+    ///
+    /// Equivalent to [`Self::try_mul_ext`] with `Rounding::Round`.
+    pub fn try_mul<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> Result<ConstScaleFpdec<I, SR>, ArithmeticError>
+    where
+        J: FpdecInner,
+    {
+        self.try_mul_ext(rhs, Rounding::Round)
+    }
+
+    /// Multiplication returning [`ArithmeticError`] on failure.
+    ///
+    /// Same with [`Self::checked_mul_ext`], but tells the reason of failure:
+    /// `Overflow` or `Underflow` by the sign of the exact result, or
+    /// `ScaleOutOfRange` if the scale difference `S + S2 - SR` is out of
+    /// range `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ArithmeticError, ConstScaleFpdec, Rounding, fpdec};
+    /// type Balance = ConstScaleFpdec<i32, 2>;
+    /// type FeeRate = ConstScaleFpdec<i16, 4>; // different types
+    ///
+    /// let balance: Balance = fpdec!(12.30);
+    /// let rate: FeeRate = fpdec!(0.01);
+    /// let fee: Balance = balance.try_mul_ext(rate, Rounding::Ceiling).unwrap();
+    /// assert_eq!(fee, fpdec!(0.13));
+    ///
+    /// let rate: FeeRate = fpdec!(-3);
+    /// let r: Result<Balance, _> = Balance::MAX.try_mul_ext(rate, Rounding::Ceiling);
+    /// assert_eq!(r, Err(ArithmeticError::Underflow));
+    /// ```
+    pub fn try_mul_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> Result<ConstScaleFpdec<I, SR>, ArithmeticError>
+    where
+        J: FpdecInner,
+    {
+        self.0
+            .try_mul_ext(try_into_inner(rhs.0)?, S + S2 - SR, rounding)
+            .map(ConstScaleFpdec)
+    }
+
+    /// Division returning [`ArithmeticError`] on failure.
+    ///
+    /// Equivalent to [`Self::try_div_ext`] with `Rounding::Round`.
+    pub fn try_div<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> Result<ConstScaleFpdec<I, SR>, ArithmeticError>
+    where
+        J: FpdecInner,
+    {
+        self.try_div_ext(rhs, Rounding::Round)
+    }
+
+    /// Division returning [`ArithmeticError`] on failure.
+    ///
+    /// Same with [`Self::checked_div_ext`], but tells the reason of failure:
+    /// `DivByZero`, `Overflow` or `Underflow` by the sign of the exact
+    /// result, or `ScaleOutOfRange` if the scale difference `S - S2 - SR`
+    /// is out of range `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ArithmeticError, ConstScaleFpdec, Rounding, fpdec};
+    /// type Balance = ConstScaleFpdec<u32, 2>;
+    /// type FeeRate = ConstScaleFpdec<u16, 4>;
+    ///
+    /// let fee: Balance = fpdec!(0.13);
+    /// let rate: FeeRate = fpdec!(0.03);
+    /// let balance: Balance = fee.try_div_ext(rate, Rounding::Ceiling).unwrap();
+    /// assert_eq!(balance, fpdec!(4.34));
+    ///
+    /// let rate: FeeRate = fpdec!(0);
+    /// let r: Result<Balance, _> = fee.try_div_ext(rate, Rounding::Ceiling);
+    /// assert_eq!(r, Err(ArithmeticError::DivByZero));
+    /// ```
+    pub fn try_div_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> Result<ConstScaleFpdec<I, SR>, ArithmeticError>
+    where
+        J: FpdecInner,
+    {
+        self.0
+            .try_div_ext(try_into_inner(rhs.0)?, S - S2 - SR, rounding)
+            .map(ConstScaleFpdec)
+    }
+
+    /// Saturating multiplication.
+    ///
+    /// Equivalent to [`Self::saturating_mul_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn saturating_mul<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> ConstScaleFpdec<I, SR>
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.saturating_mul_ext(rhs, Rounding::Round)
+    }
+
+    /// Saturating multiplication. Computes `self * rhs`, saturating at the
+    /// numeric bounds instead of overflowing.
+    ///
+    /// Same with [`Self::checked_mul_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly. It fails to
+    /// compile if the scale difference `S + S2 - SR` is out of range
+    /// `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Decimal = ConstScaleFpdec<i16, 2>;
+    ///
+    /// let a: Decimal = fpdec!(200);
+    /// let b: Decimal = fpdec!(-0.5);
+    /// let r: Decimal = a.saturating_mul(a);
+    /// assert_eq!(r, Decimal::MAX);
+    /// let r: Decimal = (-a).saturating_mul(a);
+    /// assert_eq!(r, Decimal::MIN);
+    /// let r: Decimal = a.saturating_mul(b);
+    /// assert_eq!(r, fpdec!(-100));
+    ///
+    /// // wrapping of the exact result 40000.00
+    /// let r: Decimal = a.wrapping_mul(a);
+    /// assert_eq!(r, fpdec!(23.04));
+    /// let r: (Decimal, bool) = a.overflowing_mul(a);
+    /// assert_eq!(r, (fpdec!(23.04), true));
+    /// let r: (Decimal, bool) = a.overflowing_mul(b);
+    /// assert_eq!(r, (fpdec!(-100), false));
+    ///
+    /// // rhs of other inner integer
+    /// let c: ConstScaleFpdec<i8, 0> = fpdec!(3);
+    /// let r: Decimal = a.saturating_mul(c);
+    /// assert_eq!(r, Decimal::MAX);
+    /// ```
+    ///
+    /// Scale difference out of range:
+    ///
+    /// ```compile_fail
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// type Decimal = ConstScaleFpdec<i16, 2>;
+    ///
+    /// let a: Decimal = fpdec!(2);
+    /// let r: ConstScaleFpdec<i16, -6> = a.saturating_mul(a);
+    /// ```
+    #[must_use]
+    pub fn saturating_mul_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> ConstScaleFpdec<I, SR>
+    where
+        J: FpdecInner + Into<I>,
+    {
+        const {
+            assert!(
+                (S + S2 - SR).unsigned_abs() <= I::DIGITS,
+                "scale difference out of range"
+            )
+        };
+        let rhs = rhs.0.into();
+        ConstScaleFpdec(self.0.saturating_mul_ext(rhs, S + S2 - SR, rounding))
+    }
+
+    /// Wrapping multiplication.
+    ///
+    /// Equivalent to [`Self::wrapping_mul_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn wrapping_mul<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> ConstScaleFpdec<I, SR>
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.wrapping_mul_ext(rhs, Rounding::Round)
+    }
+
+    /// Wrapping multiplication. Computes `self * rhs`, wrapping around at
+    /// the boundary of the type.
+    ///
+    /// Same with [`Self::checked_mul_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly. It fails to
+    /// compile if the scale difference `S + S2 - SR` is out of range
+    /// `[-Self::DIGITS, Self::DIGITS]`.
+    #[must_use]
+    pub fn wrapping_mul_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> ConstScaleFpdec<I, SR>
+    where
+        J: FpdecInner + Into<I>,
+    {
+        const {
+            assert!(
+                (S + S2 - SR).unsigned_abs() <= I::DIGITS,
+                "scale difference out of range"
+            )
+        };
+        let rhs = rhs.0.into();
+        ConstScaleFpdec(self.0.overflowing_mul_ext(rhs, S + S2 - SR, rounding).0)
+    }
+
+    /// Multiplication with overflow flag.
+    ///
+    /// Equivalent to [`Self::overflowing_mul_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn overflowing_mul<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> (ConstScaleFpdec<I, SR>, bool)
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.overflowing_mul_ext(rhs, Rounding::Round)
+    }
+
+    /// Multiplication with overflow flag. Computes `self * rhs`, returning
+    /// the wrapped result along with a boolean indicating whether overflow
+    /// occurred.
+    ///
+    /// Same with [`Self::checked_mul_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly. It fails to
+    /// compile if the scale difference `S + S2 - SR` is out of range
+    /// `[-Self::DIGITS, Self::DIGITS]`.
+    #[must_use]
+    pub fn overflowing_mul_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> (ConstScaleFpdec<I, SR>, bool)
+    where
+        J: FpdecInner + Into<I>,
+    {
+        const {
+            assert!(
+                (S + S2 - SR).unsigned_abs() <= I::DIGITS,
+                "scale difference out of range"
+            )
+        };
+        let rhs = rhs.0.into();
+        let (r, o) = self.0.overflowing_mul_ext(rhs, S + S2 - SR, rounding);
+        (ConstScaleFpdec(r), o)
+    }
+
+    /// Saturating division.
+    ///
+    /// Equivalent to [`Self::saturating_div_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn saturating_div<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> ConstScaleFpdec<I, SR>
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.saturating_div_ext(rhs, Rounding::Round)
+    }
+
+    /// Saturating division. Computes `self / rhs`, saturating at the
+    /// numeric bounds instead of overflowing.
+    ///
+    /// Same with [`Self::checked_div_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly. It fails to
+    /// compile if the scale difference `S - S2 - SR` is out of range
+    /// `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Decimal = ConstScaleFpdec<i16, 2>;
+    ///
+    /// let a: Decimal = fpdec!(200);
+    /// let b: Decimal = fpdec!(0.01);
+    /// let r: Decimal = a.saturating_div(b);
+    /// assert_eq!(r, Decimal::MAX);
+    /// let r: Decimal = (-a).saturating_div(b);
+    /// assert_eq!(r, Decimal::MIN);
+    /// let c: Decimal = fpdec!(3);
+    /// let r: Decimal = a.saturating_div_ext(c, Rounding::Floor);
+    /// assert_eq!(r, fpdec!(66.66));
+    ///
+    /// // wrapping of the exact result 20000.00
+    /// let r: Decimal = a.wrapping_div(b);
+    /// assert_eq!(r, fpdec!(-316.16));
+    /// let r: (Decimal, bool) = a.overflowing_div(b);
+    /// assert_eq!(r, (fpdec!(-316.16), true));
+    /// ```
+    #[must_use]
+    pub fn saturating_div_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> ConstScaleFpdec<I, SR>
+    where
+        J: FpdecInner + Into<I>,
+    {
+        const {
+            assert!(
+                (S - S2 - SR).unsigned_abs() <= I::DIGITS,
+                "scale difference out of range"
+            )
+        };
+        let rhs = rhs.0.into();
+        ConstScaleFpdec(self.0.saturating_div_ext(rhs, S - S2 - SR, rounding))
+    }
+
+    /// Wrapping division.
+    ///
+    /// Equivalent to [`Self::wrapping_div_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn wrapping_div<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> ConstScaleFpdec<I, SR>
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.wrapping_div_ext(rhs, Rounding::Round)
+    }
+
+    /// Wrapping division. Computes `self / rhs`, wrapping around at
+    /// the boundary of the type.
+    ///
+    /// Same with [`Self::checked_div_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly. It fails to
+    /// compile if the scale difference `S - S2 - SR` is out of range
+    /// `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    #[must_use]
+    pub fn wrapping_div_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> ConstScaleFpdec<I, SR>
+    where
+        J: FpdecInner + Into<I>,
+    {
+        const {
+            assert!(
+                (S - S2 - SR).unsigned_abs() <= I::DIGITS,
+                "scale difference out of range"
+            )
+        };
+        let rhs = rhs.0.into();
+        ConstScaleFpdec(self.0.overflowing_div_ext(rhs, S - S2 - SR, rounding).0)
+    }
+
+    /// Division with overflow flag.
+    ///
+    /// Equivalent to [`Self::overflowing_div_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn overflowing_div<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+    ) -> (ConstScaleFpdec<I, SR>, bool)
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.overflowing_div_ext(rhs, Rounding::Round)
+    }
+
+    /// Division with overflow flag. Computes `self / rhs`, returning
+    /// the wrapped result along with a boolean indicating whether overflow
+    /// occurred.
+    ///
+    /// Same with [`Self::checked_div_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly. It fails to
+    /// compile if the scale difference `S - S2 - SR` is out of range
+    /// `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    #[must_use]
+    pub fn overflowing_div_ext<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> (ConstScaleFpdec<I, SR>, bool)
+    where
+        J: FpdecInner + Into<I>,
+    {
+        const {
+            assert!(
+                (S - S2 - SR).unsigned_abs() <= I::DIGITS,
+                "scale difference out of range"
+            )
+        };
+        let rhs = rhs.0.into();
+        let (r, o) = self.0.overflowing_div_ext(rhs, S - S2 - SR, rounding);
+        (ConstScaleFpdec(r), o)
+    }
+
+    /// Checked multiplication with the remainder. Same with
+    /// [`Self::checked_mul_ext`], but returns the rounding remainder too,
+    /// where `self * rhs == result + remainder` exactly.
+    ///
+    /// The remainder has the scale of the exact product, so `SM` must be
+    /// `S + S2`, otherwise it fails to compile. It is zero if no rounding.
+    ///
+    /// For unsigned types, return `None` if the result is rounded up,
+    /// since the remainder is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    /// type FeeRate = ConstScaleFpdec<i16, 4>;
+    /// type Suspense = ConstScaleFpdec<i64, 6>; // scale 2 + 4
+    ///
+    /// let balance: Balance = fpdec!(12.34);
+    /// let rate: FeeRate = fpdec!(0.0125); // exact fee is 0.154250
+    ///
+    /// let (fee, rem): (Balance, Suspense) = balance.checked_mul_rem(rate, Rounding::Round).unwrap();
+    /// assert_eq!(fee, fpdec!(0.15));
+    /// assert_eq!(rem, fpdec!(0.00425));
+    ///
+    /// let (fee, rem): (Balance, Suspense) = balance.checked_mul_rem(rate, Rounding::Ceiling).unwrap();
+    /// assert_eq!(fee, fpdec!(0.16));
+    /// assert_eq!(rem, fpdec!(-0.00575));
+    /// ```
+    ///
+    /// Wrong scale of the remainder fails to compile:
+    ///
+    /// ```compile_fail
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    /// type FeeRate = ConstScaleFpdec<i16, 4>;
+    ///
+    /// let balance: Balance = fpdec!(12.34);
+    /// let rate: FeeRate = fpdec!(0.0125);
+    /// let (fee, rem): (Balance, Balance) = balance.checked_mul_rem(rate, Rounding::Round).unwrap();
+    /// ```
+    #[must_use]
+    pub fn checked_mul_rem<J, const S2: i32, const SR: i32, const SM: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> Option<(ConstScaleFpdec<I, SR>, ConstScaleFpdec<I, SM>)>
+    where
+        J: FpdecInner,
+    {
+        const { assert!(SM == S + S2, "scale of remainder must be S + S2") };
+        let (q, r) = self
+            .0
+            .checked_mul_rem(I::from(rhs.0)?, S + S2 - SR, rounding)?;
+        Some((ConstScaleFpdec(q), ConstScaleFpdec(r)))
+    }
+
+    /// Checked division with the remainder. Same with
+    /// [`Self::checked_div_ext`], but returns the rounding remainder too,
+    /// where `self == result * rhs + remainder` exactly.
+    ///
+    /// The remainder has the larger scale of `self` and `result * rhs`, so
+    /// `SM` must be `max(S, SR + S2)`, otherwise it fails to compile.
+    ///
+    /// For unsigned types, return `None` if the result is rounded up,
+    /// since the remainder is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    /// type FeeRate = ConstScaleFpdec<i16, 4>;
+    /// type Suspense = ConstScaleFpdec<i64, 6>; // max(2, 2 + 4)
+    ///
+    /// let rate: FeeRate = fpdec!(0.03);
+    /// let fee: Balance = fpdec!(0.13);
+    ///
+    /// let (balance, rem): (Balance, Suspense) = fee.checked_div_rem(rate, Rounding::Ceiling).unwrap();
+    /// assert_eq!(balance, fpdec!(4.34));
+    /// assert_eq!(rem, fpdec!(-0.0002)); // 0.13 == 4.34 * 0.03 - 0.0002
+    /// ```
+    #[must_use]
+    pub fn checked_div_rem<J, const S2: i32, const SR: i32, const SM: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+    ) -> Option<(ConstScaleFpdec<I, SR>, ConstScaleFpdec<I, SM>)>
+    where
+        J: FpdecInner,
+    {
+        const {
+            let max = if S > SR + S2 { S } else { SR + S2 };
+            assert!(SM == max, "scale of remainder must be max(S, SR + S2)");
+        };
+        let (q, r) = self
+            .0
+            .checked_div_rem(I::from(rhs.0)?, S - S2 - SR, rounding)?;
+        Some((ConstScaleFpdec(q), ConstScaleFpdec(r)))
+    }
+
+    /// Checked multiplication with [`RoundingAccumulator`]. Same with
+    /// [`Self::checked_mul_ext`], but adds the residue kept in `acc` before
+    /// rounding, and keeps the new residue in `acc`.
+    ///
+    /// Return `None` if overflow occurred, or `acc` has non-zero residue of
+    /// another divisor, i.e. different `S + S2 - SR` here. `acc` is not
+    /// changed in this case, so use one `acc` for each divisor, or
+    /// [`reset()`](RoundingAccumulator::reset) it before another. For
+    /// unsigned types, also return `None` if the residue is negative.
+    ///
+    /// See [`RoundingAccumulator`] for examples.
+    #[must_use]
+    pub fn checked_mul_acc<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+        acc: &mut RoundingAccumulator<I>,
+    ) -> Option<ConstScaleFpdec<I, SR>>
+    where
+        J: FpdecInner,
+    {
+        acc.checked_mul(self.0, I::from(rhs.0)?, S + S2 - SR, rounding)
+            .map(ConstScaleFpdec)
+    }
+
+    /// Checked division with [`RoundingAccumulator`]. Same with
+    /// [`Self::checked_div_ext`], but adds the residue kept in `acc` before
+    /// rounding, and keeps the new residue in `acc`.
+    ///
+    /// The divisor is `rhs`, times `10^(S - S2 - SR)` if `S > S2 + SR`.
+    /// The quotient is rounded once by the whole divisor, while
+    /// `checked_div_ext` rounds twice in this case, so they may differ in
+    /// the last unit.
+    ///
+    /// Return `None` if division by 0, overflow occurred, or `acc` has
+    /// non-zero residue of another divisor, i.e. different `rhs` or scales
+    /// here. `acc` is not changed in this case, so use one `acc` for each
+    /// `rhs`, or [`reset()`](RoundingAccumulator::reset) it before
+    /// another. For unsigned types, also return `None` if the residue is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, RoundingAccumulator, fpdec};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    ///
+    /// let mut acc = RoundingAccumulator::new();
+    /// let total: Balance = fpdec!(100);
+    /// let three: Balance = fpdec!(3);
+    /// let shares: [Balance; 3] = core::array::from_fn(|_| {
+    ///     total.checked_div_acc(three, Rounding::Round, &mut acc).unwrap()
+    /// });
+    /// assert_eq!(shares, [fpdec!(33.33), fpdec!(33.34), fpdec!(33.33)]);
+    /// assert!(acc.is_zero());
+    /// ```
+    #[must_use]
+    pub fn checked_div_acc<J, const S2: i32, const SR: i32>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rounding: Rounding,
+        acc: &mut RoundingAccumulator<I>,
+    ) -> Option<ConstScaleFpdec<I, SR>>
+    where
+        J: FpdecInner,
+    {
+        acc.checked_div(self.0, I::from(rhs.0)?, S - S2 - SR, rounding)
+            .map(ConstScaleFpdec)
+    }
+
+    /// Checked multiplication with stochastic rounding.
+    ///
+    /// Same with [`Self::checked_mul_ext`], but rounds away from zero with
+    /// probability equal to the fraction of the remainder, by the random
+    /// bits from `rng`. So the rounding errors do not bias the total in
+    /// large aggregation. The result is deterministic if `rng` is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, SplitMix64, fpdec};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    /// type FeeRate = ConstScaleFpdec<i16, 4>;
+    ///
+    /// let balance: Balance = fpdec!(0.50);
+    /// let rate: FeeRate = fpdec!(0.01); // fee is 0.005
+    ///
+    /// // `Rounding::Round` always rounds 0.005 up, which doubles the total
+    /// let total: Balance = (0..1000).map(|_| balance.checked_mul(rate).unwrap()).sum();
+    /// assert_eq!(total, fpdec!(10));
+    ///
+    /// // while the stochastic rounding is close to the exact total 5.00
+    /// let mut rng = SplitMix64::new(42);
+    /// let total: Balance = (0..1000)
+    ///     .map(|_| balance.checked_mul_stochastic(rate, &mut rng).unwrap())
+    ///     .sum();
+    /// assert!(total > fpdec!(4.5) && total < fpdec!(5.5));
+    /// ```
+    #[must_use]
+    pub fn checked_mul_stochastic<J, const S2: i32, const SR: i32, R>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rng: &mut R,
+    ) -> Option<ConstScaleFpdec<I, SR>>
+    where
+        J: FpdecInner,
+        R: StochasticRng + ?Sized,
+    {
+        self.0
+            .checked_mul_stochastic(I::from(rhs.0)?, S + S2 - SR, rng)
+            .map(ConstScaleFpdec)
+    }
+
+    /// Checked division with stochastic rounding.
+    ///
+    /// Same with [`Self::checked_div_ext`], but with stochastic rounding.
+    /// See [`Self::checked_mul_stochastic`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, SplitMix64, fpdec};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    ///
+    /// let mut rng = SplitMix64::new(42);
+    /// let a: Balance = fpdec!(1);
+    /// let b: Balance = fpdec!(3);
+    ///
+    /// // 0.333.. is rounded to 0.33 or 0.34
+    /// let q: Balance = a.checked_div_stochastic(b, &mut rng).unwrap();
+    /// assert!(q == fpdec!(0.33) || q == fpdec!(0.34));
+    /// ```
+    #[must_use]
+    pub fn checked_div_stochastic<J, const S2: i32, const SR: i32, R>(
+        self,
+        rhs: ConstScaleFpdec<J, S2>,
+        rng: &mut R,
+    ) -> Option<ConstScaleFpdec<I, SR>>
+    where
+        J: FpdecInner,
+        R: StochasticRng + ?Sized,
+    {
+        self.0
+            .checked_div_stochastic(I::from(rhs.0)?, S - S2 - SR, rng)
+            .map(ConstScaleFpdec)
+    }
+
+    /// Round the decimal at the specified scale.
+    ///
+    /// Equivalent to [`Self::round_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn round(self, scale: i32) -> Self {
+        self.round_ext(scale, Rounding::Round)
+    }
+
+    /// Round the decimal at the specified scale with rounding type.
+    ///
+    /// Return the original decimal if `scale >= S`.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Price = ConstScaleFpdec<i64, 8>;
+    ///
+    /// let price: Price = fpdec!(12.12345678);
+    ///
+    /// assert_eq!(price.round(6), fpdec!(12.123457)); // `Rounding::Round` as default
+    ///
+    /// assert_eq!(price.round_ext(6, Rounding::Floor), fpdec!(12.123456));
+    /// ```
+    #[must_use]
+    pub fn round_ext(self, scale: i32, rounding: Rounding) -> Self {
+        Self(self.0.round_diff_with_rounding(S - scale, rounding))
+    }
+
+    /// Convert to another scale `S2`, with `Rounding::Round` if `S2 < S`.
+    ///
+    /// Equivalent to [`Self::rescale_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn rescale<const S2: i32>(self) -> Option<ConstScaleFpdec<I, S2>> {
+        self.rescale_ext(Rounding::Round)
+    }
+
+    /// Convert to another scale `S2`, with rounding if `S2 < S`. Returning
+    /// `None` if overflow occurred.
+    ///
+    /// See [`Self::try_rescale`] if you want to keep the precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Price = ConstScaleFpdec<i64, 8>;
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    /// type Small = ConstScaleFpdec<i16, 4>;
+    ///
+    /// let price: Price = fpdec!(12.34567);
+    /// assert_eq!(price.rescale(), Some(fpdec!(12.35) as Balance));
+    /// assert_eq!(price.rescale_ext(Rounding::Floor), Some(fpdec!(12.34) as Balance));
+    ///
+    /// // to bigger scale
+    /// let balance: Balance = fpdec!(12.34);
+    /// assert_eq!(balance.rescale(), Some(fpdec!(12.34) as Price));
+    /// assert_eq!(balance.rescale::<4>(), Some(fpdec!(12.34) as ConstScaleFpdec<i64, 4>));
+    /// let small: Small = fpdec!(1.5);
+    /// assert_eq!(small.rescale::<5>(), None); // overflow
+    /// ```
+    #[must_use]
+    pub fn rescale_ext<const S2: i32>(self, rounding: Rounding) -> Option<ConstScaleFpdec<I, S2>> {
+        self.0
+            .checked_rescale(S.saturating_sub(S2), rounding)
+            .map(ConstScaleFpdec)
+    }
+
+    /// Convert to another scale `S2` without rounding.
+    ///
+    /// Return error of `ParseErrorKind::Overflow` if overflow occurred (to
+    /// bigger scale), or `ParseErrorKind::Precision` if precision lost (to
+    /// smaller scale).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseErrorKind, fpdec};
+    /// type Price = ConstScaleFpdec<i64, 8>;
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    ///
+    /// let price: Price = fpdec!(12.34);
+    /// assert_eq!(price.try_rescale(), Ok(fpdec!(12.34) as Balance));
+    ///
+    /// let price: Price = fpdec!(12.34567);
+    /// let r: Result<Balance, _> = price.try_rescale();
+    /// assert_eq!(r.map_err(|e| e.kind()), Err(ParseErrorKind::Precision(3)));
+    ///
+    /// let r: Result<Price, _> = Balance::MAX.try_rescale();
+    /// assert_eq!(r.map_err(|e| e.kind()), Err(ParseErrorKind::Overflow));
+    /// ```
+    pub fn try_rescale<const S2: i32>(self) -> Result<ConstScaleFpdec<I, S2>, ParseError> {
+        self.0
+            .try_rescale(S.saturating_sub(S2))
+            .map(ConstScaleFpdec)
+    }
+
+    /// Read decimal from string with rounding.
+    ///
+    /// Compared to [`FromStr`], this method rounds the extra fraction
+    /// digits by the rounding type, instead of returning `ParseErrorKind::Precision`.
+    /// The string can have any number of fraction digits.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseErrorKind, Rounding, fpdec};
+    /// type Decimal = ConstScaleFpdec<i16, 4>;
+    ///
+    /// assert_eq!(Decimal::from_str_ext("1.23456", Rounding::Round), Ok(fpdec!(1.2346)));
+    /// assert_eq!(Decimal::from_str_ext("1.23456", Rounding::Floor), Ok(fpdec!(1.2345)));
+    /// assert_eq!(Decimal::from_str_ext("1.5e-7", Rounding::Ceiling), Ok(fpdec!(0.0001)));
+    /// let res = Decimal::from_str_ext("9999", Rounding::Round);
+    /// assert_eq!(res.unwrap_err().kind(), ParseErrorKind::IntOverflow);
+    /// ```
+    pub fn from_str_ext(s: &str, rounding: Rounding) -> Result<Self, ParseError> {
+        I::try_from_str_ext(s, S, rounding).map(Self)
+    }
+
+    /// Convert from `f64` exactly, with rounding.
+    ///
+    /// Compared to `TryFrom<f64>`, which multiplies the float by `10^S` in
+    /// binary, this method converts through the shortest decimal
+    /// representation of the float that round-trips, e.g. `0.1` for
+    /// `0.1_f64`, and then rounds it into the scale `S` by the rounding
+    /// type, like [`Self::from_str_ext`].
+    /// So there is no error from the float arithmetic, for any scale and
+    /// magnitude.
+    ///
+    /// Return error of `ParseErrorKind::Invalid` if the float is NaN,
+    /// `ParseErrorKind::Overflow` if it's infinite, or the kind of
+    /// [`Self::from_str_ext`] if overflow occurred.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseErrorKind, Rounding, fpdec};
+    /// type Decimal = ConstScaleFpdec<i64, 17>;
+    ///
+    /// // 0.7 * 1e17 in binary is 69999999999999992
+    /// assert_eq!(Decimal::try_from(0.7).unwrap().mantissa(), 69999999999999992);
+    /// assert_eq!(Decimal::from_f64_ext(0.7, Rounding::Round).unwrap().mantissa(), 70000000000000000);
+    ///
+    /// type Price = ConstScaleFpdec<i64, 2>;
+    /// assert_eq!(Price::from_f64_ext(1.005, Rounding::Round), Ok(fpdec!(1.01)));
+    /// assert_eq!(Price::from_f64_ext(1.005, Rounding::Floor), Ok(fpdec!(1.00)));
+    /// assert_eq!(Price::from_f64_ext(1e-300, Rounding::Ceiling), Ok(fpdec!(0.01)));
+    /// assert_eq!(Price::from_f64_ext(1e300, Rounding::Round).unwrap_err().kind(), ParseErrorKind::IntOverflow);
+    /// ```
+    pub fn from_f64_ext(f: f64, rounding: Rounding) -> Result<Self, ParseError> {
+        I::try_from_float_ext(f, S, rounding).map(Self)
+    }
+
+    /// Convert from `f32` exactly, with rounding.
     ///
-    /// If you make sure no overflow error, and the result type has the same
-    /// scale (`SR`=`S`), you can use `*` and `*=` instead for convenience.
-    #[must_use]
-    pub fn checked_mul<J, const S2: i32, const SR: i32>(
-        self,
-        rhs: ConstScaleFpdec<J, S2>,
-    ) -> Option<ConstScaleFpdec<I, SR>>
-    where
-        J: FpdecInner,
-    {
-        self.checked_mul_ext(rhs, Rounding::Round)
+    /// Same with [`Self::from_f64_ext`] but for `f32`, whose shortest
+    /// representation has fewer digits, e.g. `0.1` for `0.1_f32`.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+    /// type Decimal = ConstScaleFpdec<i64, 12>;
+    ///
+    /// assert_eq!(Decimal::from_f32_ext(0.1, Rounding::Round), Ok(fpdec!(0.1)));
+    /// assert_eq!(Decimal::from_f32_ext(-3.14159, Rounding::Round), Ok(fpdec!(-3.14159)));
+    /// ```
+    pub fn from_f32_ext(f: f32, rounding: Rounding) -> Result<Self, ParseError> {
+        I::try_from_float_ext(f, S, rounding).map(Self)
     }
 
-    /// Checked multiplication. Computes `self * rhs`, returning `None` if
-    /// overflow occurred or the scale difference `S + S2 - SR` is out of range
-    /// `[-Self::DIGITS, Self::DIGITS]`.
+    /// Read decimal from string with parsing options.
     ///
-    /// The type of `rhs` can have different inner integer `J` and scale `S2`
-    /// with `self`. The type of result must have the same inner integer `I`
-    /// while have different scale `SR`.
+    /// See [`ParseOptions`] for the options. Return error of
+    /// `ParseErrorKind::Precision` if the string has more precision
+    /// than `S`.
     ///
-    /// If the scale of the result's type `SR` is less than the sum of
-    /// scales of the two multiplicands `S + S2`, then rounding operations
-    /// are required and precision may be lost.
-    /// You can specify the rounding type.
+    /// Examples:
     ///
-    /// # Examples
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseOptions, fpdec};
+    /// type Decimal = ConstScaleFpdec<i32, 2>;
+    ///
+    /// let opts = ParseOptions { separator: Some(b'_'), ..ParseOptions::DEFAULT };
+    /// assert_eq!(Decimal::from_str_opts("1_000.25", opts), Ok(fpdec!(1000.25)));
     ///
+    /// let opts = ParseOptions { allow_bare_point: true, ..ParseOptions::DEFAULT };
+    /// assert_eq!(Decimal::from_str_opts(".5", opts), Ok(fpdec!(0.5)));
+    /// assert_eq!(Decimal::from_str_opts("5.", opts), Ok(fpdec!(5)));
     /// ```
-    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
-    /// type Balance = ConstScaleFpdec<i64, 2>;
-    /// type FeeRate = ConstScaleFpdec<i16, 4>; // different types
+    pub fn from_str_opts(s: &str, opts: ParseOptions) -> Result<Self, ParseError> {
+        I::try_from_str_opts(s, S, None, opts).map(Self)
+    }
+
+    /// Read decimal from ASCII bytes.
     ///
-    /// let balance: Balance = fpdec!(12.30);
-    /// let rate: FeeRate = fpdec!(0.01);
+    /// This is the same as [`FromStr`] but without the UTF-8 validation,
+    /// which is useful for binary protocol decoders. Non-ASCII bytes are
+    /// invalid, and the offset in the error is the byte offset.
     ///
-    /// let fee: Balance = balance.checked_mul(rate).unwrap();
-    /// assert_eq!(fee, fpdec!(0.12));
+    /// Examples:
     ///
-    /// let fee: Balance = balance.checked_mul_ext(rate, Rounding::Ceiling).unwrap();
-    /// assert_eq!(fee, fpdec!(0.13));
     /// ```
-    #[must_use]
-    pub fn checked_mul_ext<J, const S2: i32, const SR: i32>(
-        self,
-        rhs: ConstScaleFpdec<J, S2>,
-        rounding: Rounding,
-    ) -> Option<ConstScaleFpdec<I, SR>>
-    where
-        J: FpdecInner,
-    {
-        self.0
-            .checked_mul_ext(I::from(rhs.0)?, S + S2 - SR, rounding)
-            .map(ConstScaleFpdec)
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseErrorKind, fpdec};
+    /// type Decimal = ConstScaleFpdec<i32, 2>;
+    ///
+    /// assert_eq!(Decimal::from_ascii(b"12.34"), Ok(fpdec!(12.34)));
+    /// assert_eq!(Decimal::from_ascii(b"1.5e2"), Ok(fpdec!(150)));
+    ///
+    /// let err = Decimal::from_ascii(b"12.3\xff").unwrap_err();
+    /// assert_eq!(err.kind(), ParseErrorKind::Invalid);
+    /// assert_eq!(err.offset(), Some(4));
+    /// ```
+    pub fn from_ascii(s: &[u8]) -> Result<Self, ParseError> {
+        I::try_from_ascii(s, S).map(Self)
     }
 
-    /// Checked division.
+    /// Write the decimal into the buffer, and return the length.
     ///
-    /// Equivalent to [`Self::checked_div_ext`] with `Rounding::Round`.
+    /// The output is the same as [`Display`](fmt::Display) with the
+    /// precision only, but much faster for it does not go through the
+    /// `core::fmt` machinery. The decimal is written at the beginning of
+    /// the buffer.
     ///
-    /// If you make sure no overflow or 0-division error, and the result type
-    /// has the same scale (`SR`=`S`), you can use `/` and `/=` instead for
-    /// convenience.
-    #[must_use]
-    pub fn checked_div<J, const S2: i32, const SR: i32>(
-        self,
-        rhs: ConstScaleFpdec<J, S2>,
-    ) -> Option<ConstScaleFpdec<I, SR>>
-    where
-        J: FpdecInner,
-    {
-        self.checked_div_ext(rhs, Rounding::Round)
+    /// Panics:
+    ///
+    /// If the buffer is too small. See [`Self::try_write_to`] for the
+    /// non-panicking version.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// type Decimal = ConstScaleFpdec<i32, 4>;
+    ///
+    /// let d: Decimal = fpdec!(-12.347);
+    /// let mut buf = [0; 32];
+    ///
+    /// let len = d.write_to(&mut buf, None);
+    /// assert_eq!(&buf[..len], b"-12.347");
+    ///
+    /// let len = d.write_to(&mut buf, Some(2));
+    /// assert_eq!(&buf[..len], b"-12.35");
+    /// ```
+    pub fn write_to(self, buf: &mut [u8], precision: Option<usize>) -> usize {
+        self.try_write_to(buf, precision)
+            .expect("buffer too small for the decimal")
     }
 
-    /// Checked division. Computes `self / rhs`, returning `None` if
-    /// division by 0, or overflow occurred, or the scale difference
-    /// `S - S2 - SR` is out of range `[-Self::DIGITS, Self::DIGITS]`.
+    /// Write the decimal into the buffer, and return the length.
     ///
-    /// The type of `rhs` can have different inner integer `J` and scale `S2`
-    /// with `self`. The type of result must have the same inner integer `I`
-    /// while have different scale `SR`.
+    /// Same with [`Self::write_to`], but return `None` if the buffer is
+    /// too small, and the buffer is not changed then.
     ///
-    /// You can specify the rounding type.
+    /// Examples:
     ///
-    /// # Examples
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// type Decimal = ConstScaleFpdec<i32, 4>;
+    ///
+    /// let d: Decimal = fpdec!(-12.347);
+    /// let mut buf = [0; 6];
+    ///
+    /// assert_eq!(d.try_write_to(&mut buf, None), None);
+    /// assert_eq!(d.try_write_to(&mut buf, Some(2)), Some(6));
+    /// assert_eq!(&buf, b"-12.35");
+    /// ```
+    pub fn try_write_to(self, buf: &mut [u8], precision: Option<usize>) -> Option<usize> {
+        self.try_write_to_ext(buf, precision, Rounding::Round)
+    }
+
+    /// Write the decimal into the buffer with rounding, and return the
+    /// length.
+    ///
+    /// Same with [`Self::write_to`], but the number is rounded by the
+    /// rounding type if the precision is smaller.
+    ///
+    /// Panics:
+    ///
+    /// If the buffer is too small. See [`Self::try_write_to_ext`] for the
+    /// non-panicking version.
+    ///
+    /// Examples:
     ///
     /// ```
     /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
-    /// type Balance = ConstScaleFpdec<i64, 2>;
-    /// type FeeRate = ConstScaleFpdec<i16, 4>; // different types
+    /// type Decimal = ConstScaleFpdec<i32, 4>;
     ///
-    /// let rate: FeeRate = fpdec!(0.03);
-    /// let fee: Balance = fpdec!(0.13);
+    /// let d: Decimal = fpdec!(-12.345);
+    /// let mut buf = [0; 32];
     ///
-    /// let balance: Balance = fee.checked_div_ext(rate, Rounding::Ceiling).unwrap();
-    /// assert_eq!(balance, fpdec!(4.34));
+    /// let len = d.write_to_ext(&mut buf, Some(2), Rounding::HalfEven);
+    /// assert_eq!(&buf[..len], b"-12.34");
+    ///
+    /// let len = d.write_to_ext(&mut buf, Some(2), Rounding::Floor);
+    /// assert_eq!(&buf[..len], b"-12.35");
     /// ```
-    #[must_use]
-    pub fn checked_div_ext<J, const S2: i32, const SR: i32>(
+    pub fn write_to_ext(
         self,
-        rhs: ConstScaleFpdec<J, S2>,
+        buf: &mut [u8],
+        precision: Option<usize>,
         rounding: Rounding,
-    ) -> Option<ConstScaleFpdec<I, SR>>
-    where
-        J: FpdecInner,
-    {
-        self.0
-            .checked_div_ext(I::from(rhs.0)?, S - S2 - SR, rounding)
-            .map(ConstScaleFpdec)
+    ) -> usize {
+        self.try_write_to_ext(buf, precision, rounding)
+            .expect("buffer too small for the decimal")
     }
 
-    /// Round the decimal at the specified scale.
+    /// Write the decimal into the buffer with rounding, and return the
+    /// length.
     ///
-    /// Equivalent to [`Self::round_ext`] with `Rounding::Round`.
-    #[must_use]
-    pub fn round(self, scale: i32) -> Self {
-        self.round_ext(scale, Rounding::Round)
+    /// Same with [`Self::write_to_ext`], but return `None` if the buffer
+    /// is too small, and the buffer is not changed then.
+    pub fn try_write_to_ext(
+        self,
+        buf: &mut [u8],
+        precision: Option<usize>,
+        rounding: Rounding,
+    ) -> Option<usize> {
+        self.0.try_write_to(S, precision, rounding, buf)
     }
 
-    /// Round the decimal at the specified scale with rounding type.
+    /// Write the decimal into a stack buffer, like [`Self::write_to`].
     ///
-    /// Return the original decimal if `scale >= S`.
+    /// Return `None` if the output is longer than [`FmtBuffer::CAPACITY`].
     ///
     /// Examples:
     ///
     /// ```
-    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
-    /// type Price = ConstScaleFpdec<i64, 8>;
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// type Decimal = ConstScaleFpdec<i32, 4>;
     ///
-    /// let price: Price = fpdec!(12.12345678);
+    /// let d: Decimal = fpdec!(-12.347);
+    /// assert_eq!(d.to_buffer(None).unwrap().as_str(), "-12.347");
+    /// assert_eq!(&*d.to_buffer(Some(2)).unwrap(), "-12.35");
+    /// assert!(d.to_buffer(Some(200)).is_none());
+    /// ```
+    pub fn to_buffer(self, precision: Option<usize>) -> Option<FmtBuffer> {
+        FmtBuffer::build(|buf| self.try_write_to(buf, precision))
+    }
+
+    /// Display with [`NumberFormat`], which sets the grouping separator
+    /// and decimal mark.
     ///
-    /// assert_eq!(price.round(6), fpdec!(12.123457)); // `Rounding::Round` as default
+    /// The returned [`DisplayWith`] supports the same formatting options
+    /// as `Display`.
+    ///
+    /// Examples:
     ///
-    /// assert_eq!(price.round_ext(6, Rounding::Floor), fpdec!(12.123456));
     /// ```
-    #[must_use]
-    pub fn round_ext(self, scale: i32, rounding: Rounding) -> Self {
-        Self(self.0.round_diff_with_rounding(S - scale, rounding))
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, NumberFormat, fpdec};
+    /// type Decimal = ConstScaleFpdec<i64, 2>;
+    ///
+    /// let d: Decimal = fpdec!(1234567.89);
+    /// assert_eq!(format!("{}", d.display_with(&NumberFormat::EN)), "1,234,567.89");
+    /// assert_eq!(format!("{:.0}", d.display_with(&NumberFormat::DE)), "1.234.568");
+    /// assert_eq!(format!("{:*>+15}", d.display_with(&NumberFormat::IN)), "**+12,34,567.89");
+    /// ```
+    pub fn display_with(self, nf: &NumberFormat) -> DisplayWith<'_, I> {
+        OobFmt(OobScaleFpdec::from_mantissa(self.0), S).display_with(nf)
+    }
+
+    /// Wrap into [`EngFmt`] to display in engineering notation, whose
+    /// exponent is multiple of 3, e.g. `12.345e3`.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// type Decimal = ConstScaleFpdec<i64, 12>;
+    ///
+    /// let d: Decimal = fpdec!(0.000004658);
+    /// assert_eq!(format!("{:e}", d.eng()), "4.658e-6");
+    ///
+    /// let d: Decimal = fpdec!(1234567.8);
+    /// assert_eq!(format!("{:e}", d.eng()), "1.2345678e6");
+    /// assert_eq!(format!("{:.1E}", d.eng()), "1.2E6");
+    ///
+    /// let d: Decimal = fpdec!(-12345.678);
+    /// assert_eq!(format!("{}", d.eng()), "-12.345678e3");
+    /// ```
+    pub fn eng(self) -> EngFmt<I> {
+        EngFmt(OobScaleFpdec::from_mantissa(self.0), S)
+    }
+
+    /// Read decimal from string with rounding and parsing options.
+    ///
+    /// This is the combination of [`Self::from_str_ext`] and
+    /// [`Self::from_str_opts`].
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseOptions, Rounding, fpdec};
+    /// type Decimal = ConstScaleFpdec<i32, 2>;
+    ///
+    /// let opts = ParseOptions { trim_whitespace: true, ..ParseOptions::DEFAULT };
+    /// let res = Decimal::from_str_ext_opts(" 1.255\n", Rounding::Floor, opts);
+    /// assert_eq!(res, Ok(fpdec!(1.25)));
+    /// ```
+    pub fn from_str_ext_opts(
+        s: &str,
+        rounding: Rounding,
+        opts: ParseOptions,
+    ) -> Result<Self, ParseError> {
+        I::try_from_str_opts(s, S, Some(rounding), opts).map(Self)
     }
 }
 
@@ -200,8 +1295,12 @@ where
 /// It supports some [formatting options](https://doc.rust-lang.org/std/fmt/index.html#formatting-parameters):
 /// width, fill, alignment, precision, sign and 0-fill.
 ///
-/// **Panic**: if the scale `S` is too big (>200 or <-200) or the specified
-/// precision is too big (>200).
+/// Any scale and precision are supported. The long zero paddings of big
+/// scales or precisions are written in chunks, without big buffer.
+///
+/// The number is rounded by [`Rounding::Round`] if the precision is smaller
+/// than the scale. Use `display_with()` with [`NumberFormat::rounding`] or
+/// `write_to_ext()` for other rounding kinds.
 ///
 /// Examples:
 ///
@@ -225,29 +1324,92 @@ where
     }
 }
 
+/// Display the decimal in scientific notation, e.g. `1.2345e3`.
+///
+/// The output is exact from the mantissa and scale, without any float
+/// conversion. The tailing zeros are omitted if no precision is set.
+/// The number is rounded by [`Rounding::Round`] if the precision is smaller.
+///
+/// It supports the same formatting options as [`Display`](fmt::Display).
+/// See [`ConstScaleFpdec::eng`] for engineering notation.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+/// type Decimal = ConstScaleFpdec<i64, 12>;
+///
+/// assert_eq!(format!("{:e}", fpdec!(1200) as Decimal), "1.2e3");
+/// assert_eq!(format!("{:e}", fpdec!(0.000004658) as Decimal), "4.658e-6");
+/// assert_eq!(format!("{:.2e}", fpdec!(-0.000004658) as Decimal), "-4.66e-6");
+/// assert_eq!(format!("{:.2e}", fpdec!(9.999) as Decimal), "1.00e1");
+/// assert_eq!(format!("{:e}", Decimal::ZERO), "0e0");
+/// ```
+impl<I, const S: i32> fmt::LowerExp for ConstScaleFpdec<I, S>
+where
+    I: FpdecInner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0.display_exp_fmt(S, false, false, f)
+    }
+}
+
+/// Display the decimal in scientific notation with upper case `E`,
+/// e.g. `1.2345E3`.
+///
+/// See [`LowerExp`](fmt::LowerExp) for details.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+/// type Decimal = ConstScaleFpdec<i64, 12>;
+///
+/// assert_eq!(format!("{:E}", fpdec!(0.000004658) as Decimal), "4.658E-6");
+/// assert_eq!(format!("{:+.1E}", fpdec!(123.45) as Decimal), "+1.2E2");
+/// ```
+impl<I, const S: i32> fmt::UpperExp for ConstScaleFpdec<I, S>
+where
+    I: FpdecInner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0.display_exp_fmt(S, false, true, f)
+    }
+}
+
 /// Read decimal from string.
 ///
-/// This method has 2 limitations:
-/// 1. Support decimal format only but not scientific notation;
-/// 2. Return `ParseError::Precision` if the string has more precision than `S`.
+/// Both decimal format (e.g. `123.45`) and scientific notation (e.g.
+/// `1.2345e2`) are supported. The exponent is applied exactly, without
+/// any float conversion.
 ///
-/// If you want to skip these limitations, you can parse the string
-/// to float number first and then convert the number to this decimal.
+/// Return error of `ParseErrorKind::Precision` if the string has more
+/// precision than `S`.
+/// Use [`ConstScaleFpdec::from_str_ext`] if you want to round it instead.
 ///
 /// Examples:
 ///
 /// ```
 /// use core::str::FromStr;
-/// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseError};
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseErrorKind};
 /// type Decimal = ConstScaleFpdec<i16, 4>;
 ///
 /// assert_eq!(Decimal::from_str("1.23"), Decimal::try_from(1.23));
-/// assert_eq!(Decimal::from_str("9999"), Err(ParseError::Overflow));
-/// assert_eq!(Decimal::from_str("1.23456"), Err(ParseError::Precision));
+/// assert_eq!(Decimal::from_str("1.5e-3"), Decimal::try_from(0.0015));
+/// assert_eq!(Decimal::from_str("3E+0"), Decimal::try_from(3));
+/// assert_eq!(Decimal::from_str("9999").map_err(|e| e.kind()), Err(ParseErrorKind::IntOverflow));
+/// assert_eq!(Decimal::from_str("1e4").map_err(|e| e.kind()), Err(ParseErrorKind::IntOverflow));
+/// assert_eq!(Decimal::from_str("1.23456").map_err(|e| e.kind()), Err(ParseErrorKind::Precision(1)));
+/// assert_eq!(Decimal::from_str("1.5e-7").map_err(|e| e.kind()), Err(ParseErrorKind::Precision(4)));
+///
+/// // the error tells where it is
+/// let err = Decimal::from_str("1.2x").unwrap_err();
+/// assert_eq!(err.kind(), ParseErrorKind::Invalid);
+/// assert_eq!(err.offset(), Some(3));
 /// ```
 impl<I, const S: i32> FromStr for ConstScaleFpdec<I, S>
 where
-    I: FpdecInner + Num<FromStrRadixErr = ParseIntError>,
+    I: FpdecInner,
 {
     type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, ParseError> {
@@ -277,6 +1439,31 @@ where
     }
 }
 
+macro_rules! convert_between_inner {
+    (From, $from:ty => $($into:ty),*) => {$(
+        impl<const S: i32> From<ConstScaleFpdec<$from, S>> for ConstScaleFpdec<$into, S> {
+            /// Convert from decimal of narrower inner integer, losslessly.
+            fn from(d: ConstScaleFpdec<$from, S>) -> Self {
+                Self(d.0.into())
+            }
+        }
+    )*};
+    (TryFrom, $from:ty => $($into:ty),*) => {$(
+        impl<const S: i32> TryFrom<ConstScaleFpdec<$from, S>> for ConstScaleFpdec<$into, S> {
+            type Error = ParseError;
+
+            /// Convert from decimal of other inner integer. Returning error
+            /// of `ParseErrorKind::Overflow` if out of range.
+            fn try_from(d: ConstScaleFpdec<$from, S>) -> Result<Self, Self::Error> {
+                <$into>::try_from(d.0)
+                    .map(Self)
+                    .map_err(|_| ParseErrorKind::Overflow.into())
+            }
+        }
+    )*};
+}
+crate::none_scale_common::for_each_inner_pair!(convert_between_inner);
+
 macro_rules! convert_from_int {
     ($from_int_type:ty) => {
         impl<I, const S: i32> TryFrom<$from_int_type> for ConstScaleFpdec<I, S>
@@ -292,25 +1479,25 @@ macro_rules! convert_from_int {
             ///
             /// ```
             /// use core::str::FromStr;
-            /// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseError};
+            /// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseError, ParseErrorKind};
             /// type Decimal = ConstScaleFpdec<i32, 6>;
             /// type NegPrec = ConstScaleFpdec<i16, -6>;
             ///
             /// assert_eq!(Decimal::try_from(123).unwrap(), Decimal::from_str("123").unwrap());
             /// assert_eq!(Decimal::try_from(123_i8).unwrap(), Decimal::from_str("123").unwrap());
             /// assert_eq!(NegPrec::try_from(12000000).unwrap(), NegPrec::from_str("12000000").unwrap());
-            /// assert_eq!(Decimal::try_from(9999999), Err(ParseError::Overflow));
-            /// assert_eq!(NegPrec::try_from(123), Err(ParseError::Precision));
+            /// assert_eq!(Decimal::try_from(9999999), Err(ParseError::from(ParseErrorKind::Overflow)));
+            /// assert_eq!(NegPrec::try_from(123), Err(ParseError::from(ParseErrorKind::Precision(6))));
             /// ```
             fn try_from(i: $from_int_type) -> Result<Self, Self::Error> {
                 if S > 0 {
                     // convert from type i to I first
-                    let i2 = I::from(i).ok_or(ParseError::Overflow)?;
+                    let i2 = I::from(i).ok_or(ParseErrorKind::Overflow)?;
                     I::checked_from_int(i2, S).map(Self)
                 } else {
                     // convert to fpdec inner first
                     let i2 = i.checked_from_int(S)?;
-                    I::from(i2).ok_or(ParseError::Overflow).map(Self)
+                    I::from(i2).ok_or(ParseErrorKind::Overflow.into()).map(Self)
                 }
             }
         }
@@ -339,7 +1526,9 @@ macro_rules! convert_from_float {
             ///
             /// Since it's hard for the float types to represent decimal fraction
             /// exactly, so this method always rounds the float number into
-            /// ConstScaleFpdec.
+            /// ConstScaleFpdec. The multiplication by `10^S` is in float, so
+            /// the result may be off for big scales or magnitudes. See
+            /// [`ConstScaleFpdec::from_f64_ext`] for exact conversion.
             ///
             /// Examples:
             ///
@@ -361,7 +1550,7 @@ macro_rules! convert_from_float {
                 };
                 I::$from_fn(inner_f.round())
                     .map(Self)
-                    .ok_or(ParseError::Overflow)
+                    .ok_or(ParseErrorKind::Overflow.into())
             }
         }
 
@@ -645,7 +1834,7 @@ where
 #[cfg(feature = "serde")]
 impl<'de, I, const S: i32> Deserialize<'de> for ConstScaleFpdec<I, S>
 where
-    I: FromPrimitive + FpdecInner + Num<FromStrRadixErr = ParseIntError>,
+    I: FromPrimitive + FpdecInner,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -667,7 +1856,7 @@ where
 
         impl<'de, I, const S: i32> Visitor<'de> for ConstScaleFpdecVistor<I, S>
         where
-            I: FromPrimitive + FpdecInner + Num<FromStrRadixErr = ParseIntError>,
+            I: FromPrimitive + FpdecInner,
         {
             type Value = ConstScaleFpdec<I, S>;
 
@@ -894,7 +2083,10 @@ mod tests {
     #[test]
     fn test_from_int() {
         assert_eq!(Dec32p2::try_from(1_i16).unwrap().mantissa(), 100);
-        assert_eq!(Dec32p2::try_from(i32::MAX), Err(ParseError::Overflow));
+        assert_eq!(
+            Dec32p2::try_from(i32::MAX),
+            Err(ParseErrorKind::Overflow.into())
+        );
 
         // avoid overflow for: i16::MAX * 100
         assert_eq!(
@@ -909,10 +2101,97 @@ mod tests {
         );
 
         // overflow
-        assert_eq!(Dec32p2::try_from(i32::MAX), Err(ParseError::Overflow));
+        assert_eq!(
+            Dec32p2::try_from(i32::MAX),
+            Err(ParseErrorKind::Overflow.into())
+        );
         assert_eq!(
             Dec32n2::try_from(i32::MAX as i64 * 1000),
-            Err(ParseError::Overflow)
+            Err(ParseErrorKind::Overflow.into())
+        );
+    }
+
+    #[test]
+    fn test_from_float_ext() {
+        for r in Rounding::ALL {
+            assert_eq!(Dec32p2::from_f64_ext(0.0, r), Ok(fpdec!(0)));
+            assert_eq!(Dec32p2::from_f64_ext(-0.0, r), Ok(fpdec!(0)));
+            assert_eq!(Dec32p2::from_f64_ext(3.14, r), Ok(fpdec!(3.14)));
+            assert_eq!(Dec32p2::from_f32_ext(-3.14, r), Ok(fpdec!(-3.14)));
+            assert_eq!(Dec32n2::from_f64_ext(31400.0, r), Ok(fpdec!(31400)));
+            assert_eq!(
+                Dec32p2::from_f64_ext(1e-300, r),
+                Dec32p2::from_str_ext("1e-300", r)
+            );
+            assert_eq!(
+                Dec32p2::from_f64_ext(-5e-324, r),
+                Dec32p2::from_str_ext("-5e-324", r)
+            );
+            assert_eq!(
+                Dec32n2::from_f64_ext(31415.16, r),
+                Dec32n2::from_str_ext("31415.16", r)
+            );
+            assert_eq!(
+                Dec32p2::from_f32_ext(1.005, r),
+                Dec32p2::from_str_ext("1.005", r)
+            );
+        }
+        assert_eq!(
+            Dec32p2::from_f64_ext(1.005, Rounding::Round),
+            Ok(fpdec!(1.01))
+        );
+        assert_eq!(
+            Dec32p2::from_f64_ext(-1.005, Rounding::Floor),
+            Ok(fpdec!(-1.01))
+        );
+        assert_eq!(
+            Dec32n2::from_f64_ext(31450.0, Rounding::HalfEven),
+            Ok(fpdec!(31400))
+        );
+
+        // exact for big scales and magnitudes
+        type Dec128p30 = ConstScaleFpdec<i128, 30>;
+        let d = Dec128p30::from_f64_ext(0.1, Rounding::Round).unwrap();
+        assert_eq!(d.mantissa(), 10_i128.pow(29));
+        type Dec128n280 = ConstScaleFpdec<i128, -280>;
+        let d = Dec128n280::from_f64_ext(1e300, Rounding::Round).unwrap();
+        assert_eq!(d.mantissa(), 10_i128.pow(20));
+        let d = Dec128n280::from_f64_ext(f64::MAX, Rounding::Round).unwrap();
+        assert_eq!(d.mantissa(), 17976931348623157 * 10_i128.pow(12));
+        let d = ConstScaleFpdec::<u128, 0>::from_f32_ext(f32::MAX, Rounding::Round).unwrap();
+        assert_eq!(d.mantissa(), 340282350000000000000000000000000000000);
+
+        // errors
+        let kind = |r: Result<Dec32p2, ParseError>| r.unwrap_err().kind();
+        assert_eq!(
+            kind(Dec32p2::from_f64_ext(f64::NAN, Rounding::Round)),
+            ParseErrorKind::Invalid
+        );
+        assert_eq!(
+            kind(Dec32p2::from_f64_ext(f64::INFINITY, Rounding::Round)),
+            ParseErrorKind::Overflow
+        );
+        assert_eq!(
+            kind(Dec32p2::from_f32_ext(f32::NEG_INFINITY, Rounding::Round)),
+            ParseErrorKind::Overflow
+        );
+        assert_eq!(
+            kind(Dec32p2::from_f64_ext(3.14e10, Rounding::Round)),
+            ParseErrorKind::IntOverflow
+        );
+        assert_eq!(
+            kind(Dec32p2::from_f64_ext(1e300, Rounding::Round)),
+            ParseErrorKind::IntOverflow
+        );
+        assert_eq!(
+            kind(Dec32p2::from_f64_ext(-f64::MAX, Rounding::Round)),
+            ParseErrorKind::IntOverflow
+        );
+        assert_eq!(
+            ConstScaleFpdec::<u32, 2>::from_f64_ext(-1.5, Rounding::Round)
+                .unwrap_err()
+                .kind(),
+            ParseErrorKind::Invalid
         );
     }
 
@@ -921,8 +2200,14 @@ mod tests {
         assert_eq!(Dec32p2::try_from(3.1415).unwrap().mantissa(), 314);
         assert_eq!(Dec32n2::try_from(31415.16).unwrap().mantissa(), 314);
 
-        assert_eq!(Dec32p2::try_from(3.14e10), Err(ParseError::Overflow));
-        assert_eq!(Dec32n2::try_from(3.14e16), Err(ParseError::Overflow));
+        assert_eq!(
+            Dec32p2::try_from(3.14e10),
+            Err(ParseErrorKind::Overflow.into())
+        );
+        assert_eq!(
+            Dec32n2::try_from(3.14e16),
+            Err(ParseErrorKind::Overflow.into())
+        );
     }
 
     #[test]
@@ -935,12 +2220,39 @@ mod tests {
         assert_eq!(Dec32p2::from_str("-0.12"), Ok(fpdec!(-0.12)));
         assert_eq!(Dec32p2::from_str("3.14"), Ok(fpdec!(3.14)));
         assert_eq!(Dec32p2::from_str("-3.14"), Ok(fpdec!(-3.14)));
-        assert_eq!(Dec32p2::from_str("3.1415"), Err(ParseError::Precision));
+        assert_eq!(
+            Dec32p2::from_str("3.1415"),
+            Err(ParseError::new(ParseErrorKind::Precision(2), 4))
+        );
+        assert_eq!(Dec32p2::from_str("3.14e2"), Ok(fpdec!(314)));
+        assert_eq!(Dec32p2::from_str("-314E-2"), Ok(fpdec!(-3.14)));
+        assert_eq!(
+            Dec32p2::from_str("3.14e-3"),
+            Err(ParseError::new(ParseErrorKind::Precision(3), 0))
+        );
 
         assert_eq!(Dec32n2::from_str("1000"), Ok(fpdec!(1000)));
         assert_eq!(Dec32n2::from_str("-1000"), Ok(fpdec!(-1000)));
-        assert_eq!(Dec32n2::from_str("1000.00"), Err(ParseError::Precision));
-        assert_eq!(Dec32n2::from_str("1001"), Err(ParseError::Precision));
+        assert_eq!(
+            Dec32n2::from_str("1000.00"),
+            Err(ParseError::new(ParseErrorKind::Precision(4), 2))
+        );
+        assert_eq!(
+            Dec32n2::from_str("1001"),
+            Err(ParseError::new(ParseErrorKind::Precision(2), 2))
+        );
+        assert_eq!(Dec32n2::from_str("1.2e5"), Ok(fpdec!(120000)));
+        assert_eq!(
+            Dec32n2::from_str("1.2e1"),
+            Err(ParseError::new(ParseErrorKind::Precision(2), 0))
+        );
+
+        // with rounding
+        let r = Rounding::Round;
+        assert_eq!(Dec32p2::from_str_ext("3.1415", r), Ok(fpdec!(3.14)));
+        assert_eq!(Dec32p2::from_str_ext("-3.145", r), Ok(fpdec!(-3.15)));
+        assert_eq!(Dec32n2::from_str_ext("1050", r), Ok(fpdec!(1100)));
+        assert_eq!(Dec32n2::from_str_ext("1049.999", r), Ok(fpdec!(1000)));
     }
 
     // used for testing format
@@ -0,0 +1,787 @@
+//! Currencies of ISO 4217, and formatting and parsing of amounts with
+//! currency symbol or code.
+//!
+//! This module is enabled by the optional `currency` feature flag.
+//!
+//! The amount is backed by [`OobScaleFpdec`] whose scale is the currency's
+//! minor units, or by [`ConstScaleFpdec`] with its own scale.
+//!
+//! [`Money`] binds the amount to a currency at compile time, so amounts
+//! in different currencies can not be added or subtracted by mistake, and
+//! can only be converted by [`ExchangeRate`]. [`RuntimeMoney`] does
+//! the same at runtime for assets in [`AssetRegistry`].
+//!
+//! Examples:
+//!
+//! ```
+//! use primitive_fixed_point_decimal::{ConstScaleFpdec, OobScaleFpdec, fpdec};
+//! use primitive_fixed_point_decimal::currency::{Currency, CurrencyFormat};
+//! type Balance = ConstScaleFpdec<i64, 2>;
+//!
+//! // ConstScaleFpdec, displayed with the currency's minor units
+//! let b: Balance = fpdec!(-1234.56);
+//! assert_eq!(format!("{}", b.display_currency(&Currency::USD, &CurrencyFormat::DEFAULT)), "-$1,234.56");
+//! assert_eq!(format!("{}", b.display_currency(&Currency::USD, &CurrencyFormat::ACCOUNTING)), "($1,234.56)");
+//! assert_eq!(Balance::from_currency_str("($1,234.56)", &Currency::USD, &CurrencyFormat::DEFAULT), Ok(b));
+//!
+//! // OobScaleFpdec, whose scale is the currency's minor units
+//! let jpy = Currency::from_code("JPY").unwrap();
+//! let a: OobScaleFpdec<i64> = jpy.parse("JPY 8,888,800", &CurrencyFormat::CODE).unwrap();
+//! assert_eq!(a, fpdec!(8888800, 0));
+//! assert_eq!(format!("{}", jpy.display(a, &CurrencyFormat::CODE)), "JPY 8,888,800");
+//! ```
+
+use crate::fpdec_inner::FpdecInner;
+use crate::{ConstScaleFpdec, NumberFormat, OobScaleFpdec, ParseError, ParseOptions};
+
+use core::fmt;
+
+mod money;
+mod runtime_money;
+pub use money::{CurrencyUnit, ExchangeRate, Money};
+pub use runtime_money::{AssetId, AssetRegistry, MoneyError, RuntimeMoney};
+
+/// Currency of ISO 4217.
+///
+/// All active currencies of ISO 4217 are defined as associated constants,
+/// e.g. [`Currency::USD`], and listed in [`CURRENCIES`], including the fund
+/// codes, e.g. [`Currency::CLF`]. But the X-codes without minor units are
+/// not, e.g. `XAU` for gold, `XDR` for SDR and `XXX` for no currency. You
+/// can also define your own currencies, e.g. for crypto.
+///
+/// The `symbol` is the one commonly used in the currency's own country,
+/// and may be shared by several currencies, e.g. `$`. Use the `code` if
+/// that's ambiguous.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::currency::Currency;
+///
+/// let eur = Currency::from_code("EUR").unwrap();
+/// assert_eq!(eur, &Currency::EUR);
+/// assert_eq!(eur.minor_units, 2);
+/// assert_eq!(eur.symbol, "€");
+///
+/// assert_eq!(Currency::JPY.minor_units, 0);
+/// assert_eq!(Currency::KWD.minor_units, 3);
+/// assert_eq!(Currency::from_code("XYZ"), None);
+///
+/// // crypto
+/// const BTC: Currency = Currency { code: "BTC", minor_units: 8, symbol: "₿", name: "Bitcoin" };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency {
+    /// Alphabetic code, e.g. `"USD"`.
+    pub code: &'static str,
+    /// Number of digits after the decimal point, e.g. 2 for USD and 0 for JPY.
+    pub minor_units: u8,
+    /// Symbol, e.g. `"$"`. It is the same as the code if there is no symbol.
+    pub symbol: &'static str,
+    /// English name, e.g. `"US Dollar"`.
+    pub name: &'static str,
+}
+
+macro_rules! define_currencies {
+    ($($code:ident, $minor_units:literal, $symbol:literal, $name:literal;)*) => {
+        impl Currency {
+            $(
+                #[doc = concat!($name, ".")]
+                pub const $code: Currency = Currency {
+                    code: stringify!($code),
+                    minor_units: $minor_units,
+                    symbol: $symbol,
+                    name: $name,
+                };
+            )*
+        }
+
+        /// All the currencies defined in [`Currency`], sorted by code.
+        pub const CURRENCIES: &[Currency] = &[$(Currency::$code),*];
+
+        /// Marker types of the currencies defined in [`Currency`], for
+        /// [`Money`].
+        #[allow(clippy::upper_case_acronyms)]
+        pub mod unit {
+            use super::{Currency, CurrencyUnit};
+            $(
+                #[doc = concat!("Marker type of [`Currency::", stringify!($code), "`].")]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+                pub struct $code;
+
+                impl CurrencyUnit for $code {
+                    const CURRENCY: &'static Currency = &Currency::$code;
+                }
+            )*
+        }
+    };
+}
+
+define_currencies! {
+    AED, 2, "د.إ", "UAE Dirham";
+    AFN, 2, "؋", "Afghani";
+    ALL, 2, "L", "Lek";
+    AMD, 2, "֏", "Armenian Dram";
+    AOA, 2, "Kz", "Kwanza";
+    ARS, 2, "$", "Argentine Peso";
+    AUD, 2, "$", "Australian Dollar";
+    AWG, 2, "ƒ", "Aruban Florin";
+    AZN, 2, "₼", "Azerbaijan Manat";
+    BAM, 2, "KM", "Convertible Mark";
+    BBD, 2, "$", "Barbados Dollar";
+    BDT, 2, "৳", "Taka";
+    BGN, 2, "лв", "Bulgarian Lev";
+    BHD, 3, "BD", "Bahraini Dinar";
+    BIF, 0, "FBu", "Burundi Franc";
+    BMD, 2, "$", "Bermudian Dollar";
+    BND, 2, "$", "Brunei Dollar";
+    BOB, 2, "Bs", "Boliviano";
+    BOV, 2, "BOV", "Mvdol";
+    BRL, 2, "R$", "Brazilian Real";
+    BSD, 2, "$", "Bahamian Dollar";
+    BTN, 2, "Nu.", "Ngultrum";
+    BWP, 2, "P", "Pula";
+    BYN, 2, "Br", "Belarusian Ruble";
+    BZD, 2, "$", "Belize Dollar";
+    CAD, 2, "$", "Canadian Dollar";
+    CDF, 2, "FC", "Congolese Franc";
+    CHE, 2, "CHE", "WIR Euro";
+    CHF, 2, "CHF", "Swiss Franc";
+    CHW, 2, "CHW", "WIR Franc";
+    CLF, 4, "UF", "Unidad de Fomento";
+    CLP, 0, "$", "Chilean Peso";
+    CNY, 2, "¥", "Yuan Renminbi";
+    COP, 2, "$", "Colombian Peso";
+    COU, 2, "COU", "Unidad de Valor Real";
+    CRC, 2, "₡", "Costa Rican Colon";
+    CUP, 2, "$", "Cuban Peso";
+    CVE, 2, "$", "Cabo Verde Escudo";
+    CZK, 2, "Kč", "Czech Koruna";
+    DJF, 0, "Fdj", "Djibouti Franc";
+    DKK, 2, "kr", "Danish Krone";
+    DOP, 2, "$", "Dominican Peso";
+    DZD, 2, "DA", "Algerian Dinar";
+    EGP, 2, "E£", "Egyptian Pound";
+    ERN, 2, "Nfk", "Nakfa";
+    ETB, 2, "Br", "Ethiopian Birr";
+    EUR, 2, "€", "Euro";
+    FJD, 2, "$", "Fiji Dollar";
+    FKP, 2, "£", "Falkland Islands Pound";
+    GBP, 2, "£", "Pound Sterling";
+    GEL, 2, "₾", "Lari";
+    GHS, 2, "₵", "Ghana Cedi";
+    GIP, 2, "£", "Gibraltar Pound";
+    GMD, 2, "D", "Dalasi";
+    GNF, 0, "FG", "Guinean Franc";
+    GTQ, 2, "Q", "Quetzal";
+    GYD, 2, "$", "Guyana Dollar";
+    HKD, 2, "$", "Hong Kong Dollar";
+    HNL, 2, "L", "Lempira";
+    HTG, 2, "G", "Gourde";
+    HUF, 2, "Ft", "Forint";
+    IDR, 2, "Rp", "Rupiah";
+    ILS, 2, "₪", "New Israeli Sheqel";
+    INR, 2, "₹", "Indian Rupee";
+    IQD, 3, "IQD", "Iraqi Dinar";
+    IRR, 2, "IRR", "Iranian Rial";
+    ISK, 0, "kr", "Iceland Krona";
+    JMD, 2, "$", "Jamaican Dollar";
+    JOD, 3, "JD", "Jordanian Dinar";
+    JPY, 0, "¥", "Yen";
+    KES, 2, "KSh", "Kenyan Shilling";
+    KGS, 2, "сом", "Som";
+    KHR, 2, "៛", "Riel";
+    KMF, 0, "CF", "Comorian Franc";
+    KPW, 2, "₩", "North Korean Won";
+    KRW, 0, "₩", "Won";
+    KWD, 3, "KD", "Kuwaiti Dinar";
+    KYD, 2, "$", "Cayman Islands Dollar";
+    KZT, 2, "₸", "Tenge";
+    LAK, 2, "₭", "Lao Kip";
+    LBP, 2, "LBP", "Lebanese Pound";
+    LKR, 2, "Rs", "Sri Lanka Rupee";
+    LRD, 2, "$", "Liberian Dollar";
+    LSL, 2, "L", "Loti";
+    LYD, 3, "LD", "Libyan Dinar";
+    MAD, 2, "MAD", "Moroccan Dirham";
+    MDL, 2, "L", "Moldovan Leu";
+    MGA, 2, "Ar", "Malagasy Ariary";
+    MKD, 2, "ден", "Denar";
+    MMK, 2, "K", "Kyat";
+    MNT, 2, "₮", "Tugrik";
+    MOP, 2, "MOP$", "Pataca";
+    MRU, 2, "UM", "Ouguiya";
+    MUR, 2, "₨", "Mauritius Rupee";
+    MVR, 2, "Rf", "Rufiyaa";
+    MWK, 2, "MK", "Malawi Kwacha";
+    MXN, 2, "$", "Mexican Peso";
+    MXV, 2, "MXV", "Mexican Unidad de Inversion (UDI)";
+    MYR, 2, "RM", "Malaysian Ringgit";
+    MZN, 2, "MT", "Mozambique Metical";
+    NAD, 2, "$", "Namibia Dollar";
+    NGN, 2, "₦", "Naira";
+    NIO, 2, "C$", "Cordoba Oro";
+    NOK, 2, "kr", "Norwegian Krone";
+    NPR, 2, "₨", "Nepalese Rupee";
+    NZD, 2, "$", "New Zealand Dollar";
+    OMR, 3, "OMR", "Rial Omani";
+    PAB, 2, "B/.", "Balboa";
+    PEN, 2, "S/", "Sol";
+    PGK, 2, "K", "Kina";
+    PHP, 2, "₱", "Philippine Peso";
+    PKR, 2, "₨", "Pakistan Rupee";
+    PLN, 2, "zł", "Zloty";
+    PYG, 0, "₲", "Guarani";
+    QAR, 2, "QR", "Qatari Rial";
+    RON, 2, "lei", "Romanian Leu";
+    RSD, 2, "дин.", "Serbian Dinar";
+    RUB, 2, "₽", "Russian Ruble";
+    RWF, 0, "FRw", "Rwanda Franc";
+    SAR, 2, "SAR", "Saudi Riyal";
+    SBD, 2, "$", "Solomon Islands Dollar";
+    SCR, 2, "₨", "Seychelles Rupee";
+    SDG, 2, "SDG", "Sudanese Pound";
+    SEK, 2, "kr", "Swedish Krona";
+    SGD, 2, "$", "Singapore Dollar";
+    SHP, 2, "£", "Saint Helena Pound";
+    SLE, 2, "Le", "Leone";
+    SOS, 2, "Sh", "Somali Shilling";
+    SRD, 2, "$", "Surinam Dollar";
+    SSP, 2, "£", "South Sudanese Pound";
+    STN, 2, "Db", "Dobra";
+    SVC, 2, "₡", "El Salvador Colon";
+    SYP, 2, "£", "Syrian Pound";
+    SZL, 2, "L", "Lilangeni";
+    THB, 2, "฿", "Baht";
+    TJS, 2, "SM", "Somoni";
+    TMT, 2, "m", "Turkmenistan New Manat";
+    TND, 3, "DT", "Tunisian Dinar";
+    TOP, 2, "T$", "Pa'anga";
+    TRY, 2, "₺", "Turkish Lira";
+    TTD, 2, "$", "Trinidad and Tobago Dollar";
+    TWD, 2, "$", "New Taiwan Dollar";
+    TZS, 2, "TSh", "Tanzanian Shilling";
+    UAH, 2, "₴", "Hryvnia";
+    UGX, 0, "USh", "Uganda Shilling";
+    USD, 2, "$", "US Dollar";
+    USN, 2, "USN", "US Dollar (Next day)";
+    UYI, 0, "UYI", "Uruguay Peso en Unidades Indexadas (UI)";
+    UYU, 2, "$", "Peso Uruguayo";
+    UYW, 4, "UYW", "Unidad Previsional";
+    UZS, 2, "soʻm", "Uzbekistan Sum";
+    VED, 2, "Bs.D", "Bolívar Soberano";
+    VES, 2, "Bs.S", "Bolívar Soberano";
+    VND, 0, "₫", "Dong";
+    VUV, 0, "VT", "Vatu";
+    WST, 2, "T", "Tala";
+    XAF, 0, "FCFA", "CFA Franc BEAC";
+    XCD, 2, "$", "East Caribbean Dollar";
+    XCG, 2, "Cg", "Caribbean Guilder";
+    XOF, 0, "CFA", "CFA Franc BCEAO";
+    XPF, 0, "CFP", "CFP Franc";
+    YER, 2, "YER", "Yemeni Rial";
+    ZAR, 2, "R", "Rand";
+    ZMW, 2, "K", "Zambian Kwacha";
+    ZWG, 2, "ZiG", "Zimbabwe Gold";
+}
+
+impl Currency {
+    /// Find the currency in [`CURRENCIES`] by the alphabetic code,
+    /// e.g. `"USD"`. The code is case-sensitive.
+    pub fn from_code(code: &str) -> Option<&'static Currency> {
+        CURRENCIES
+            .binary_search_by(|c| c.code.cmp(code))
+            .ok()
+            .map(|i| &CURRENCIES[i])
+    }
+
+    /// Wrap the amount to display with the currency symbol or code.
+    /// The scale of the amount is the currency's minor units.
+    ///
+    /// See [`CurrencyFormat`] for the format.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, fpdec};
+    /// use primitive_fixed_point_decimal::currency::{Currency, CurrencyFormat};
+    ///
+    /// let a: OobScaleFpdec<i64> = fpdec!(1234.567, 3);
+    /// assert_eq!(format!("{}", Currency::KWD.display(a, &CurrencyFormat::CODE)), "KWD 1,234.567");
+    /// ```
+    pub fn display<'a, I>(
+        &'a self,
+        amount: OobScaleFpdec<I>,
+        cf: &'a CurrencyFormat,
+    ) -> CurrencyDisplay<'a, I>
+    where
+        I: FpdecInner,
+    {
+        CurrencyDisplay {
+            inner: amount.mantissa(),
+            scale: self.minor_units as i32,
+            currency: self,
+            cf,
+        }
+    }
+
+    /// Read amount from string with the currency symbol or code. The scale
+    /// of the amount is the currency's minor units.
+    ///
+    /// It accepts the symbol or code before or after the number, with or
+    /// without space, and the negative in minus or parentheses style.
+    /// The currency mark can also be omitted. The grouping separator and
+    /// decimal mark are from `cf.number`, and only ASCII ones are
+    /// supported for parsing.
+    ///
+    /// Return error if the string has more precision than the minor units.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, NumberFormat, ParseErrorKind, fpdec};
+    /// use primitive_fixed_point_decimal::currency::{Currency, CurrencyFormat};
+    /// type Amount = OobScaleFpdec<i64>;
+    ///
+    /// let usd = &Currency::USD;
+    /// let cf = CurrencyFormat::DEFAULT;
+    /// assert_eq!(usd.parse("$1,234.56", &cf), Ok(fpdec!(1234.56, 2) as Amount));
+    /// assert_eq!(usd.parse("-$1,234.56", &cf), Ok(fpdec!(-1234.56, 2) as Amount));
+    /// assert_eq!(usd.parse("(USD 1,234.56)", &cf), Ok(fpdec!(-1234.56, 2) as Amount));
+    /// assert_eq!(usd.parse("1234.56", &cf), Ok(fpdec!(1234.56, 2) as Amount));
+    /// assert_eq!(usd.parse::<i64>("$1.234", &cf).map_err(|e| e.kind()), Err(ParseErrorKind::Precision(1)));
+    ///
+    /// let cf = CurrencyFormat { number: NumberFormat::DE, ..CurrencyFormat::SUFFIX };
+    /// assert_eq!(Currency::EUR.parse("1.234,56 €", &cf), Ok(fpdec!(1234.56, 2) as Amount));
+    /// ```
+    pub fn parse<I>(&self, s: &str, cf: &CurrencyFormat) -> Result<OobScaleFpdec<I>, ParseError>
+    where
+        I: FpdecInner,
+    {
+        parse_amount(s, self.minor_units as i32, self, cf).map(OobScaleFpdec::from_mantissa)
+    }
+}
+
+/// Where to put the currency symbol or code, and which one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CurrencyStyle {
+    /// Symbol, e.g. `$`.
+    Symbol,
+    /// Alphabetic code, e.g. `USD`.
+    Code,
+}
+
+/// How to show the negative amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NegativeStyle {
+    /// With minus sign before the currency mark, e.g. `-$1.00`.
+    Minus,
+    /// In parentheses as accounting, e.g. `($1.00)`.
+    Parentheses,
+}
+
+/// Format of amount with currency.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, NumberFormat, fpdec};
+/// use primitive_fixed_point_decimal::currency::{Currency, CurrencyFormat, NegativeStyle};
+/// type Balance = ConstScaleFpdec<i64, 2>;
+///
+/// let b: Balance = fpdec!(-1234.5);
+/// let show = |cf| format!("{}", b.display_currency(&Currency::EUR, &cf));
+///
+/// assert_eq!(show(CurrencyFormat::DEFAULT), "-€1,234.50");
+/// assert_eq!(show(CurrencyFormat::ACCOUNTING), "(€1,234.50)");
+/// assert_eq!(show(CurrencyFormat::CODE), "-EUR 1,234.50");
+/// assert_eq!(show(CurrencyFormat::SUFFIX), "-1,234.50 €");
+///
+/// let de = CurrencyFormat { number: NumberFormat::DE, ..CurrencyFormat::SUFFIX };
+/// assert_eq!(show(de), "-1.234,50 €");
+///
+/// let cf = CurrencyFormat { negative: NegativeStyle::Parentheses, ..CurrencyFormat::CODE };
+/// assert_eq!(show(cf), "(EUR 1,234.50)");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CurrencyFormat {
+    /// Show the symbol or code.
+    pub style: CurrencyStyle,
+    /// Show the negative amount with minus or in parentheses.
+    pub negative: NegativeStyle,
+    /// Put the symbol or code after the number, e.g. `1,234.56 €`.
+    pub suffix: bool,
+    /// Put a space between the number and the symbol or code.
+    pub space: bool,
+    /// Grouping separator and decimal mark of the number.
+    pub number: NumberFormat,
+}
+
+impl CurrencyFormat {
+    /// Symbol before the number, e.g. `$1,234.56` and `-$1,234.56`.
+    pub const DEFAULT: Self = Self {
+        style: CurrencyStyle::Symbol,
+        negative: NegativeStyle::Minus,
+        suffix: false,
+        space: false,
+        number: NumberFormat::EN,
+    };
+
+    /// Accounting style, e.g. `$1,234.56` and `($1,234.56)`.
+    pub const ACCOUNTING: Self = Self {
+        negative: NegativeStyle::Parentheses,
+        ..Self::DEFAULT
+    };
+
+    /// Code before the number, e.g. `USD 1,234.56`.
+    pub const CODE: Self = Self {
+        style: CurrencyStyle::Code,
+        space: true,
+        ..Self::DEFAULT
+    };
+
+    /// Symbol after the number, e.g. `1,234.56 €`.
+    pub const SUFFIX: Self = Self {
+        suffix: true,
+        space: true,
+        ..Self::DEFAULT
+    };
+}
+
+impl Default for CurrencyFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Adaptor to display amount with currency.
+///
+/// It is made by [`Currency::display`] or [`ConstScaleFpdec::display_currency`].
+/// The precision is the currency's minor units by default, and the
+/// amount is rounded if its scale is bigger. It supports the same
+/// formatting options as `Display`: width, fill, alignment, precision
+/// and 0-fill.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+/// use primitive_fixed_point_decimal::currency::{Currency, CurrencyFormat};
+/// type Price = ConstScaleFpdec<i64, 4>;
+///
+/// let p: Price = fpdec!(8888.8888);
+/// let jpy = p.display_currency(&Currency::JPY, &CurrencyFormat::CODE);
+/// assert_eq!(format!("{}", jpy), "JPY 8,889");
+/// assert_eq!(format!("{:.2}", jpy), "JPY 8,888.89");
+/// assert_eq!(format!("{:>12}", jpy), "   JPY 8,889");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CurrencyDisplay<'a, I> {
+    inner: I,
+    scale: i32,
+    currency: &'a Currency,
+    cf: &'a CurrencyFormat,
+}
+
+impl<I> fmt::Display for CurrencyDisplay<'_, I>
+where
+    I: FpdecInner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cf = self.cf;
+        let mark = match cf.style {
+            CurrencyStyle::Symbol => self.currency.symbol,
+            CurrencyStyle::Code => self.currency.code,
+        };
+        let space = if cf.space { " " } else { "" };
+
+        let (open, close) = match (self.inner < I::ZERO, cf.negative) {
+            (false, _) if f.sign_plus() => ("+", ""),
+            (false, _) => ("", ""),
+            (true, NegativeStyle::Minus) => ("-", ""),
+            (true, NegativeStyle::Parentheses) => ("(", ")"),
+        };
+
+        let precision = f.precision().or(Some(self.currency.minor_units as usize));
+        let (prefix, suffix) = if cf.suffix {
+            ([open, "", ""], [space, mark, close])
+        } else {
+            ([open, mark, space], ["", "", close])
+        };
+
+        self.inner
+            .display_affix_fmt(self.scale, precision, &cf.number, &prefix, &suffix, f)
+    }
+}
+
+impl<I, const S: i32> ConstScaleFpdec<I, S>
+where
+    I: FpdecInner,
+{
+    /// Display with the currency symbol or code.
+    ///
+    /// The precision is the currency's minor units by default. See
+    /// [`CurrencyDisplay`] for details.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// use primitive_fixed_point_decimal::currency::{Currency, CurrencyFormat};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    ///
+    /// let b: Balance = fpdec!(1234.5);
+    /// assert_eq!(format!("{}", b.display_currency(&Currency::USD, &CurrencyFormat::DEFAULT)), "$1,234.50");
+    /// ```
+    pub fn display_currency<'a>(
+        self,
+        currency: &'a Currency,
+        cf: &'a CurrencyFormat,
+    ) -> CurrencyDisplay<'a, I> {
+        CurrencyDisplay {
+            inner: self.mantissa(),
+            scale: S,
+            currency,
+            cf,
+        }
+    }
+
+    /// Read decimal from string with the currency symbol or code.
+    ///
+    /// The scale is still `S` but not the currency's minor units. See
+    /// [`Currency::parse`] for the accepted formats.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// use primitive_fixed_point_decimal::currency::{Currency, CurrencyFormat};
+    /// type Balance = ConstScaleFpdec<i64, 2>;
+    ///
+    /// let cf = CurrencyFormat::ACCOUNTING;
+    /// assert_eq!(Balance::from_currency_str("($1,234.56)", &Currency::USD, &cf), Ok(fpdec!(-1234.56)));
+    /// assert_eq!(Balance::from_currency_str("¥8,888,800", &Currency::JPY, &cf), Ok(fpdec!(8888800)));
+    /// ```
+    pub fn from_currency_str(
+        s: &str,
+        currency: &Currency,
+        cf: &CurrencyFormat,
+    ) -> Result<Self, ParseError> {
+        parse_amount(s, S, currency, cf).map(Self::from_mantissa)
+    }
+}
+
+fn parse_amount<I>(
+    s: &str,
+    scale: i32,
+    currency: &Currency,
+    cf: &CurrencyFormat,
+) -> Result<I, ParseError>
+where
+    I: FpdecInner,
+{
+    // Strip the currency mark at either end. Try the longer one first,
+    // e.g. symbol `MOP$` before code `MOP`.
+    fn strip_mark<'s>(s: &'s str, currency: &Currency) -> &'s str {
+        let (code, symbol) = (currency.code, currency.symbol);
+        let marks = if symbol.len() > code.len() {
+            [symbol, code]
+        } else {
+            [code, symbol]
+        };
+        for mark in marks {
+            if let Some(rest) = s.strip_prefix(mark) {
+                return rest.trim_start();
+            }
+            if let Some(rest) = s.strip_suffix(mark) {
+                return rest.trim_end();
+            }
+        }
+        s
+    }
+
+    // The negative signs outside of the number: parentheses, or minus
+    // before the currency mark. The minus before the number is handled
+    // by the number parsing.
+    let t = s.trim();
+    let (neg, num) = match t.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        Some(t) => (true, strip_mark(t.trim(), currency)),
+        None => match t.strip_prefix('-').map(|t| t.trim_start()) {
+            Some(t) if strip_mark(t, currency).len() != t.len() => (true, strip_mark(t, currency)),
+            _ => (false, strip_mark(t, currency)),
+        },
+    };
+
+    let ascii = |ch: char| ch.is_ascii().then_some(ch as u8);
+    let opts = ParseOptions {
+        separator: cf.number.group_separator.and_then(ascii),
+        point: ascii(cf.number.decimal_mark).unwrap_or(b'.'),
+        ..ParseOptions::DEFAULT
+    };
+
+    // the errors' offsets are in the original string
+    let start = num.as_ptr() as usize - s.as_ptr() as usize;
+    I::try_from_ascii_neg_opts(num.as_bytes(), scale, neg, opts).map_err(|err| match err.offset() {
+        Some(offset) => ParseError::new(err.kind(), start + offset),
+        None => err,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseErrorKind;
+    extern crate std;
+    use std::format;
+
+    #[test]
+    fn test_table() {
+        for pair in CURRENCIES.windows(2) {
+            assert!(pair[0].code < pair[1].code);
+        }
+        for c in CURRENCIES {
+            assert_eq!(c.code.len(), 3);
+            assert!(c.code.bytes().all(|b| b.is_ascii_uppercase()));
+            assert!(!c.symbol.is_empty());
+            assert_eq!(Currency::from_code(c.code), Some(c));
+        }
+        assert_eq!(Currency::from_code("CLF").unwrap().minor_units, 4);
+        assert_eq!(Currency::from_code("UYW").unwrap().minor_units, 4);
+        assert_eq!(Currency::from_code("UYI").unwrap().minor_units, 0);
+        assert_eq!(Currency::from_code("VED").unwrap().minor_units, 2);
+        assert_eq!(Currency::from_code("XAU"), None);
+        assert_eq!(Currency::from_code("usd"), None);
+        assert_eq!(Currency::from_code(""), None);
+    }
+
+    #[test]
+    fn test_display() {
+        type Balance = ConstScaleFpdec<i64, 2>;
+        let b = Balance::from_mantissa(-123456);
+        let show = |cf: CurrencyFormat| format!("{}", b.display_currency(&Currency::USD, &cf));
+
+        assert_eq!(show(CurrencyFormat::DEFAULT), "-$1,234.56");
+        assert_eq!(show(CurrencyFormat::ACCOUNTING), "($1,234.56)");
+        assert_eq!(show(CurrencyFormat::CODE), "-USD 1,234.56");
+        assert_eq!(show(CurrencyFormat::SUFFIX), "-1,234.56 $");
+        let cf = CurrencyFormat {
+            negative: NegativeStyle::Parentheses,
+            ..CurrencyFormat::SUFFIX
+        };
+        assert_eq!(show(cf), "(1,234.56 $)");
+
+        // options
+        let d = b.display_currency(&Currency::USD, &CurrencyFormat::ACCOUNTING);
+        assert_eq!(format!("{:>14}", d), "   ($1,234.56)");
+        assert_eq!(format!("{:<14}", d), "($1,234.56)   ");
+        assert_eq!(format!("{:014}", d), "($0001,234.56)");
+        assert_eq!(format!("{:.0}", d), "($1,235)");
+        let d = (-b).display_currency(&Currency::USD, &CurrencyFormat::ACCOUNTING);
+        assert_eq!(format!("{}", d), "$1,234.56");
+        assert_eq!(format!("{:+}", d), "+$1,234.56");
+
+        // minor units
+        assert_eq!(
+            format!(
+                "{}",
+                b.display_currency(&Currency::JPY, &CurrencyFormat::CODE)
+            ),
+            "-JPY 1,235"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                b.display_currency(&Currency::BHD, &CurrencyFormat::CODE)
+            ),
+            "-BHD 1,234.560"
+        );
+
+        // width counts chars
+        let a = OobScaleFpdec::<i32>::from_mantissa(100);
+        let d = Currency::EUR.display(a, &CurrencyFormat::SUFFIX);
+        assert_eq!(format!("{:*>8}", d), "**1.00 €");
+        let d = Currency::JPY.display(a, &CurrencyFormat::DEFAULT);
+        assert_eq!(format!("{:*^8}", d), "**¥100**");
+    }
+
+    #[test]
+    fn test_parse() {
+        let usd = &Currency::USD;
+        let cf = CurrencyFormat::DEFAULT;
+        let parse = |s| usd.parse::<i64>(s, &cf).map(|a| a.mantissa());
+        let err = |kind, offset| Err(ParseError::new(kind, offset));
+
+        assert_eq!(parse("$1,234.56"), Ok(123456));
+        assert_eq!(parse(" $ 1,234.56 "), Ok(123456));
+        assert_eq!(parse("USD1234.5"), Ok(123450));
+        assert_eq!(parse("1,234.56 USD"), Ok(123456));
+        assert_eq!(parse("1,234.56$"), Ok(123456));
+        assert_eq!(parse("1234"), Ok(123400));
+        assert_eq!(parse("+$1"), err(ParseErrorKind::Invalid, 1));
+
+        // negative
+        assert_eq!(parse("-$1,234.56"), Ok(-123456));
+        assert_eq!(parse("- $1,234.56"), Ok(-123456));
+        assert_eq!(parse("$-1,234.56"), Ok(-123456));
+        assert_eq!(parse("-1,234.56"), Ok(-123456));
+        assert_eq!(parse("-1,234.56 USD"), Ok(-123456));
+        assert_eq!(parse("($1,234.56)"), Ok(-123456));
+        assert_eq!(parse("( 1,234.56 $ )"), Ok(-123456));
+        assert_eq!(parse("(1,234.56)"), Ok(-123456));
+        assert_eq!(parse("($-1)"), err(ParseErrorKind::Invalid, 2));
+        assert_eq!(parse("--$1"), err(ParseErrorKind::Invalid, 1));
+        assert_eq!(
+            usd.parse::<u32>("($1)", &cf),
+            Err(ParseError::new(ParseErrorKind::Invalid, 2))
+        );
+        assert_eq!(
+            usd.parse::<i16>("($327.68)", &cf).map(|a| a.mantissa()),
+            Ok(i16::MIN)
+        );
+
+        // errors with offset in the original string
+        assert_eq!(parse("$"), err(ParseErrorKind::Empty, 1));
+        assert_eq!(parse("($1.234)"), err(ParseErrorKind::Precision(1), 6));
+        assert_eq!(parse("  USD 1.2x"), err(ParseErrorKind::Invalid, 9));
+        assert_eq!(parse("€1"), err(ParseErrorKind::Invalid, 0));
+
+        // number format
+        let cf = CurrencyFormat {
+            number: NumberFormat::DE,
+            ..CurrencyFormat::SUFFIX
+        };
+        let eur = &Currency::EUR;
+        assert_eq!(
+            eur.parse::<i64>("1.234,56 €", &cf).unwrap().mantissa(),
+            123456
+        );
+        assert_eq!(
+            eur.parse::<i64>("-1.234,5 EUR", &cf).unwrap().mantissa(),
+            -123450
+        );
+        let cf = CurrencyFormat {
+            number: NumberFormat::FR,
+            ..CurrencyFormat::SUFFIX
+        };
+        assert_eq!(
+            eur.parse::<i64>("1 234,56 €", &cf).unwrap().mantissa(),
+            123456
+        );
+
+        // round trip
+        for cf in [
+            CurrencyFormat::DEFAULT,
+            CurrencyFormat::ACCOUNTING,
+            CurrencyFormat::CODE,
+            CurrencyFormat::SUFFIX,
+        ] {
+            for c in CURRENCIES {
+                for n in [0, 1, -1, 123456789, -987654321, i64::MAX, i64::MIN] {
+                    let a = OobScaleFpdec::<i64>::from_mantissa(n);
+                    let s = format!("{}", c.display(a, &cf));
+                    assert_eq!(c.parse(&s, &cf), Ok(a), "{s}");
+                }
+            }
+        }
+    }
+}
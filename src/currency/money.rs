@@ -0,0 +1,636 @@
+use super::{parse_amount, Currency, CurrencyDisplay, CurrencyFormat};
+use crate::fpdec_inner::FpdecInner;
+use crate::{ConstScaleFpdec, ParseError, Rounding};
+
+use core::{cmp::Ordering, fmt, hash, marker::PhantomData, ops, str::FromStr};
+
+use num_traits::Signed;
+
+/// Marker type of currency, which binds [`Money`] to the currency at
+/// compile time.
+///
+/// All currencies defined in [`Currency`] have their marker types in
+/// [`unit`](super::unit). You can also define your own ones.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::currency::{Currency, CurrencyUnit, Money};
+///
+/// const BITCOIN: Currency = Currency { code: "BTC", minor_units: 8, symbol: "₿", name: "Bitcoin" };
+///
+/// struct BTC;
+/// impl CurrencyUnit for BTC {
+///     const CURRENCY: &'static Currency = &BITCOIN;
+/// }
+///
+/// let b: Money<i64, BTC> = "0.00012345".parse().unwrap();
+/// assert_eq!(b.to_string(), "BTC 0.00012345");
+/// ```
+pub trait CurrencyUnit {
+    /// The currency.
+    const CURRENCY: &'static Currency;
+
+    /// The scale of the amount, which is the currency's minor units by default.
+    const SCALE: i32 = Self::CURRENCY.minor_units as i32;
+}
+
+/// Amount of money in a compile-time currency.
+///
+/// `I` is the inner integer type as [`ConstScaleFpdec`], and `C` is the
+/// [`CurrencyUnit`] marker type carrying the currency and the scale.
+///
+/// It is the same as `ConstScaleFpdec<I, C::SCALE>`, but allows `+` and `-`
+/// only between the same currency. The amount in another currency can only
+/// be got by the explicit [`ExchangeRate`].
+///
+/// Examples:
+///
+/// ```compile_fail
+/// use primitive_fixed_point_decimal::currency::{Money, unit::{EUR, USD}};
+///
+/// let usd: Money<i64, USD> = "1.23".parse().unwrap();
+/// let eur: Money<i64, EUR> = "1.23".parse().unwrap();
+/// let _ = usd + eur; // compile error!
+/// ```
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+/// use primitive_fixed_point_decimal::currency::{CurrencyFormat, Money, unit::USD};
+/// type Usd = Money<i64, USD>;
+/// type Balance = ConstScaleFpdec<i64, 2>;
+///
+/// let a: Usd = "1234.56".parse().unwrap();
+/// let b: Balance = fpdec!(0.44);
+/// let sum = a + Usd::new(b);
+///
+/// assert_eq!(sum.amount::<2>(), fpdec!(1235));
+/// assert_eq!(sum.to_string(), "USD 1,235.00");
+/// assert_eq!(format!("{}", (-sum).display(&CurrencyFormat::ACCOUNTING)), "($1,235.00)");
+/// ```
+#[repr(transparent)]
+pub struct Money<I, C>(I, PhantomData<C>);
+
+impl<I, C> Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    /// The zero value.
+    pub const ZERO: Self = Self(I::ZERO, PhantomData);
+
+    /// The currency.
+    pub const CURRENCY: &'static Currency = C::CURRENCY;
+
+    /// The scale of the amount.
+    pub const SCALE: i32 = C::SCALE;
+
+    /// Make money from the amount.
+    ///
+    /// The scale `S` must be the currency's [`CurrencyUnit::SCALE`],
+    /// otherwise it fails to compile.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// use primitive_fixed_point_decimal::currency::{Money, unit::JPY};
+    ///
+    /// let a: ConstScaleFpdec<i64, 0> = fpdec!(8800);
+    /// let m: Money<i64, JPY> = Money::new(a);
+    /// assert_eq!(m.to_string(), "JPY 8,800");
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+    /// use primitive_fixed_point_decimal::currency::{Money, unit::JPY};
+    ///
+    /// let a: ConstScaleFpdec<i64, 2> = fpdec!(8800);
+    /// let m: Money<i64, JPY> = Money::new(a); // compile error!
+    /// ```
+    #[must_use]
+    pub const fn new<const S: i32>(amount: ConstScaleFpdec<I, S>) -> Self {
+        const { assert!(S == C::SCALE, "scale mismatch with the currency") };
+        Self(amount.mantissa(), PhantomData)
+    }
+
+    /// Get the amount.
+    ///
+    /// The scale `S` must be the currency's [`CurrencyUnit::SCALE`],
+    /// otherwise it fails to compile.
+    #[must_use]
+    pub const fn amount<const S: i32>(self) -> ConstScaleFpdec<I, S> {
+        const { assert!(S == C::SCALE, "scale mismatch with the currency") };
+        ConstScaleFpdec::from_mantissa(self.0)
+    }
+
+    /// Make money from the mantissa whose scale is [`CurrencyUnit::SCALE`].
+    #[must_use]
+    pub const fn from_mantissa(i: I) -> Self {
+        Self(i, PhantomData)
+    }
+
+    /// Get the mantissa whose scale is [`CurrencyUnit::SCALE`].
+    #[must_use]
+    pub const fn mantissa(self) -> I {
+        self.0
+    }
+
+    /// Return if zero.
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Checked addition. Computes `self + rhs`, returning `None` if overflow occurred.
+    ///
+    /// The right operand must be in the same currency.
+    #[must_use]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(&rhs.0).map(Self::from_mantissa)
+    }
+
+    /// Checked subtraction. Computes `self - rhs`, returning `None` if overflow occurred.
+    ///
+    /// The right operand must be in the same currency.
+    #[must_use]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(&rhs.0).map(Self::from_mantissa)
+    }
+
+    /// Checked conversion into another currency by the exchange rate.
+    ///
+    /// Equivalent to [`Self::checked_convert_ext`] with `Rounding::Round`.
+    ///
+    /// If you make sure no overflow error, you can use `*` instead for
+    /// convenience.
+    #[must_use]
+    pub fn checked_convert<To, R, const S: i32>(
+        self,
+        rate: ExchangeRate<C, To, R, S>,
+    ) -> Option<Money<I, To>>
+    where
+        To: CurrencyUnit,
+        R: FpdecInner,
+    {
+        self.checked_convert_ext(rate, Rounding::Round)
+    }
+
+    /// Checked conversion into another currency by the exchange rate,
+    /// with rounding. Computes `self * rate`, returning `None` if overflow
+    /// occurred.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{Rounding, fpdec};
+    /// use primitive_fixed_point_decimal::currency::{ExchangeRate, Money, unit::{JPY, USD}};
+    ///
+    /// let rate: ExchangeRate<USD, JPY, i64, 4> = ExchangeRate::new(fpdec!(150.1234));
+    /// let usd: Money<i64, USD> = "12.34".parse().unwrap();
+    ///
+    /// let jpy = usd.checked_convert_ext(rate, Rounding::Floor).unwrap();
+    /// assert_eq!(jpy.to_string(), "JPY 1,852");
+    /// let jpy = usd.checked_convert_ext(rate, Rounding::Ceiling).unwrap();
+    /// assert_eq!(jpy.to_string(), "JPY 1,853");
+    /// ```
+    #[must_use]
+    pub fn checked_convert_ext<To, R, const S: i32>(
+        self,
+        rate: ExchangeRate<C, To, R, S>,
+        rounding: Rounding,
+    ) -> Option<Money<I, To>>
+    where
+        To: CurrencyUnit,
+        R: FpdecInner,
+    {
+        self.0
+            .checked_mul_ext(
+                I::from(rate.rate.mantissa())?,
+                C::SCALE + S - To::SCALE,
+                rounding,
+            )
+            .map(Money::from_mantissa)
+    }
+
+    /// Display with the currency symbol or code.
+    ///
+    /// See [`CurrencyDisplay`] for details.
+    pub fn display<'a>(self, cf: &'a CurrencyFormat) -> CurrencyDisplay<'a, I> {
+        CurrencyDisplay {
+            inner: self.0,
+            scale: C::SCALE,
+            currency: C::CURRENCY,
+            cf,
+        }
+    }
+
+    /// Read money from string with the currency symbol or code.
+    ///
+    /// See [`Currency::parse`] for the accepted formats.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::currency::{CurrencyFormat, Money, unit::USD};
+    /// type Usd = Money<i64, USD>;
+    ///
+    /// let cf = CurrencyFormat::DEFAULT;
+    /// assert_eq!(Usd::parse("-$1,234.56", &cf), "-1234.56".parse());
+    /// ```
+    pub fn parse(s: &str, cf: &CurrencyFormat) -> Result<Self, ParseError> {
+        parse_amount(s, C::SCALE, C::CURRENCY, cf).map(Self::from_mantissa)
+    }
+}
+
+impl<I, C> Money<I, C>
+where
+    I: FpdecInner + Signed,
+    C: CurrencyUnit,
+{
+    /// Computes the absolute value of `self`.
+    ///
+    /// # Overflow behavior
+    ///
+    /// The absolute value of `MIN` cannot be represented as this type,
+    /// and attempting to calculate it will cause an overflow. This means that
+    /// code in debug mode will trigger a panic on this case and optimized code
+    /// will return `MIN` without a panic.
+    #[must_use]
+    pub fn abs(self) -> Self {
+        Self::from_mantissa(self.0.abs())
+    }
+
+    /// Return if negative.
+    pub fn is_neg(self) -> bool {
+        self.0.is_negative()
+    }
+
+    /// Return if positive.
+    pub fn is_pos(self) -> bool {
+        self.0.is_positive()
+    }
+}
+
+impl<I: Clone, C> Clone for Money<I, C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<I: Copy, C> Copy for Money<I, C> {}
+
+impl<I: PartialEq, C> PartialEq for Money<I, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<I: Eq, C> Eq for Money<I, C> {}
+
+impl<I: PartialOrd, C> PartialOrd for Money<I, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<I: Ord, C> Ord for Money<I, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<I: hash::Hash, C> hash::Hash for Money<I, C> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<I, C> Default for Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<I, C> fmt::Debug for Money<I, C>
+where
+    I: fmt::Display,
+    C: CurrencyUnit,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Money({},{})", self.0, C::CURRENCY.code)
+    }
+}
+
+/// Display the money with the currency code, e.g. `USD 1,234.56`.
+///
+/// This is the same as [`Money::display`] with [`CurrencyFormat::CODE`],
+/// and supports the same formatting options.
+impl<I, C> fmt::Display for Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.display(&CurrencyFormat::CODE), f)
+    }
+}
+
+/// Read money from plain decimal string, e.g. `1234.56`, in the
+/// currency's scale.
+impl<I, C> FromStr for Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        I::try_from_str(s, C::SCALE).map(Self::from_mantissa)
+    }
+}
+
+impl<I, C> ops::Neg for Money<I, C>
+where
+    I: FpdecInner + Signed,
+    C: CurrencyUnit,
+{
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::from_mantissa(-self.0)
+    }
+}
+
+impl<I, C> ops::Add for Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_mantissa(self.0 + rhs.0)
+    }
+}
+
+impl<I, C> ops::Sub for Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_mantissa(self.0 - rhs.0)
+    }
+}
+
+impl<I, C> ops::AddAssign for Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl<I, C> ops::SubAssign for Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl<I, C> core::iter::Sum for Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    fn sum<Iter: Iterator<Item = Self>>(iter: Iter) -> Self {
+        iter.fold(Self::ZERO, |acc, m| acc + m)
+    }
+}
+
+impl<'a, I, C> core::iter::Sum<&'a Self> for Money<I, C>
+where
+    I: FpdecInner,
+    C: CurrencyUnit,
+{
+    fn sum<Iter: Iterator<Item = &'a Self>>(iter: Iter) -> Self {
+        iter.fold(Self::ZERO, |acc, m| acc + *m)
+    }
+}
+
+/// Performs the `*` operation to convert into another currency.
+///
+/// # Panics
+///
+/// If [`Money::checked_convert`] returns `None`.
+impl<I, From, To, R, const S: i32> ops::Mul<ExchangeRate<From, To, R, S>> for Money<I, From>
+where
+    I: FpdecInner,
+    From: CurrencyUnit,
+    To: CurrencyUnit,
+    R: FpdecInner,
+{
+    type Output = Money<I, To>;
+    fn mul(self, rate: ExchangeRate<From, To, R, S>) -> Self::Output {
+        self.checked_convert(rate)
+            .expect("overflow in currency conversion")
+    }
+}
+
+/// Exchange rate from one currency to another.
+///
+/// `From` and `To` are the [`CurrencyUnit`] marker types. The rate is
+/// the price of 1 `From` in `To`, and is `ConstScaleFpdec<R, S>`.
+///
+/// It is the only way to convert [`Money`] between currencies, by
+/// [`Money::checked_convert_ext`] with rounding, or by `*` with
+/// `Rounding::Round`.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::fpdec;
+/// use primitive_fixed_point_decimal::currency::{ExchangeRate, Money, unit::{EUR, USD}};
+///
+/// let eur_usd: ExchangeRate<EUR, USD, i64, 5> = ExchangeRate::new(fpdec!(1.08765));
+/// let eur: Money<i64, EUR> = "100.00".parse().unwrap();
+/// let usd: Money<i64, USD> = eur * eur_usd;
+/// assert_eq!(usd.to_string(), "USD 108.77");
+///
+/// // back
+/// let usd_eur = eur_usd.checked_inverse().unwrap();
+/// assert_eq!(usd_eur.rate(), fpdec!(0.91941));
+/// assert_eq!((usd * usd_eur).to_string(), "EUR 100.00");
+/// ```
+pub struct ExchangeRate<From, To, R, const S: i32> {
+    rate: ConstScaleFpdec<R, S>,
+    _currencies: PhantomData<(From, To)>,
+}
+
+impl<From, To, R, const S: i32> ExchangeRate<From, To, R, S>
+where
+    From: CurrencyUnit,
+    To: CurrencyUnit,
+    R: FpdecInner,
+{
+    /// Make exchange rate from the price of 1 `From` in `To`.
+    pub const fn new(rate: ConstScaleFpdec<R, S>) -> Self {
+        Self {
+            rate,
+            _currencies: PhantomData,
+        }
+    }
+
+    /// Get the rate.
+    pub const fn rate(self) -> ConstScaleFpdec<R, S> {
+        self.rate
+    }
+
+    /// Checked inverse rate, from `To` to `From`, returning `None` if
+    /// overflow occurred or the rate is zero.
+    ///
+    /// Equivalent to [`Self::checked_inverse_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn checked_inverse(self) -> Option<ExchangeRate<To, From, R, S>> {
+        self.checked_inverse_ext(Rounding::Round)
+    }
+
+    /// Checked inverse rate, from `To` to `From`, with rounding. Returning
+    /// `None` if overflow occurred or the rate is zero.
+    #[must_use]
+    pub fn checked_inverse_ext(self, rounding: Rounding) -> Option<ExchangeRate<To, From, R, S>> {
+        let rate = self.rate.mantissa();
+        let r = if S > 0 {
+            // 10^S * 10^S / rate, without 10^(2S) which may overflow
+            let exp = R::get_exp(S as usize)?;
+            exp.calc_mul_div(exp, rate, rounding)?
+        } else {
+            R::ONE.checked_div_ext(rate, -2 * S, rounding)?
+        };
+        Some(ExchangeRate::new(ConstScaleFpdec::from_mantissa(r)))
+    }
+}
+
+impl<From, To, R: Clone, const S: i32> Clone for ExchangeRate<From, To, R, S> {
+    fn clone(&self) -> Self {
+        Self {
+            rate: self.rate.clone(),
+            _currencies: PhantomData,
+        }
+    }
+}
+
+impl<From, To, R: Copy, const S: i32> Copy for ExchangeRate<From, To, R, S> {}
+
+impl<From, To, R: PartialEq, const S: i32> PartialEq for ExchangeRate<From, To, R, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rate == other.rate
+    }
+}
+
+impl<From, To, R: Eq, const S: i32> Eq for ExchangeRate<From, To, R, S> {}
+
+impl<From, To, R, const S: i32> fmt::Debug for ExchangeRate<From, To, R, S>
+where
+    From: CurrencyUnit,
+    To: CurrencyUnit,
+    R: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "ExchangeRate({}/{},{:?})",
+            From::CURRENCY.code,
+            To::CURRENCY.code,
+            self.rate
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::unit::{BHD, EUR, JPY, USD};
+    use super::*;
+    extern crate std;
+    use std::format;
+
+    #[test]
+    fn test_money() {
+        type Usd = Money<i64, USD>;
+        let a: Usd = "1.23".parse().unwrap();
+        let b: Usd = "-0.03".parse().unwrap();
+        assert_eq!(a.mantissa(), 123);
+        assert_eq!(a + b, Usd::from_mantissa(120));
+        assert_eq!(a - b, Usd::from_mantissa(126));
+        assert_eq!(a.checked_add(Usd::from_mantissa(i64::MAX)), None);
+        assert_eq!([a, b, a].iter().sum::<Usd>(), Usd::from_mantissa(243));
+        assert!(b < a);
+        assert!(b.is_neg() && (-b).is_pos() && b.abs() == -b);
+        assert!(Usd::default().is_zero());
+
+        let mut c = a;
+        c += a;
+        c -= b;
+        assert_eq!(c, Usd::from_mantissa(249));
+
+        assert_eq!(format!("{:?}", c), "Money(249,USD)");
+        assert_eq!(format!("{}", c), "USD 2.49");
+        assert_eq!(format!("{:>10.1}", c), "   USD 2.5");
+        assert_eq!(
+            "1.234".parse::<Usd>().map_err(|e| e.kind()),
+            Err(crate::ParseErrorKind::Precision(1))
+        );
+    }
+
+    #[test]
+    fn test_exchange() {
+        let usd: Money<i64, USD> = "-1000.00".parse().unwrap();
+
+        // to smaller scale
+        let rate: ExchangeRate<USD, JPY, i64, 4> =
+            ExchangeRate::new(ConstScaleFpdec::from_mantissa(1498765));
+        assert_eq!((usd * rate).mantissa(), -149877);
+        let jpy = usd.checked_convert_ext(rate, Rounding::Ceiling).unwrap();
+        assert_eq!(jpy.mantissa(), -149876);
+
+        // to bigger scale, with smaller inner type of rate
+        let rate: ExchangeRate<USD, BHD, i32, 6> =
+            ExchangeRate::new(ConstScaleFpdec::from_mantissa(376000));
+        assert_eq!(format!("{}", usd * rate), "-BHD 376.000");
+
+        // inverse
+        let rate: ExchangeRate<EUR, USD, i64, 4> =
+            ExchangeRate::new(ConstScaleFpdec::from_mantissa(12500));
+        let inv = rate.checked_inverse().unwrap();
+        assert_eq!(inv.rate(), ConstScaleFpdec::from_mantissa(8000));
+        assert_eq!(format!("{:?}", inv), "ExchangeRate(USD/EUR,Fpdec(8000,4))");
+        let zero: ExchangeRate<EUR, USD, i64, 4> = ExchangeRate::new(ConstScaleFpdec::ZERO);
+        assert_eq!(zero.checked_inverse(), None);
+
+        // inverse with 10^(2S) out of the inner type
+        let rate: ExchangeRate<EUR, USD, i32, 5> =
+            ExchangeRate::new(ConstScaleFpdec::from_mantissa(108765));
+        let inv = rate.checked_inverse().unwrap();
+        assert_eq!(inv.rate(), ConstScaleFpdec::from_mantissa(91941));
+        let inv = rate.checked_inverse_ext(Rounding::Ceiling).unwrap();
+        assert_eq!(inv.rate(), ConstScaleFpdec::from_mantissa(91942));
+        let small: ExchangeRate<EUR, USD, i32, 5> =
+            ExchangeRate::new(ConstScaleFpdec::from_mantissa(1));
+        assert_eq!(small.checked_inverse(), None);
+
+        // overflow
+        let rate: ExchangeRate<USD, JPY, i64, 4> =
+            ExchangeRate::new(ConstScaleFpdec::from_mantissa(1500000));
+        assert_eq!(
+            Money::<i64, USD>::from_mantissa(i64::MAX).checked_convert(rate),
+            None
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use super::{Currency, CurrencyDisplay, CurrencyFormat};
+use crate::fpdec_inner::FpdecInner;
+use crate::OobScaleFpdec;
+
+use core::{cmp::Ordering, fmt};
+
+/// Compact id of asset, which is resolved by [`AssetRegistry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AssetId(pub u32);
+
+/// Registry of assets, which resolves [`AssetId`] into the currency and
+/// the scale for [`RuntimeMoney`].
+///
+/// It is implemented for `[Currency]` by index, so [`CURRENCIES`](super::CURRENCIES)
+/// and your own tables can be used directly.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::currency::{AssetId, AssetRegistry, Currency};
+///
+/// const ASSETS: &[Currency] = &[
+///     Currency { code: "BTC", minor_units: 8, symbol: "₿", name: "Bitcoin" },
+///     Currency { code: "USDT", minor_units: 6, symbol: "USDT", name: "Tether" },
+/// ];
+/// assert_eq!(ASSETS.scale(AssetId(0)), Some(8));
+/// assert_eq!(ASSETS.currency(AssetId(1)).map(|c| c.code), Some("USDT"));
+/// assert_eq!(ASSETS.currency(AssetId(2)), None);
+/// ```
+pub trait AssetRegistry {
+    /// Return the currency of the asset, or `None` if not found.
+    fn currency(&self, asset: AssetId) -> Option<&Currency>;
+
+    /// Return the scale of the asset, or `None` if not found.
+    ///
+    /// It is the currency's minor units by default.
+    fn scale(&self, asset: AssetId) -> Option<i32> {
+        self.currency(asset).map(|c| c.minor_units as i32)
+    }
+}
+
+impl AssetRegistry for [Currency] {
+    fn currency(&self, asset: AssetId) -> Option<&Currency> {
+        self.get(usize::try_from(asset.0).ok()?)
+    }
+}
+
+/// Error of [`RuntimeMoney`] operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoneyError {
+    /// The operands are in different assets, the left one and the right one.
+    AssetMismatch(AssetId, AssetId),
+    /// The asset is not found in the registry.
+    UnknownAsset(AssetId),
+    /// Overflow.
+    Overflow,
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AssetMismatch(a, b) => write!(f, "asset mismatch: {} and {}", a.0, b.0),
+            Self::UnknownAsset(a) => write!(f, "unknown asset: {}", a.0),
+            Self::Overflow => write!(f, "overflow"),
+        }
+    }
+}
+
+impl core::error::Error for MoneyError {}
+
+/// Amount of money in a runtime asset.
+///
+/// This is for the case with too many assets to define [`CurrencyUnit`](super::CurrencyUnit)
+/// for each one, e.g. in cryptocurrency exchanges. It holds the
+/// mantissa of [`OobScaleFpdec`] and the [`AssetId`], whose scale is
+/// resolved by [`AssetRegistry`]. The operations check the asset at
+/// runtime and return [`MoneyError::AssetMismatch`] on mismatch.
+///
+/// Since there is no scale in the operations of the same asset, the
+/// registry is needed only in display.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::fpdec;
+/// use primitive_fixed_point_decimal::currency::{AssetId, Currency, CurrencyFormat, MoneyError, RuntimeMoney};
+///
+/// const ASSETS: &[Currency] = &[
+///     Currency { code: "BTC", minor_units: 8, symbol: "₿", name: "Bitcoin" },
+///     Currency { code: "USDT", minor_units: 6, symbol: "USDT", name: "Tether" },
+/// ];
+/// const BTC: AssetId = AssetId(0);
+/// const USDT: AssetId = AssetId(1);
+///
+/// let a = RuntimeMoney::new(fpdec!(0.5, 8), BTC);
+/// let b = RuntimeMoney::new(fpdec!(0.25, 8), BTC);
+/// let c: RuntimeMoney<i64> = RuntimeMoney::new(fpdec!(1000, 6), USDT);
+///
+/// let sum = a.try_add(b).unwrap();
+/// assert_eq!(sum.amount(), fpdec!(0.75, 8));
+/// assert!(a > b);
+///
+/// // different assets
+/// assert_eq!(a.try_add(c), Err(MoneyError::AssetMismatch(BTC, USDT)));
+/// assert_eq!(a.partial_cmp(&c), None);
+///
+/// let cf = CurrencyFormat::CODE;
+/// assert_eq!(format!("{}", sum.display(ASSETS, &cf).unwrap()), "BTC 0.75000000");
+/// assert_eq!(format!("{}", c.display(ASSETS, &cf).unwrap()), "USDT 1,000.000000");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RuntimeMoney<I> {
+    inner: I,
+    asset: AssetId,
+}
+
+impl<I> RuntimeMoney<I>
+where
+    I: FpdecInner,
+{
+    /// Make money from the amount, whose scale must be the asset's.
+    #[must_use]
+    pub const fn new(amount: OobScaleFpdec<I>, asset: AssetId) -> Self {
+        Self {
+            inner: amount.mantissa(),
+            asset,
+        }
+    }
+
+    /// Make money from the mantissa, whose scale is the asset's.
+    #[must_use]
+    pub const fn from_mantissa(i: I, asset: AssetId) -> Self {
+        Self { inner: i, asset }
+    }
+
+    /// Make zero money of the asset.
+    #[must_use]
+    pub const fn zero(asset: AssetId) -> Self {
+        Self::from_mantissa(I::ZERO, asset)
+    }
+
+    /// Get the amount, whose scale is the asset's.
+    #[must_use]
+    pub const fn amount(self) -> OobScaleFpdec<I> {
+        OobScaleFpdec::from_mantissa(self.inner)
+    }
+
+    /// Get the mantissa, whose scale is the asset's.
+    #[must_use]
+    pub const fn mantissa(self) -> I {
+        self.inner
+    }
+
+    /// Get the asset.
+    #[must_use]
+    pub const fn asset(self) -> AssetId {
+        self.asset
+    }
+
+    /// Return if zero.
+    pub fn is_zero(self) -> bool {
+        self.inner.is_zero()
+    }
+
+    /// Computes `self + rhs`, returning error if the assets are different
+    /// or overflow occurred.
+    pub fn try_add(self, rhs: Self) -> Result<Self, MoneyError> {
+        self.check_asset(rhs)?;
+        let inner = self
+            .inner
+            .checked_add(&rhs.inner)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self::from_mantissa(inner, self.asset))
+    }
+
+    /// Computes `self - rhs`, returning error if the assets are different
+    /// or overflow occurred.
+    pub fn try_sub(self, rhs: Self) -> Result<Self, MoneyError> {
+        self.check_asset(rhs)?;
+        let inner = self
+            .inner
+            .checked_sub(&rhs.inner)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self::from_mantissa(inner, self.asset))
+    }
+
+    /// Compares `self` and `rhs`, returning error if the assets are
+    /// different.
+    ///
+    /// [`PartialOrd`] is also implemented, which returns `None` in this case.
+    pub fn try_cmp(self, rhs: Self) -> Result<Ordering, MoneyError> {
+        self.check_asset(rhs)?;
+        Ok(self.inner.cmp(&rhs.inner))
+    }
+
+    /// Display with the currency symbol or code, resolved by the registry.
+    /// Returning error if the asset is not found in the registry.
+    ///
+    /// See [`CurrencyDisplay`] for details.
+    pub fn display<'a, R>(
+        self,
+        registry: &'a R,
+        cf: &'a CurrencyFormat,
+    ) -> Result<CurrencyDisplay<'a, I>, MoneyError>
+    where
+        R: AssetRegistry + ?Sized,
+    {
+        let unknown = MoneyError::UnknownAsset(self.asset);
+        Ok(CurrencyDisplay {
+            inner: self.inner,
+            scale: registry.scale(self.asset).ok_or(unknown)?,
+            currency: registry.currency(self.asset).ok_or(unknown)?,
+            cf,
+        })
+    }
+
+    fn check_asset(self, rhs: Self) -> Result<(), MoneyError> {
+        if self.asset == rhs.asset {
+            Ok(())
+        } else {
+            Err(MoneyError::AssetMismatch(self.asset, rhs.asset))
+        }
+    }
+}
+
+/// Compare the amounts of the same asset, or `None` if the assets are
+/// different.
+impl<I> PartialOrd for RuntimeMoney<I>
+where
+    I: FpdecInner,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.try_cmp(*other).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currency::CURRENCIES;
+    extern crate std;
+    use std::format;
+
+    #[test]
+    fn test_runtime_money() {
+        let usd = AssetId(CURRENCIES.binary_search_by(|c| c.code.cmp("USD")).unwrap() as u32);
+        let jpy = AssetId(CURRENCIES.binary_search_by(|c| c.code.cmp("JPY")).unwrap() as u32);
+
+        let a = RuntimeMoney::from_mantissa(12345_i32, usd);
+        let b = RuntimeMoney::from_mantissa(-345, usd);
+        let c = RuntimeMoney::from_mantissa(12345, jpy);
+
+        assert_eq!(a.try_add(b), Ok(RuntimeMoney::from_mantissa(12000, usd)));
+        assert_eq!(a.try_sub(b), Ok(RuntimeMoney::from_mantissa(12690, usd)));
+        assert_eq!(a.try_add(c), Err(MoneyError::AssetMismatch(usd, jpy)));
+        assert_eq!(c.try_sub(a), Err(MoneyError::AssetMismatch(jpy, usd)));
+        assert_eq!(
+            RuntimeMoney::from_mantissa(i32::MAX, usd).try_add(a),
+            Err(MoneyError::Overflow)
+        );
+
+        assert_eq!(a.try_cmp(b), Ok(Ordering::Greater));
+        assert_eq!(a.try_cmp(c), Err(MoneyError::AssetMismatch(usd, jpy)));
+        assert!(b < a && a != c);
+        assert_eq!(a.partial_cmp(&c), None);
+        assert!(RuntimeMoney::<i32>::zero(jpy).is_zero());
+
+        let cf = CurrencyFormat::DEFAULT;
+        assert_eq!(
+            format!("{}", a.display(CURRENCIES, &cf).unwrap()),
+            "$123.45"
+        );
+        assert_eq!(format!("{}", b.display(CURRENCIES, &cf).unwrap()), "-$3.45");
+        assert_eq!(
+            format!("{}", c.display(CURRENCIES, &cf).unwrap()),
+            "¥12,345"
+        );
+
+        let unknown = RuntimeMoney::from_mantissa(1, AssetId(10000));
+        assert_eq!(
+            unknown.display(CURRENCIES, &cf).err(),
+            Some(MoneyError::UnknownAsset(AssetId(10000)))
+        );
+        assert_eq!(
+            format!("{}", a.try_add(c).unwrap_err()),
+            format!("asset mismatch: {} and {}", usd.0, jpy.0)
+        );
+    }
+}
@@ -1,24 +1,40 @@
-use crate::{ParseError, Rounding};
+use crate::stochastic::{stochastic_away, StochasticRng};
+use crate::{
+    ArithmeticError, IntoRatioInt, NumberFormat, ParseError, ParseErrorKind, ParseOptions, Rounding,
+};
 
 use core::{
-    fmt,
+    cmp::{Ordering, Reverse},
+    fmt::{self, Write},
     mem::MaybeUninit,
-    num::{IntErrorKind, ParseIntError},
     ops::{AddAssign, SubAssign},
 };
 
 use num_traits::{
+    float::FloatCore,
     identities::{ConstOne, ConstZero, Zero},
     int::PrimInt,
-    ops::wrapping::WrappingAdd,
-    AsPrimitive, Num,
+    ops::{
+        checked::CheckedSub,
+        wrapping::{WrappingAdd, WrappingMul, WrappingNeg, WrappingSub},
+    },
+    AsPrimitive,
 };
 
 /// The trait for underlying representation.
 ///
 /// Normal users don't need to use this trait.
 pub trait FpdecInner:
-    PrimInt + ConstOne + ConstZero + AddAssign + SubAssign + WrappingAdd + Zero
+    PrimInt
+    + ConstOne
+    + ConstZero
+    + AddAssign
+    + SubAssign
+    + WrappingAdd
+    + WrappingSub
+    + WrappingMul
+    + WrappingNeg
+    + Zero
 {
     const MAX: Self;
     const MIN: Self;
@@ -26,7 +42,6 @@ pub trait FpdecInner:
     const HUNDRED: Self;
     const MAX_POWERS: Self;
     const DIGITS: u32;
-    const NEG_MIN_STR: &'static str;
 
     /// Used by unsigned_abs() method.
     type Unsigned: FpdecInner + AsPrimitive<u8> + AsPrimitive<usize>;
@@ -59,6 +74,22 @@ pub trait FpdecInner:
         self.calc_mul_div(b, Self::get_exp(i)?, rounding)
     }
 
+    /// Calculate `self * b / c` with rounding, and the remainder `r` where
+    /// `self * b == q * c + r`.
+    /// Since i128 and u128 do not have wider types, then have
+    /// their own implementations.
+    ///
+    /// Return `None` if the remainder is negative for unsigned types.
+    fn calc_mul_div_rem(self, b: Self, c: Self, rounding: Rounding) -> Option<(Self, Self)> {
+        let p = self.as_wider() * b.as_wider();
+        let c = c.as_wider();
+        let q = p.rounding_div(c, rounding)?;
+
+        // |q * c| <= |p| + |c|, no overflow in wider type
+        let r = p.checked_sub(&(q * c))?;
+        Some((Self::from_wider(q)?, Self::from_wider(r)?))
+    }
+
     // works only when: diff_scale in range [-Self::DIGITS, Self::DIGITS]
     // diff_scale = scale (self + rhs - result)
     fn checked_mul_ext(self, rhs: Self, diff_scale: i32, rounding: Rounding) -> Option<Self> {
@@ -102,6 +133,207 @@ pub trait FpdecInner:
         }
     }
 
+    // same with checked_mul_ext() but with the remainder, whose scale is
+    // scale (self + rhs), where `self * rhs == result + remainder`
+    fn checked_mul_rem(
+        self,
+        rhs: Self,
+        diff_scale: i32,
+        rounding: Rounding,
+    ) -> Option<(Self, Self)> {
+        if diff_scale > 0 {
+            let exp = Self::get_exp(diff_scale as usize)?;
+            self.calc_mul_div_rem(rhs, exp, rounding)
+        } else {
+            // no rounding
+            Some((self.checked_mul_ext(rhs, diff_scale, rounding)?, Self::ZERO))
+        }
+    }
+
+    // same with checked_div_ext() but with the remainder, whose scale is
+    // max(scale (self), scale (rhs + result)), where
+    // `self == result * rhs + remainder`
+    fn checked_div_rem(
+        self,
+        rhs: Self,
+        diff_scale: i32,
+        rounding: Rounding,
+    ) -> Option<(Self, Self)> {
+        if diff_scale > 0 {
+            // self / rhs / diff_exp, in two roundings as checked_div_ext(),
+            // where `self == q1 * rhs + r1` and `q1 == q * diff_exp + r2`,
+            // so the remainder is `r2 * rhs + r1`
+            let exp = Self::get_exp(diff_scale as usize)?;
+            let (q1, r1) = self.calc_mul_div_rem(Self::ONE, rhs, rounding)?;
+            let (q, r2) = q1.calc_mul_div_rem(Self::ONE, exp, rounding)?;
+
+            // `r2 * rhs` may overflow while the remainder does not, if `r1`
+            // has the opposite sign. Move one `rhs` into `r1` then.
+            let r2_neg = (r2 < Self::ZERO) != (rhs < Self::ZERO);
+            let (r2, r1) = if !r2.is_zero() && !r1.is_zero() && (r1 < Self::ZERO) != r2_neg {
+                if r2 > Self::ZERO {
+                    (r2 - Self::ONE, r1 + rhs)
+                } else {
+                    (r2 + Self::ONE, r1 - rhs)
+                }
+            } else {
+                (r2, r1)
+            };
+            Some((q, r2.checked_mul(&rhs)?.checked_add(&r1)?))
+        } else {
+            // self * diff_exp / rhs
+            let exp = Self::get_exp(-diff_scale as usize)?;
+            self.calc_mul_div_rem(exp, rhs, rounding)
+        }
+    }
+
+    // same with checked_mul_ext() but returns the reason of failure.
+    fn try_mul_ext(
+        self,
+        rhs: Self,
+        diff_scale: i32,
+        rounding: Rounding,
+    ) -> Result<Self, ArithmeticError> {
+        check_diff_scale::<Self>(diff_scale)?;
+        self.checked_mul_ext(rhs, diff_scale, rounding)
+            .ok_or(ArithmeticError::out_of_bounds((self ^ rhs) < Self::ZERO))
+    }
+
+    // same with checked_div_ext() but returns the reason of failure.
+    fn try_div_ext(
+        self,
+        rhs: Self,
+        diff_scale: i32,
+        rounding: Rounding,
+    ) -> Result<Self, ArithmeticError> {
+        if rhs.is_zero() {
+            return Err(ArithmeticError::DivByZero);
+        }
+        check_diff_scale::<Self>(diff_scale)?;
+        self.checked_div_ext(rhs, diff_scale, rounding)
+            .ok_or(ArithmeticError::out_of_bounds((self ^ rhs) < Self::ZERO))
+    }
+
+    // same with calc_mul_div() but returns the reason of failure.
+    fn try_mul_div(self, b: Self, c: Self, rounding: Rounding) -> Result<Self, ArithmeticError> {
+        if c.is_zero() {
+            return Err(ArithmeticError::DivByZero);
+        }
+        self.calc_mul_div(b, c, rounding)
+            .ok_or(ArithmeticError::out_of_bounds((self ^ b ^ c) < Self::ZERO))
+    }
+
+    // same with checked_mul_ext() but wraps around on overflow, and
+    // returns if overflowed.
+    // The diff_scale out of range is taken as overflow too, see
+    // out_of_range_result().
+    fn overflowing_mul_ext(self, rhs: Self, diff_scale: i32, rounding: Rounding) -> (Self, bool) {
+        if let Some(r) = self.checked_mul_ext(rhs, diff_scale, rounding) {
+            return (r, false);
+        }
+        let Some(exp) = Self::get_exp(diff_scale.unsigned_abs() as usize) else {
+            return out_of_range_result(self, rhs);
+        };
+
+        let r = if diff_scale > 0 {
+            self.wrapping_mul_div(rhs, exp, rounding)
+        } else {
+            self.wrapping_mul(&rhs).wrapping_mul(&exp)
+        };
+        (r, true)
+    }
+
+    // same with checked_div_ext() but wraps around on overflow, and
+    // returns if overflowed.
+    // The diff_scale out of range is taken as overflow too, see
+    // out_of_range_result().
+    // Panics if rhs is zero.
+    fn overflowing_div_ext(self, rhs: Self, diff_scale: i32, rounding: Rounding) -> (Self, bool) {
+        assert!(!rhs.is_zero(), "division by zero");
+        if let Some(r) = self.checked_div_ext(rhs, diff_scale, rounding) {
+            return (r, false);
+        }
+        let Some(exp) = Self::get_exp(diff_scale.unsigned_abs() as usize) else {
+            return out_of_range_result(self, rhs);
+        };
+
+        if diff_scale > 0 {
+            // only `MIN / -1` fails, so `rhs * exp` does not overflow
+            self.overflowing_mul_div(Self::ONE, rhs * exp, rounding)
+        } else {
+            self.overflowing_mul_div(exp, rhs, rounding)
+        }
+    }
+
+    // same with overflowing_mul_ext() but saturates at MIN or MAX.
+    fn saturating_mul_ext(self, rhs: Self, diff_scale: i32, rounding: Rounding) -> Self {
+        match self.overflowing_mul_ext(rhs, diff_scale, rounding) {
+            (r, false) => r,
+            _ => saturate_by_sign(self, rhs),
+        }
+    }
+
+    // same with overflowing_div_ext() but saturates at MIN or MAX.
+    fn saturating_div_ext(self, rhs: Self, diff_scale: i32, rounding: Rounding) -> Self {
+        match self.overflowing_div_ext(rhs, diff_scale, rounding) {
+            (r, false) => r,
+            _ => saturate_by_sign(self, rhs),
+        }
+    }
+
+    fn overflowing_mul_div(self, b: Self, c: Self, rounding: Rounding) -> (Self, bool) {
+        match self.calc_mul_div(b, c, rounding) {
+            Some(q) => (q, false),
+            None => (self.wrapping_mul_div(b, c, rounding), true),
+        }
+    }
+
+    // Calculate `self * b / c` with rounding, wrapping around at the
+    // boundary of the type. `c` must not be zero.
+    fn wrapping_mul_div(self, b: Self, c: Self, rounding: Rounding) -> Self {
+        if let Some(q) = self.calc_mul_div(b, c, rounding) {
+            return q;
+        }
+
+        // split `self` by `c`, then `self * b / c == hi * b + lo * b / c`,
+        // where `lo * b / c` does not overflow.
+        let Some(hi) = self.checked_div(&c) else {
+            // MIN / -1
+            return self.wrapping_mul(&b).wrapping_neg();
+        };
+        let lo = self % c;
+        let (t, r) = lo
+            .calc_mul_div_rem(b, c, Rounding::TowardsZero)
+            .expect("no overflow");
+        let q = hi.wrapping_mul(&b).wrapping_add(&t);
+        if r.is_zero() {
+            return q;
+        }
+
+        // The last digit of the real quotient, not the wrapped one, for
+        // the rounding kinds depending on it. The real quotient has the
+        // sign of `hi * b`, because `|t| < |b| <= |hi * b|`.
+        let last = (hi % Self::TEN) * (b % Self::TEN) + t % Self::TEN; // in [-90, 90]
+        let mut last = last % Self::TEN;
+        if last < Self::ZERO {
+            last += Self::TEN;
+        }
+        if (hi ^ b) < Self::ZERO && !last.is_zero() {
+            last = Self::TEN - last;
+        }
+
+        let is_neg = (r ^ c) < Self::ZERO;
+        let ur = r.unsigned_abs();
+        let half = || ur.cmp(&(c.unsigned_abs() - ur));
+        if !round_away(rounding, last, is_neg, half) {
+            q
+        } else if is_neg {
+            q.wrapping_sub(&Self::ONE)
+        } else {
+            q.wrapping_add(&Self::ONE)
+        }
+    }
+
     // diff_scale = scale (src - dst)
     fn round_diff_with_rounding(self, diff_scale: i32, rounding: Rounding) -> Self {
         if diff_scale <= 0 {
@@ -117,6 +349,63 @@ pub trait FpdecInner:
         }
     }
 
+    // Convert to another scale, with rounding if to smaller scale.
+    // Return `None` if overflow occurred.
+    // diff_scale = scale (src - dst)
+    fn checked_rescale(self, diff_scale: i32, rounding: Rounding) -> Option<Self> {
+        if diff_scale < 0 {
+            if self.is_zero() {
+                return Some(Self::ZERO);
+            }
+            let exp = Self::get_exp(diff_scale.unsigned_abs() as usize)?;
+            return self.checked_mul(&exp);
+        }
+        if diff_scale == 0 {
+            return Some(self);
+        }
+
+        let k = diff_scale as usize;
+        if let Some(exp) = Self::get_exp(k) {
+            return self.rounding_div(exp, rounding);
+        }
+
+        // `|self| < 10^k`, so the result is 0 or ±1. Compare `|self|`
+        // with the half of 10^k, that is 5*10^(k-1).
+        let is_neg = self < Self::ZERO;
+        let half = || cmp_half_exp(self.unsigned_abs(), k);
+        if self.is_zero() || !round_away(rounding, Self::ZERO, is_neg, half) {
+            Some(Self::ZERO)
+        } else if is_neg {
+            Self::ZERO.checked_sub(&Self::ONE)
+        } else {
+            Some(Self::ONE)
+        }
+    }
+
+    // Convert to another scale, returning error of `Overflow` if to bigger
+    // scale, or `Precision` if to smaller scale and precision lost.
+    // diff_scale = scale (src - dst)
+    fn try_rescale(self, diff_scale: i32) -> Result<Self, ParseError> {
+        if diff_scale <= 0 {
+            return self
+                .checked_rescale(diff_scale, Rounding::Round)
+                .ok_or(ParseErrorKind::Overflow.into());
+        }
+
+        if self.is_zero() {
+            return Ok(Self::ZERO);
+        }
+        let diff = diff_scale as u32;
+        let Some(exp) = Self::get_exp(diff as usize) else {
+            return Err(precision_error(self, diff));
+        };
+        if (self % exp).is_zero() {
+            Ok(self / exp)
+        } else {
+            Err(precision_error(self, diff))
+        }
+    }
+
     /// Calculate rounding division.
     ///
     /// Examples:
@@ -159,8 +448,9 @@ pub trait FpdecInner:
                         // `self` and `b` have same sign. So does `remain`.
                         // If b>0, then we check: remain - (b - remain) >= 0;
                         // else, we check: remain - (b - remain) <= 0.
-                        // Finally, we get:
-                        if (remain - (b - remain)) ^ b >= Self::ZERO {
+                        // Finally, we get (the xor misses 0 if b<0):
+                        let d = remain - (b - remain);
+                        if d ^ b >= Self::ZERO || d == Self::ZERO {
                             q + Self::ONE
                         } else {
                             q
@@ -174,6 +464,14 @@ pub trait FpdecInner:
                         }
                     }
                 }
+                _ => {
+                    let r = remain.unsigned_abs();
+                    if round_away(rounding, q, false, || r.cmp(&(b.unsigned_abs() - r))) {
+                        q + Self::ONE
+                    } else {
+                        q
+                    }
+                }
             }
         } else {
             // signed types and self and b have different sign.
@@ -188,256 +486,1556 @@ pub trait FpdecInner:
                         q
                     }
                 }
+                _ => {
+                    let r = remain.unsigned_abs();
+                    if round_away(rounding, q, true, || r.cmp(&(b.unsigned_abs() - r))) {
+                        q - Self::ONE
+                    } else {
+                        q
+                    }
+                }
             }
         }
         .into() // Some()
     }
 
-    // INTERNAL
-    // Parse an string as negative.
-    // We try to parse it as positive first. If fail for overflow,
-    // then it maybe the MIN value.
-    fn parse_int_as_negative(s: &str) -> Result<Self, ParseIntError>
+    /// Calculate `self / b` with stochastic rounding, which rounds away
+    /// from zero with probability equal to the fraction of the remainder.
+    fn rounding_div_stochastic<R>(self, b: Self, rng: &mut R) -> Option<Self>
     where
-        Self: Num<FromStrRadixErr = ParseIntError>,
+        R: StochasticRng + ?Sized,
     {
-        match Self::from_str_radix(s, 10) {
-            Ok(num) => {
-                // Return -num.
-                // The FpdecInner works for both signed and unsigned types.
-                // But unsigned type does not support negative operation.
-                // So we use Two's Complement to calculate negative.
-                Ok((!num).wrapping_add(&Self::ONE))
-            }
-            Err(err) => {
-                if err.kind() == &IntErrorKind::PosOverflow
-                    && s.trim_start_matches('0') == Self::NEG_MIN_STR
-                {
-                    Ok(Self::MIN)
-                } else {
-                    Err(err)
-                }
-            }
+        let q = self.checked_div(&b)?;
+        let remain = self % b;
+        if remain == Self::ZERO || !stochastic_away(remain.unsigned_abs(), b.unsigned_abs(), rng) {
+            Some(q)
+        } else if (self ^ b) >= Self::ZERO {
+            Some(q + Self::ONE)
+        } else {
+            Some(q - Self::ONE)
         }
     }
 
-    fn try_from_str(s: &str, scale: i32) -> Result<Self, ParseError>
+    /// Calculate `self * b / c` with stochastic rounding.
+    /// Since i128 and u128 do not have wider types, then have
+    /// their own implementations.
+    fn calc_mul_div_stochastic<R>(self, b: Self, c: Self, rng: &mut R) -> Option<Self>
+    where
+        R: StochasticRng + ?Sized,
+    {
+        let p = self.as_wider() * b.as_wider();
+        p.rounding_div_stochastic(c.as_wider(), rng)
+            .and_then(Self::from_wider)
+    }
+
+    // same with checked_mul_ext() but with stochastic rounding
+    fn checked_mul_stochastic<R>(self, rhs: Self, diff_scale: i32, rng: &mut R) -> Option<Self>
     where
-        Self: Num<FromStrRadixErr = ParseIntError>,
+        R: StochasticRng + ?Sized,
     {
-        let (num, raw_scale) = Self::try_from_str_only(s)?;
-        if num.is_zero() {
-            Ok(num)
-        } else if raw_scale == scale {
-            Ok(num)
-        } else if raw_scale > scale {
-            Err(ParseError::Precision)
+        if diff_scale > 0 {
+            let exp = Self::get_exp(diff_scale as usize)?;
+            self.calc_mul_div_stochastic(rhs, exp, rng)
         } else {
-            Self::get_exp((scale - raw_scale) as usize)
-                .ok_or(ParseError::Precision)?
-                .checked_mul(&num)
-                .ok_or(ParseError::Overflow)
+            // no rounding
+            self.checked_mul_ext(rhs, diff_scale, Rounding::Round)
+        }
+    }
+
+    // same with checked_div_ext() but with stochastic rounding
+    fn checked_div_stochastic<R>(self, rhs: Self, diff_scale: i32, rng: &mut R) -> Option<Self>
+    where
+        R: StochasticRng + ?Sized,
+    {
+        if diff_scale > 0 {
+            // self / rhs / diff_exp
+            // Both roundings are unbiased, so is the result.
+            let exp = Self::get_exp(diff_scale as usize)?;
+            let q = self.rounding_div_stochastic(rhs, rng)?;
+            q.rounding_div_stochastic(exp, rng)
+        } else if diff_scale < 0 {
+            // self * diff_exp / rhs
+            let exp = Self::get_exp(-diff_scale as usize)?;
+            self.calc_mul_div_stochastic(exp, rhs, rng)
+        } else {
+            self.rounding_div_stochastic(rhs, rng)
+        }
+    }
+
+    // INTERNAL
+    fn try_from_str(s: &str, scale: i32) -> Result<Self, ParseError> {
+        Self::try_from_ascii(s.as_bytes(), scale)
+    }
+
+    // Try the fast path first for the common cases, and go to the
+    // general path for the others. The methods with options always go
+    // the general path, which is used to check this in fuzzing.
+    fn try_from_ascii(s: &[u8], scale: i32) -> Result<Self, ParseError> {
+        match parse_fast(s, scale) {
+            Some(num) => Ok(num),
+            None => Self::try_from_ascii_opts(s, scale, None, ParseOptions::DEFAULT),
         }
     }
 
     // Guess and return the scale by the input string.
-    fn try_from_str_only(s: &str) -> Result<(Self, i32), ParseError>
+    //
+    // Scientific notation is supported, e.g. "1.5e-7" and "3E+4".
+    // The exponent is folded into the returned scale.
+    fn try_from_str_only(s: &str) -> Result<(Self, i32), ParseError> {
+        Self::try_from_str_only_opts(s, ParseOptions::DEFAULT)
+    }
+
+    // Parse the string with scale, and round the extra precision
+    // instead of returning `ParseErrorKind::Precision`.
+    fn try_from_str_ext(s: &str, scale: i32, rounding: Rounding) -> Result<Self, ParseError> {
+        Self::try_from_str_opts(s, scale, Some(rounding), ParseOptions::DEFAULT)
+    }
+
+    // Convert from float through its shortest decimal representation
+    // which round-trips, e.g. "1e-1" for 0.1, but not the binary expansion
+    // 0.1000000000000000055511151231257827... The `LowerExp` of the
+    // float types in `core` gives exactly that. Then round the extra
+    // precision like parsing string.
+    fn try_from_float_ext<F>(f: F, scale: i32, rounding: Rounding) -> Result<Self, ParseError>
     where
-        Self: Num<FromStrRadixErr = ParseIntError>,
+        F: FloatCore + fmt::LowerExp,
     {
-        if s.is_empty() {
-            return Err(ParseError::Empty);
+        if f.is_nan() {
+            return Err(ParseErrorKind::Invalid.into());
+        }
+        if f.is_infinite() {
+            return Err(ParseErrorKind::Overflow.into());
         }
+        let mut buf = FloatBuf::new();
+        write!(buf, "{f:e}").map_err(|_| ParseErrorKind::Invalid)?;
+        Self::try_from_ascii_opts(buf.as_bytes(), scale, Some(rounding), ParseOptions::DEFAULT)
+            .map_err(|e| e.kind().into())
+    }
 
-        if let Some((int_str, frac_str)) = s.split_once('.') {
-            let int_num = Self::from_str_radix(int_str, 10)?;
+    // Parse the string with scale and options. Round the extra precision
+    // if `rounding` is set.
+    fn try_from_str_opts(
+        s: &str,
+        scale: i32,
+        rounding: Option<Rounding>,
+        opts: ParseOptions,
+    ) -> Result<Self, ParseError> {
+        Self::try_from_ascii_opts(s.as_bytes(), scale, rounding, opts)
+    }
 
-            let frac_num = if s.as_bytes()[0] == b'-' {
-                Self::parse_int_as_negative(frac_str)?
-            } else {
-                Self::from_str_radix(frac_str, 10)?
-            };
+    fn try_from_str_only_opts(s: &str, opts: ParseOptions) -> Result<(Self, i32), ParseError> {
+        Self::try_from_ascii_only_opts(s.as_bytes(), opts)
+    }
 
-            let inner = if int_num.is_zero() {
-                // only fraction part
-                frac_num
-            } else {
-                // exp * integer + fraction
-                Self::get_exp(frac_str.len())
-                    .ok_or(ParseError::Precision)?
-                    .checked_mul(&int_num)
-                    .ok_or(ParseError::Overflow)?
-                    .checked_add(&frac_num)
-                    .ok_or(ParseError::Overflow)?
-            };
-            Ok((inner, frac_str.len() as i32))
-        } else {
-            // only integer part
-            if s == "0" || s == "-0" || s == "+0" {
-                return Ok((Self::ZERO, 0));
+    // Parse the ASCII bytes. All the string parsing methods above come
+    // here. No UTF-8 validation is needed, because any non-ASCII byte
+    // is invalid anyway.
+    fn try_from_ascii_opts(
+        s: &[u8],
+        scale: i32,
+        rounding: Option<Rounding>,
+        opts: ParseOptions,
+    ) -> Result<Self, ParseError> {
+        parse_with_scale(&DecStr::split(s, &opts)?, scale, rounding)
+    }
+
+    fn try_from_ascii_only_opts(s: &[u8], opts: ParseOptions) -> Result<(Self, i32), ParseError> {
+        parse_guess_scale(&DecStr::split(s, &opts)?)
+    }
+
+    // Parse as negative if `neg` is set, for the sign outside of the
+    // string, e.g. the parentheses of the accounting negative. The string
+    // itself can not be negative then.
+    fn try_from_ascii_neg_opts(
+        s: &[u8],
+        scale: i32,
+        neg: bool,
+        opts: ParseOptions,
+    ) -> Result<Self, ParseError> {
+        let mut ds = DecStr::split(s, &opts)?;
+        if neg {
+            if ds.is_neg {
+                return Err(invalid_error(ds.start));
             }
-            let new_int_str = s.trim_end_matches('0');
-            let diff = s.len() - new_int_str.len();
-            Ok((Self::from_str_radix(new_int_str, 10)?, -(diff as i32)))
+            ds.is_neg = true;
         }
+        parse_with_scale(&ds, scale, None)
     }
 
     fn display_fmt(self, scale: i32, f: &mut fmt::Formatter) -> fmt::Result {
-        // The buffer is 250 long. 50 is for the number, and 200 is for
-        // the padding zeros for specified precision and big scales.
-        // We panic if the string is too long.
-        let mut buf: [MaybeUninit<u8>; 250] = [MaybeUninit::uninit(); 250];
-        assert!(scale.abs() <= 200);
+        DisplayParts::new(self.unsigned_abs(), scale, f.precision(), Rounding::Round)
+            .fmt(self >= Self::ZERO, f)
+    }
 
-        let offset = display_num(self.unsigned_abs(), scale, f.precision(), &mut buf);
+    // Display with grouping separator and decimal mark.
+    fn display_with_fmt(
+        self,
+        scale: i32,
+        nf: &NumberFormat,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        let rounding = magnitude_rounding(nf.rounding, self < Self::ZERO);
+        DisplayParts::new(self.unsigned_abs(), scale, f.precision(), rounding).stream(
+            self >= Self::ZERO,
+            nf,
+            f,
+        )
+    }
 
-        // SAFETY: offset is updated along with buf
-        let buf = unsafe {
-            core::slice::from_raw_parts(buf[offset..].as_ptr().cast(), buf.len() - offset)
-        };
+    // Display the absolute value with the prefix and suffix, e.g. "($"
+    // and ")" for the accounting negative. The caller should put the
+    // sign into them.
+    fn display_affix_fmt(
+        self,
+        scale: i32,
+        precision: Option<usize>,
+        nf: &NumberFormat,
+        prefix: &[&str],
+        suffix: &[&str],
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        let rounding = magnitude_rounding(nf.rounding, self < Self::ZERO);
+        DisplayParts::new(self.unsigned_abs(), scale, precision, rounding)
+            .stream_affix(prefix, suffix, nf, f)
+    }
 
-        // SAFETY: all data is valid charactor
-        let s = unsafe { str::from_utf8_unchecked(buf) };
+    // Display in scientific notation, or engineering notation whose
+    // exponent is multiple of 3 if `eng` is set.
+    fn display_exp_fmt(
+        self,
+        scale: i32,
+        eng: bool,
+        upper: bool,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        let e = if upper { b'E' } else { b'e' };
+        DisplayParts::new_exp(
+            self.unsigned_abs(),
+            scale,
+            f.precision(),
+            Rounding::Round,
+            eng,
+            e,
+        )
+        .fmt(self >= Self::ZERO, f)
+    }
 
-        f.pad_integral(self >= Self::ZERO, "", s)
+    // Write the decimal at the beginning of the buffer, and return the
+    // length. Same output as `display_fmt()` without formatting options
+    // except precision, which is rounded by `rounding`. Return `None` if
+    // the buffer is too small.
+    fn try_write_to(
+        self,
+        scale: i32,
+        precision: Option<usize>,
+        rounding: Rounding,
+        buf: &mut [u8],
+    ) -> Option<usize> {
+        let is_neg = self < Self::ZERO;
+        let rounding = magnitude_rounding(rounding, is_neg);
+        let parts = DisplayParts::new(self.unsigned_abs(), scale, precision, rounding);
+        let len = parts
+            .len(&NumberFormat::DEFAULT)
+            .saturating_add(is_neg as usize);
+        let buf = buf.get_mut(..len)?;
+
+        // SAFETY: `MaybeUninit<u8>` has the same layout as `u8`, and
+        // only initialized bytes are written in.
+        let ubuf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+
+        parts.dump(ubuf);
+        if is_neg {
+            buf[0] = b'-';
+        }
+        Some(len)
     }
 
     fn checked_from_int(self, scale: i32) -> Result<Self, ParseError> {
         if scale > 0 {
-            let exp = Self::get_exp(scale as usize).ok_or(ParseError::Overflow)?;
-            self.checked_mul(&exp).ok_or(ParseError::Overflow)
+            let exp = Self::get_exp(scale as usize).ok_or(ParseErrorKind::Overflow)?;
+            Ok(self.checked_mul(&exp).ok_or(ParseErrorKind::Overflow)?)
         } else if scale < 0 {
-            let exp = Self::get_exp(-scale as usize).ok_or(ParseError::Precision)?;
+            let diff = scale.unsigned_abs();
+            let Some(exp) = Self::get_exp(diff as usize) else {
+                return Err(precision_error(self, diff));
+            };
             if !(self % exp).is_zero() {
-                return Err(ParseError::Precision);
+                return Err(precision_error(self, diff));
             }
             Ok(self / exp)
         } else {
             Ok(self)
         }
     }
+
+    // Split self into parts by the ratios, by the largest remainder method.
+    // Each part is truncated first, and then the remainder units are given
+    // one by one to the parts with the largest remainders, the first ones
+    // in ties, so the parts always sum to self.
+    fn allocate<'a, R>(self, ratios: &'a [R]) -> Option<impl Iterator<Item = Self> + 'a>
+    where
+        Self: 'a,
+        R: IntoRatioInt<Self> + Copy,
+    {
+        let mut sum = Self::ZERO;
+        for r in ratios {
+            let r = r.to_int();
+            if r < Self::ZERO {
+                return None;
+            }
+            sum = sum.checked_add(&r)?;
+        }
+        if sum.is_zero() {
+            return None;
+        }
+
+        // the truncated part, and the key of its remainder to take a unit
+        let part = move |i: usize, r: &R| {
+            let (q, rem) = self.calc_mul_div_rem(r.to_int(), sum, Rounding::TowardsZero)?;
+            Some((q, (rem.unsigned_abs(), Reverse(i))))
+        };
+
+        let mut remain = self;
+        for (i, r) in ratios.iter().enumerate() {
+            remain -= part(i, r)?.0;
+        }
+
+        // Find the smallest key to take a unit. Without allocation, it's
+        // found by one pass for each remainder unit, for the largest key
+        // below the last one. The zero remainders never take any unit,
+        // because there are more non-zero ones than the units.
+        let mut threshold = None;
+        let mut count = remain.unsigned_abs();
+        while !count.is_zero() {
+            let mut next = None;
+            for (i, r) in ratios.iter().enumerate() {
+                let key = part(i, r)?.1;
+                if threshold.is_none_or(|t| key < t) && next.is_none_or(|n| key > n) {
+                    next = Some(key);
+                }
+            }
+            threshold = next;
+            count -= Self::Unsigned::ONE;
+        }
+
+        // Each part succeeded above, so map_while() never stops early.
+        Some(ratios.iter().enumerate().map_while(move |(i, r)| {
+            let (q, key) = part(i, r)?;
+            if threshold.is_some_and(|t| key >= t) {
+                Some(take_unit(q, &mut remain))
+            } else {
+                Some(q)
+            }
+        }))
+    }
+
+    // Split self into `n` parts evenly, with the remainder units given
+    // one by one to the first parts.
+    fn split_evenly(self, n: usize) -> Option<impl Iterator<Item = Self>> {
+        if n == 0 {
+            return None;
+        }
+        let (part, mut remain) = match Self::from(n) {
+            Some(n) => (self / n, self % n),
+            None => (Self::ZERO, self), // `n` is bigger than self
+        };
+        Some((0..n).map(move |_| take_unit(part, &mut remain)))
+    }
+}
+
+// Whether to round the truncated quotient `q` away from zero, when the
+// remainder is not zero. `half()` compares the remainder with the half of
+// the divisor. `q` could be signed, or the unsigned magnitude with the
+// sign in `is_neg`.
+pub(crate) fn round_away<I: FpdecInner>(
+    rounding: Rounding,
+    q: I,
+    is_neg: bool,
+    half: impl FnOnce() -> Ordering,
+) -> bool {
+    match rounding {
+        Rounding::Floor => is_neg,
+        Rounding::Ceiling => !is_neg,
+        Rounding::TowardsZero => false,
+        Rounding::AwayFromZero => true,
+        Rounding::Round => half().is_ge(),
+        Rounding::HalfTowardsZero => half().is_gt(),
+        Rounding::HalfDown => match half() {
+            Ordering::Equal => is_neg,
+            o => o.is_gt(),
+        },
+        Rounding::HalfEven => match half() {
+            Ordering::Equal => !(q & I::ONE).is_zero(),
+            o => o.is_gt(),
+        },
+        Rounding::HalfOdd => match half() {
+            Ordering::Equal => (q & I::ONE).is_zero(),
+            o => o.is_gt(),
+        },
+        Rounding::ZeroFiveUp => {
+            let last: u8 = (q % I::TEN).unsigned_abs().as_();
+            last == 0 || last == 5
+        }
+    }
+}
+
+// The rounding kind for the magnitude of a number with sign `is_neg`,
+// which is independent of the sign. So there is no `Floor`, `Ceiling`
+// or `HalfDown` in result.
+pub(crate) fn magnitude_rounding(rounding: Rounding, is_neg: bool) -> Rounding {
+    match rounding {
+        Rounding::Floor | Rounding::Ceiling => {
+            if (rounding == Rounding::Floor) == is_neg {
+                Rounding::AwayFromZero
+            } else {
+                Rounding::TowardsZero
+            }
+        }
+        Rounding::HalfDown => {
+            if is_neg {
+                Rounding::Round
+            } else {
+                Rounding::HalfTowardsZero
+            }
+        }
+        _ => rounding,
+    }
+}
+
+// The extra added to the unsigned dividend `n` before the truncated
+// division by `d`, to get the rounded quotient, i.e. `(n + extra) / d`.
+// The kinds depending on the last digit of the quotient can not be done
+// in this way, so they get 0 here and should be adjusted by
+// `round_by_last_digit()` after the division.
+pub(crate) fn rounding_extra<U: FpdecInner>(rounding: Rounding, d: U) -> U {
+    match rounding {
+        Rounding::Floor | Rounding::TowardsZero => U::ZERO,
+        Rounding::Ceiling | Rounding::AwayFromZero => d - U::ONE,
+        Rounding::Round => d >> 1,
+        Rounding::HalfDown | Rounding::HalfTowardsZero => (d - U::ONE) >> 1,
+        Rounding::HalfEven | Rounding::HalfOdd | Rounding::ZeroFiveUp => U::ZERO,
+    }
+}
+
+// Adjust the unsigned truncated quotient `q` of divisor `d`, for the
+// kinds that `rounding_extra()` can not handle. `remain()` returns the
+// remainder. Return `None` if overflow.
+pub(crate) fn round_by_last_digit<U: FpdecInner>(
+    q: U,
+    d: U,
+    rounding: Rounding,
+    remain: impl FnOnce() -> U,
+) -> Option<U> {
+    match rounding {
+        Rounding::HalfEven | Rounding::HalfOdd | Rounding::ZeroFiveUp => {
+            let r = remain();
+            if !r.is_zero() && round_away(rounding, q, false, || r.cmp(&(d - r))) {
+                q.checked_add(&U::ONE)
+            } else {
+                Some(q)
+            }
+        }
+        _ => Some(q),
+    }
+}
+
+// Calculate `q + dividend / c` with rounding, where `q` is the quotient
+// of the higher part. The kinds depending on the last digit need the
+// whole quotient.
+pub(crate) fn add_rounding_div<I: FpdecInner>(
+    q: I,
+    dividend: I,
+    c: I,
+    rounding: Rounding,
+) -> Option<I> {
+    match rounding {
+        Rounding::HalfEven | Rounding::HalfOdd | Rounding::ZeroFiveUp => {
+            let q = q.checked_add(&dividend.checked_div(&c)?)?;
+            let remain = dividend % c;
+            if remain.is_zero() {
+                return Some(q);
+            }
+            let is_neg = (remain ^ c) < I::ZERO;
+            let r = remain.unsigned_abs();
+            if !round_away(rounding, q, is_neg, || r.cmp(&(c.unsigned_abs() - r))) {
+                Some(q)
+            } else if is_neg {
+                q.checked_sub(&I::ONE)
+            } else {
+                q.checked_add(&I::ONE)
+            }
+        }
+        _ => q.checked_add(&dividend.rounding_div(c, rounding)?),
+    }
+}
+
+// Check if `diff_scale` is in range `[-I::DIGITS, I::DIGITS]`.
+fn check_diff_scale<I: FpdecInner>(diff_scale: i32) -> Result<(), ArithmeticError> {
+    if diff_scale.unsigned_abs() > I::DIGITS {
+        Err(ArithmeticError::ScaleOutOfRange)
+    } else {
+        Ok(())
+    }
 }
 
-// We assume the number is non-negative here. The caller should handle the sign.
-fn display_num<I>(
-    uns: I,
+// Convert the other operand into `I`, for the `try_*` methods.
+pub(crate) fn try_into_inner<I, J>(n: J) -> Result<I, ArithmeticError>
+where
+    I: FpdecInner,
+    J: FpdecInner,
+{
+    I::from(n).ok_or(ArithmeticError::out_of_bounds(n < J::ZERO))
+}
+
+// Calculate `a + b`, or `a - b` if `is_sub`, of different types, returning
+// `None` if the result overflows `I`.
+pub(crate) fn checked_add_mixed<I, J>(a: I, b: J, is_sub: bool) -> Option<I>
+where
+    I: FpdecInner,
+    J: FpdecInner,
+{
+    if let Some(b) = I::from(b) {
+        return if is_sub {
+            a.checked_sub(&b)
+        } else {
+            a.checked_add(&b)
+        };
+    }
+
+    // `b` is out of range of `I`, but the result may be not, e.g.
+    // `1_u8 + (-1_i8)`. So calculate in sign and magnitude.
+    let (a_neg, a_abs) = sign_abs(a);
+    let (b_neg, b_abs) = sign_abs(b);
+    let b_neg = b_neg ^ is_sub; // `b` is not zero here
+    let (neg, abs) = if a_neg == b_neg {
+        (a_neg, a_abs.checked_add(b_abs)?)
+    } else if a_abs >= b_abs {
+        (a_neg, a_abs - b_abs)
+    } else {
+        (b_neg, b_abs - a_abs)
+    };
+    if neg {
+        I::from(0_i128.checked_sub_unsigned(abs)?)
+    } else {
+        I::from(abs)
+    }
+}
+
+fn sign_abs<I: FpdecInner>(n: I) -> (bool, u128) {
+    match n.to_i128() {
+        Some(n) => (n < 0, n.unsigned_abs()),
+        None => (false, n.to_u128().unwrap()),
+    }
+}
+
+// The result of the overflowing arithmetic if the scale difference is
+// out of range, which is taken as overflow like the checked arithmetic.
+// So it's MIN or MAX by the signs, or zero if any operand is zero.
+fn out_of_range_result<I: FpdecInner>(a: I, b: I) -> (I, bool) {
+    if a.is_zero() || b.is_zero() {
+        (I::ZERO, false)
+    } else {
+        (saturate_by_sign(a, b), true)
+    }
+}
+
+// Return MIN if the product or quotient of `a` and `b` is negative,
+// otherwise MAX.
+fn saturate_by_sign<I: FpdecInner>(a: I, b: I) -> I {
+    if (a ^ b) < I::ZERO {
+        I::MIN
+    } else {
+        I::MAX
+    }
+}
+
+// Calculate `n / 10^k` with rounding for unsigned `n`. The result is 0
+// or 1 if 10^k overflows.
+fn round_exp<I: FpdecInner>(n: I, k: usize, rounding: Rounding) -> I {
+    if let Some(exp) = I::get_exp(k) {
+        return n.rounding_div(exp, rounding).unwrap();
+    }
+
+    let half = || cmp_half_exp(n, k);
+    if !n.is_zero() && round_away(rounding, I::ZERO, false, half) {
+        I::ONE
+    } else {
+        I::ZERO
+    }
+}
+
+// Compare unsigned `n` with the half of 10^k, that is 5*10^(k-1).
+fn cmp_half_exp<I: FpdecInner>(n: I, k: usize) -> Ordering {
+    let five = I::from(5).unwrap();
+    match k
+        .checked_sub(1)
+        .and_then(I::get_exp)
+        .and_then(|e| e.checked_mul(&five))
+    {
+        Some(h) => n.cmp(&h),
+        None => Ordering::Less,
+    }
+}
+
+// Move 1 unit from the remain into the part, towards zero.
+fn take_unit<I: FpdecInner>(part: I, remain: &mut I) -> I {
+    if *remain > I::ZERO {
+        *remain -= I::ONE;
+        part + I::ONE
+    } else if *remain < I::ZERO {
+        *remain += I::ONE;
+        part - I::ONE
+    } else {
+        part
+    }
+}
+
+// The exponent is saturated at this, which is big enough to make any
+// non-zero number overflow or lose precision, while keeping the
+// scale calculation in i64 safe.
+const EXP_LIMIT: i64 = 1 << 40;
+
+// Components of decimal string: `[+-]int[.frac][(e|E)[+-]exp]`.
+//
+// All the parts are validated, so the int and frac parts contain only
+// ASCII digits, and the separators between digits.
+struct DecStr<'a> {
+    is_neg: bool,
+    start: usize,
+    int_part: &'a [u8],
+    int_offset: usize,
+    int_len: usize,
+    frac_part: &'a [u8],
+    frac_offset: usize,
+    frac_len: usize,
+    has_point: bool,
+    exp: i64,
+    separator: Option<u8>,
+}
+
+impl<'a> DecStr<'a> {
+    // The offsets are all about the original string, even if the
+    // whitespaces are trimmed.
+    fn split(s: &'a [u8], opts: &ParseOptions) -> Result<Self, ParseError> {
+        if !opts.is_valid() {
+            return Err(invalid_error(0));
+        }
+
+        let (start, s) = if opts.trim_whitespace {
+            let start = s.len() - s.trim_ascii_start().len();
+            (start, &s[..start + s[start..].trim_ascii_end().len()])
+        } else {
+            (0, s)
+        };
+        if s.len() == start {
+            return Err(ParseError::new(ParseErrorKind::Empty, start));
+        }
+
+        let (is_neg, int_offset) = match s[start] {
+            b'-' => (true, start + 1),
+            b'+' if opts.allow_plus => (false, start + 1),
+            _ => (false, start),
+        };
+        let sep = opts.separator;
+        let (int_end, int_len) = skip_digits(s, int_offset, sep);
+
+        if !opts.allow_leading_zeros && int_len > 1 && s[int_offset] == b'0' {
+            return Err(invalid_error(int_offset + 1));
+        }
+
+        let (has_point, frac_offset, frac_end, frac_len) = if s.get(int_end) == Some(&opts.point) {
+            let (frac_end, frac_len) = skip_digits(s, int_end + 1, sep);
+            if frac_len == 0 && !(opts.allow_bare_point && int_len > 0) {
+                return Err(invalid_error(frac_end));
+            }
+            (true, int_end + 1, frac_end, frac_len)
+        } else {
+            (false, int_end, int_end, 0)
+        };
+
+        if int_len == 0 && !(opts.allow_bare_point && frac_len > 0) {
+            return Err(invalid_error(int_offset));
+        }
+
+        let mut pos = frac_end;
+        let mut exp = 0;
+        if matches!(s.get(pos), Some(b'e' | b'E')) {
+            pos += 1;
+            let is_neg_exp = s.get(pos) == Some(&b'-');
+            if matches!(s.get(pos), Some(b'-' | b'+')) {
+                pos += 1;
+            }
+            let (exp_end, exp_len) = skip_digits(s, pos, None);
+            if exp_len == 0 {
+                return Err(invalid_error(pos));
+            }
+            for &ch in &s[pos..exp_end] {
+                exp = (exp * 10 + (ch - b'0') as i64).min(EXP_LIMIT);
+            }
+            if is_neg_exp {
+                exp = -exp;
+            }
+            pos = exp_end;
+        }
+
+        if pos != s.len() {
+            return Err(invalid_error(pos));
+        }
+
+        Ok(DecStr {
+            is_neg,
+            start,
+            int_part: &s[int_offset..int_end],
+            int_offset,
+            int_len,
+            frac_part: &s[frac_offset..frac_end],
+            frac_offset,
+            frac_len,
+            has_point,
+            exp,
+            separator: sep,
+        })
+    }
+
+    fn n_digits(&self) -> usize {
+        self.int_len + self.frac_len
+    }
+
+    fn digits(&self) -> impl DoubleEndedIterator<Item = &u8> {
+        let sep = self.separator;
+        self.int_part
+            .iter()
+            .chain(self.frac_part)
+            .filter(move |&&ch| Some(ch) != sep)
+    }
+
+    // The number of digits after the decimal point, with the exponent applied.
+    fn raw_scale(&self) -> i64 {
+        self.frac_len as i64 - self.exp
+    }
+
+    // The number of tailing zeros of the integer without decimal point.
+    fn int_tailing_zeros(&self) -> usize {
+        if self.has_point {
+            0
+        } else {
+            self.digits().rev().take_while(|&&ch| ch == b'0').count()
+        }
+    }
+
+    // The byte offset of the i-th digit, or the end of the digits.
+    fn digit_offset(&self, i: usize) -> usize {
+        let int_offsets = (self.int_offset..).zip(self.int_part);
+        let frac_offsets = (self.frac_offset..).zip(self.frac_part);
+        int_offsets
+            .chain(frac_offsets)
+            .filter(|&(_, &ch)| Some(ch) != self.separator)
+            .nth(i)
+            .map_or(self.frac_offset + self.frac_part.len(), |(offset, _)| {
+                offset
+            })
+    }
+}
+
+// Skip the digits and the separators between digits. Return the end
+// position and the number of digits.
+fn skip_digits(s: &[u8], pos: usize, sep: Option<u8>) -> (usize, usize) {
+    let mut end = pos;
+    let mut n_digits = 0;
+    while let Some(&ch) = s.get(end) {
+        if ch.is_ascii_digit() {
+            n_digits += 1;
+        } else if !(Some(ch) == sep
+            && n_digits > 0
+            && s.get(end + 1).is_some_and(u8::is_ascii_digit))
+        {
+            break;
+        }
+        end += 1;
+    }
+    (end, n_digits)
+}
+
+fn invalid_error(offset: usize) -> ParseError {
+    ParseError::new(ParseErrorKind::Invalid, offset)
+}
+
+// Negative numbers are invalid for unsigned types, except zero.
+fn check_sign<I: FpdecInner>(ds: &DecStr) -> Result<(), ParseError> {
+    if ds.is_neg && I::MIN.is_zero() && ds.digits().any(|&ch| ch != b'0') {
+        Err(invalid_error(ds.start))
+    } else {
+        Ok(())
+    }
+}
+
+fn accumulate<'a, I: FpdecInner>(digits: impl Iterator<Item = &'a u8>, is_neg: bool) -> Option<I> {
+    let mut num = I::ZERO;
+    for &ch in digits {
+        let d = I::from(ch - b'0').unwrap();
+        num = num.checked_mul(&I::TEN)?;
+        num = if is_neg {
+            num.checked_sub(&d)?
+        } else {
+            num.checked_add(&d)?
+        };
+    }
+    Some(num)
+}
+
+// Return num * 10^exp.
+fn checked_mul_exp<I: FpdecInner>(num: I, exp: i64) -> Option<I> {
+    if num.is_zero() {
+        return Some(num);
+    }
+    I::get_exp(usize::try_from(exp).ok()?)?.checked_mul(&num)
+}
+
+// Make the error when the number overflows, with the scale or not. It's
+// `IntOverflow` if the integer part overflows already, and
+// `FracOverflow` otherwise.
+fn overflow_error<I: FpdecInner>(ds: &DecStr, scale: Option<i64>) -> ParseError {
+    let n_digits = ds.n_digits() as i64;
+    let n_int = (n_digits - ds.raw_scale()).clamp(0, n_digits) as usize;
+
+    // Check the integer part only. For parsing with scale, it's padded
+    // with zeros to the scale.
+    let shift = scale.map_or(0, |scale| scale - ds.raw_scale().min(0));
+    let int_fits = n_int < ds.n_digits()
+        && accumulate::<I>(ds.digits().take(n_int), ds.is_neg)
+            .and_then(|num| checked_mul_exp(num, shift))
+            .is_some();
+
+    if int_fits {
+        ParseError::new(ParseErrorKind::FracOverflow, ds.digit_offset(n_int))
+    } else {
+        ParseError::new(ParseErrorKind::IntOverflow, ds.int_offset)
+    }
+}
+
+// Make the precision error when the lowest `diff` digits of the number
+// are to be discarded but not all zeros.
+pub(crate) fn precision_error<I: FpdecInner>(num: I, diff: u32) -> ParseError {
+    let mut num = num;
+    let mut extra = diff;
+    while extra > 0 && !num.is_zero() && (num % I::TEN).is_zero() {
+        num = num / I::TEN;
+        extra -= 1;
+    }
+    ParseErrorKind::Precision(extra).into()
+}
+
+// The fast path of parsing, for the plain decimal `[+-]int[.frac]` with
+// at most 19 digits, which fit in u64, and without padding precision.
+// Return `None` for all the other cases, including the errors, which
+// should go to the general path then.
+fn parse_fast<I: FpdecInner>(s: &[u8], scale: i32) -> Option<I> {
+    let (is_neg, pos) = match s.first()? {
+        b'-' => (true, 1),
+        b'+' => (false, 1),
+        _ => (false, 0),
+    };
+
+    let (int_num, int_len) = fast_parse_digits(&s[pos..])?;
+    if int_len == 0 {
+        return None;
+    }
+    let pos = pos + int_len;
+
+    let (num, frac_len) = match s.get(pos) {
+        None => (int_num, 0),
+        Some(b'.') => {
+            let (frac_num, frac_len) = fast_parse_digits(&s[pos + 1..])?;
+            if frac_len == 0 || pos + 1 + frac_len != s.len() {
+                return None;
+            }
+            let num = int_num
+                .checked_mul(10_u64.pow(frac_len as u32))?
+                .checked_add(frac_num)?;
+            (num, frac_len)
+        }
+        Some(_) => return None,
+    };
+
+    let diff = scale.checked_sub(frac_len as i32)?;
+    if diff < 0 {
+        return None;
+    }
+    let num = I::from(num)?;
+    let num = if is_neg {
+        I::ZERO.checked_sub(&num)?
+    } else {
+        num
+    };
+    if diff == 0 {
+        Some(num)
+    } else {
+        I::get_exp(diff as usize)?.checked_mul(&num)
+    }
+}
+
+// Parse the leading ASCII digits, at most 19. Return the number and
+// the number of digits, or `None` if there are more digits.
+fn fast_parse_digits(s: &[u8]) -> Option<(u64, usize)> {
+    let mut num = 0;
+    let mut len = 0;
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "ssse3"))]
+    if let Some(n) = simd::parse_16_digits(s) {
+        num = n;
+        len = 16;
+    }
+
+    loop {
+        let (n, l) = swar_parse_8_digits(&s[len..]);
+        len += l;
+        if len > 19 {
+            return None;
+        }
+        num = num * 10_u64.pow(l as u32) + n;
+        if l < 8 {
+            return Some((num, len));
+        }
+    }
+}
+
+// Parse the leading ASCII digits, at most 8, at once by SWAR (SIMD
+// within a register). Return the number and the number of digits.
+fn swar_parse_8_digits(s: &[u8]) -> (u64, usize) {
+    let mut buf = [0; 8];
+    let len = s.len().min(8);
+    buf[..len].copy_from_slice(&s[..len]);
+    let chunk = u64::from_le_bytes(buf);
+
+    // Set the high bit of each non-digit byte: the byte > b'9' overflows
+    // when adding 0x46, and the byte < b'0' underflows when subtracting
+    // 0x30. The carry or borrow only affects the later bytes, so the
+    // first non-digit is exact.
+    let non_digits = (chunk.wrapping_add(0x4646_4646_4646_4646)
+        | chunk.wrapping_sub(0x3030_3030_3030_3030))
+        & 0x8080_8080_8080_8080;
+    let len = (non_digits.trailing_zeros() / 8) as usize;
+    if len == 0 {
+        return (0, 0);
+    }
+
+    // The first char is at the lowest byte. Move the digits to the
+    // highest bytes, and fill the lowest bytes by b'0'.
+    let chunk = if len < 8 {
+        let shift = 8 * (8 - len as u32);
+        (chunk << shift) | (0x3030_3030_3030_3030 >> (64 - shift))
+    } else {
+        chunk
+    };
+
+    // Merge the adjacent digits, 2, 4 and then 8.
+    let chunk = chunk - 0x3030_3030_3030_3030;
+    let chunk = (chunk.wrapping_mul(10) + (chunk >> 8)) & 0x00ff_00ff_00ff_00ff;
+    let chunk = (chunk.wrapping_mul(100) + (chunk >> 16)) & 0x0000_ffff_0000_ffff;
+    let chunk = (chunk.wrapping_mul(10000) + (chunk >> 32)) & 0xffff_ffff;
+    (chunk, len)
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "ssse3"))]
+mod simd {
+    use core::arch::x86_64::*;
+
+    // Parse 16 ASCII digits at once by SSSE3. Return `None` if there
+    // are less than 16 leading digits.
+    pub fn parse_16_digits(s: &[u8]) -> Option<u64> {
+        if s.len() < 16 {
+            return None;
+        }
+
+        // SAFETY: the length is checked above, and the SSSE3 is enabled
+        // at compile time.
+        unsafe {
+            let chunk = _mm_loadu_si128(s.as_ptr().cast());
+
+            let below = _mm_cmplt_epi8(chunk, _mm_set1_epi8(b'0' as i8));
+            let above = _mm_cmpgt_epi8(chunk, _mm_set1_epi8(b'9' as i8));
+            if _mm_movemask_epi8(_mm_or_si128(below, above)) != 0 {
+                return None;
+            }
+
+            // Merge the adjacent digits, 2, 4, and 8.
+            let chunk = _mm_sub_epi8(chunk, _mm_set1_epi8(b'0' as i8));
+            let chunk = _mm_maddubs_epi16(
+                chunk,
+                _mm_set_epi8(1, 10, 1, 10, 1, 10, 1, 10, 1, 10, 1, 10, 1, 10, 1, 10),
+            );
+            let chunk = _mm_madd_epi16(chunk, _mm_set_epi16(1, 100, 1, 100, 1, 100, 1, 100));
+            let chunk = _mm_packs_epi32(chunk, chunk);
+            let chunk =
+                _mm_madd_epi16(chunk, _mm_set_epi16(1, 10000, 1, 10000, 1, 10000, 1, 10000));
+
+            let high = _mm_cvtsi128_si32(chunk) as u64;
+            let low = _mm_cvtsi128_si32(_mm_srli_si128(chunk, 4)) as u64;
+            Some(high * 100_000_000 + low)
+        }
+    }
+}
+
+// Parse the decimal string with the scale.
+//
+// If `rounding` is `None`, the extra fraction digits are not allowed,
+// even if they are zeros, unless the number is zero.
+//
+// Otherwise the digits beyond the scale are not accumulated into the
+// number, but only used for rounding. So the string can be arbitrarily
+// long.
+fn parse_with_scale<I: FpdecInner>(
+    ds: &DecStr,
     scale: i32,
-    precision: Option<usize>,
-    buf: &mut [MaybeUninit<u8>],
-) -> usize
+    rounding: Option<Rounding>,
+) -> Result<I, ParseError> {
+    check_sign::<I>(ds)?;
+
+    // The number of tailing digits to be discarded. Negative means
+    // padding zeros.
+    let scale = scale as i64;
+    let n_digits = ds.n_digits();
+    let extra = ds.raw_scale() - scale;
+
+    if extra <= 0 {
+        return accumulate(ds.digits(), ds.is_neg)
+            .and_then(|num| checked_mul_exp(num, -extra))
+            .ok_or_else(|| overflow_error::<I>(ds, Some(scale)));
+    }
+
+    let kept = (n_digits as i64 - extra).max(0) as usize;
+
+    let num = || {
+        accumulate::<I>(ds.digits().take(kept), ds.is_neg)
+            .ok_or_else(|| overflow_error::<I>(ds, Some(scale)))
+    };
+
+    let Some(rounding) = rounding else {
+        // Only the tailing zeros of integer can be discarded, e.g. "1200"
+        // with scale -2.
+        let n_zeros = ds.int_tailing_zeros() as i64;
+        if extra <= n_zeros {
+            return num();
+        }
+        if ds.digits().all(|&ch| ch == b'0') {
+            return Ok(I::ZERO);
+        }
+        let kind = ParseErrorKind::Precision(u32::try_from(extra - n_zeros).unwrap_or(u32::MAX));
+        return Err(ParseError::new(kind, ds.digit_offset(kept)));
+    };
+
+    let num = num()?;
+
+    // The first discarded digit is a virtual leading zero if all the
+    // digits are beyond the scale.
+    let mut discarded = ds.digits().skip(kept);
+    let first_discarded = if extra <= n_digits as i64 {
+        discarded.next().map_or(0, |ch| ch - b'0')
+    } else {
+        0
+    };
+    let rest_nonzero = discarded.any(|&ch| ch != b'0');
+
+    let half = || match first_discarded.cmp(&5) {
+        Ordering::Equal if rest_nonzero => Ordering::Greater,
+        o => o,
+    };
+    let carry =
+        (first_discarded != 0 || rest_nonzero) && round_away(rounding, num, ds.is_neg, half);
+    if !carry {
+        Ok(num)
+    } else if ds.is_neg {
+        num.checked_sub(&I::ONE)
+            .ok_or_else(|| overflow_error::<I>(ds, Some(scale)))
+    } else {
+        num.checked_add(&I::ONE)
+            .ok_or_else(|| overflow_error::<I>(ds, Some(scale)))
+    }
+}
+
+// Parse the decimal string, and guess the scale by it.
+fn parse_guess_scale<I: FpdecInner>(ds: &DecStr) -> Result<(I, i32), ParseError> {
+    check_sign::<I>(ds)?;
+
+    // Trim the tailing zeros of integer, e.g. "1200" is (12, -2).
+    let n_zeros = ds.int_tailing_zeros();
+    let kept = ds.n_digits() - n_zeros;
+    let scale = ds.raw_scale() - n_zeros as i64;
+
+    let num = accumulate::<I>(ds.digits().take(kept), ds.is_neg)
+        .ok_or_else(|| overflow_error::<I>(ds, None))?;
+    if num.is_zero() {
+        return Ok((num, 0));
+    }
+
+    match i32::try_from(scale) {
+        Ok(scale) => Ok((num, scale)),
+        Err(_) if scale > 0 => {
+            let extra = scale - i32::MAX as i64;
+            let kind = ParseErrorKind::Precision(u32::try_from(extra).unwrap_or(u32::MAX));
+            let first = (kept as i64 - extra).max(0) as usize;
+            Err(ParseError::new(kind, ds.digit_offset(first)))
+        }
+        Err(_) => Err(ParseError::new(ParseErrorKind::IntOverflow, ds.int_offset)),
+    }
+}
+
+// The parts of a non-negative decimal to display:
+//
+//     {int}{int_zeros}.{frac padded to width}{tail_zeros}e{exp}
+//
+// The zeros are counted but not dumped here, so any scale and precision
+// can be displayed without a big buffer. The caller should handle the sign.
+struct DisplayParts<I> {
+    int: I,
+    int_zeros: usize,         // for negative scale
+    frac: Option<(I, usize)>, // fraction and its width; None for no '.'
+    tail_zeros: usize,        // for precision bigger than scale
+    exp: Option<(u8, i64)>,   // 'e' or 'E' and the exponent, for notations
+}
+
+impl<I> DisplayParts<I>
 where
     I: FpdecInner + AsPrimitive<u8> + AsPrimitive<usize>,
 {
-    if scale <= 0 {
+    // The `rounding` is for the unsigned `uns`, see `magnitude_rounding()`.
+    fn new(uns: I, scale: i32, precision: Option<usize>, rounding: Rounding) -> Self {
+        let mut parts = DisplayParts {
+            int: uns,
+            int_zeros: 0,
+            frac: None,
+            tail_zeros: 0,
+            exp: None,
+        };
+
+        if scale <= 0 {
+            // padding 0 for negative scale
+            if scale < 0 && !uns.is_zero() {
+                parts.int_zeros = scale.unsigned_abs() as usize;
+            }
+
+            // padding 0 for precision
+            let precision = precision.unwrap_or(0);
+            if precision != 0 {
+                parts.frac = Some((I::ZERO, precision));
+            }
+            return parts;
+        }
+
+        // now, scale > 0
+        let scale = scale as usize;
+
+        // calculate integer and fraction parts
+        let (int, frac) = match I::get_exp(scale) {
+            Some(exp) => (uns / exp, uns % exp),
+            None => (I::ZERO, uns),
+        };
+        parts.int = int;
+
+        match precision {
+            // no precition set, remove fraction tailing zeros
+            None => {
+                if !frac.is_zero() {
+                    let mut zeros = 0;
+                    let mut n = frac;
+                    while (n % I::TEN).is_zero() {
+                        n = n / I::TEN;
+                        zeros += 1;
+                    }
+                    parts.frac = Some((n, scale - zeros));
+                }
+            }
+
+            // set precision = 0, do not show the '.' char
+            Some(0) => {
+                parts.int = round_exp(uns, scale, rounding);
+            }
+
+            // set precision > 0
+            Some(precision) => {
+                if precision >= scale {
+                    parts.frac = Some((frac, scale));
+                    parts.tail_zeros = precision - scale;
+                } else {
+                    let frac = round_exp(frac, scale - precision, rounding);
+
+                    // fraction digits adds 1 more.
+                    // for example: 12.99967 with precision=3, the fraction 99967
+                    // is round to 1000 whose digits is more than 3.
+                    if I::get_exp(precision) == Some(frac) {
+                        parts.int = int + I::ONE;
+                        parts.frac = Some((I::ZERO, precision));
+                    } else {
+                        parts.frac = Some((frac, precision));
+                    }
+                }
+            }
+        }
+        parts
+    }
+
+    // Scientific notation, with 1 digit before the point, e.g. 1.2345e4;
+    // or engineering notation, with 1 to 3 digits before the point and
+    // exponent of multiple of 3, e.g. 12.345e3.
+    fn new_exp(
+        uns: I,
+        scale: i32,
+        precision: Option<usize>,
+        rounding: Rounding,
+        eng: bool,
+        e: u8,
+    ) -> Self {
+        let mut parts = DisplayParts {
+            int: I::ZERO,
+            int_zeros: 0,
+            frac: None,
+            tail_zeros: 0,
+            exp: Some((e, 0)),
+        };
+
+        if uns.is_zero() {
+            if let Some(precision) = precision.filter(|p| *p != 0) {
+                parts.frac = Some((I::ZERO, precision));
+            }
+            return parts;
+        }
+
+        // the count of digits before the point
+        let lead = |exp: i64| {
+            if eng {
+                exp.rem_euclid(3) as usize + 1
+            } else {
+                1
+            }
+        };
+
+        // the significant digits and the exponent of the first digit
+        let mut m = uns;
+        let mut n = count_digits(m);
+        let mut exp = n as i64 - 1 - scale as i64;
+
+        let frac_digits = match precision {
+            // no precition set, remove tailing zeros
+            None => {
+                while (m % I::TEN).is_zero() {
+                    m = m / I::TEN;
+                    n -= 1;
+                }
+                n.saturating_sub(lead(exp))
+            }
+
+            // round to the precision
+            Some(precision) => {
+                let digits = lead(exp).saturating_add(precision);
+                if n > digits {
+                    let exp_diff = I::get_exp(n - digits).unwrap();
+                    m = m.rounding_div(exp_diff, rounding).unwrap();
+                    n = digits;
+
+                    // all digits are 9 and round up, e.g. 9.996 to 10.00
+                    if I::get_exp(digits) == Some(m) {
+                        m = I::ONE;
+                        n = 1;
+                        exp += 1;
+                    }
+                }
+                precision
+            }
+        };
+
+        let lead = lead(exp);
+        parts.exp = Some((e, exp - (lead as i64 - 1)));
+
+        if n <= lead {
+            parts.int = m;
+            parts.int_zeros = lead - n;
+            if frac_digits != 0 {
+                parts.frac = Some((I::ZERO, frac_digits));
+            }
+        } else {
+            let exp_frac = I::get_exp(n - lead).unwrap();
+            parts.int = m / exp_frac;
+            parts.frac = Some((m % exp_frac, n - lead));
+            parts.tail_zeros = frac_digits - (n - lead);
+        }
+        parts
+    }
+
+    fn fmt(&self, is_nonnegative: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        // Most numbers fit in the buffer and are padded by `pad_integral()`
+        // at once. Longer ones, with big scale or precision, are streamed
+        // to the formatter with the padding zeros in chunks.
+        let mut buf: [MaybeUninit<u8>; 128] = [MaybeUninit::uninit(); 128];
+        if self.len(&NumberFormat::DEFAULT) > buf.len() {
+            return self.stream(is_nonnegative, &NumberFormat::DEFAULT, f);
+        }
+
+        let offset = self.dump(&mut buf);
+
+        // SAFETY: offset is updated along with buf
+        let buf = unsafe {
+            core::slice::from_raw_parts(buf[offset..].as_ptr().cast(), buf.len() - offset)
+        };
+
+        // SAFETY: all data is valid charactor
+        let s = unsafe { str::from_utf8_unchecked(buf) };
+
+        f.pad_integral(is_nonnegative, "", s)
+    }
+
+    // Count of chars. Saturate at `usize::MAX`, which is too long to
+    // display anyway.
+    fn len(&self, nf: &NumberFormat) -> usize {
+        let int_len = count_digits(self.int).saturating_add(self.int_zeros);
+        let mut len = int_len.saturating_add(GroupWriter::new(nf, int_len).separators());
+        if let Some((_, width)) = self.frac {
+            len = len
+                .saturating_add(1)
+                .saturating_add(width)
+                .saturating_add(self.tail_zeros);
+        }
+        if let Some((_, exp)) = self.exp {
+            len = len
+                .saturating_add(1 + (exp < 0) as usize)
+                .saturating_add(count_digits(exp.unsigned_abs()));
+        }
+        len
+    }
+
+    // Dump at the end of the buffer, and return the offset.
+    // The buffer must be not shorter than `self.len(&NumberFormat::DEFAULT)`.
+    fn dump(&self, buf: &mut [MaybeUninit<u8>]) -> usize {
         let mut offset = buf.len();
 
-        // padding 0 for precision
-        let precision = precision.unwrap_or(0);
-        if precision != 0 {
-            assert!(precision + (-scale) as usize <= 200);
+        if let Some((e, exp)) = self.exp {
+            offset = dump_single(exp.unsigned_abs(), buf);
+            if exp < 0 {
+                offset -= 1;
+                buf[offset].write(b'-');
+            }
+            offset -= 1;
+            buf[offset].write(e);
+        }
 
-            offset = pad_zeros(precision, buf);
+        if let Some((frac, width)) = self.frac {
+            offset = pad_zeros(self.tail_zeros, &mut buf[..offset]);
+
+            let end = offset;
+            offset = dump_single(frac, &mut buf[..offset]);
+            offset = pad_zeros(width - (end - offset), &mut buf[..offset]);
 
-            // point '.'
             offset -= 1;
             buf[offset].write(b'.');
         }
 
-        // padding 0 for negative scale
-        if scale < 0 && !uns.is_zero() {
-            offset = pad_zeros((-scale) as usize, &mut buf[..offset]);
-        }
+        offset = pad_zeros(self.int_zeros, &mut buf[..offset]);
 
-        return dump_single(uns, &mut buf[..offset]);
+        dump_single(self.int, &mut buf[..offset])
     }
 
-    // now, scale > 0
-    let scale = scale as usize;
+    // Write to the formatter piece by piece, with the same padding
+    // as `Formatter::pad_integral()`.
+    fn stream(
+        &self,
+        is_nonnegative: bool,
+        nf: &NumberFormat,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        let sign = if !is_nonnegative {
+            "-"
+        } else if f.sign_plus() {
+            "+"
+        } else {
+            ""
+        };
+        self.stream_affix(&[sign], &[], nf, f)
+    }
 
-    // calculate integer and fraction parts
-    let (int, frac, exp) = match I::get_exp(scale) {
-        Some(exp) => (uns / exp, uns % exp, Some(exp)),
-        None => (I::ZERO, uns, None),
-    };
+    // Write with the prefix and suffix around the digits. The padding
+    // zeros of `sign_aware_zero_pad()` are after the prefix.
+    fn stream_affix(
+        &self,
+        prefix: &[&str],
+        suffix: &[&str],
+        nf: &NumberFormat,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        let affix_len = prefix.iter().chain(suffix).map(|s| s.chars().count()).sum();
+        let len = self.len(nf).saturating_add(affix_len);
+        let padding = f.width().map_or(0, |width| width.saturating_sub(len));
+
+        let (pre, zeros, post) = if f.sign_aware_zero_pad() {
+            (0, padding, 0)
+        } else {
+            match f.align() {
+                Some(fmt::Alignment::Left) => (0, 0, padding),
+                Some(fmt::Alignment::Center) => (padding / 2, 0, padding.div_ceil(2)),
+                _ => (padding, 0, 0),
+            }
+        };
+
+        let fill = f.fill();
+        for _ in 0..pre {
+            f.write_char(fill)?;
+        }
+        for s in prefix {
+            f.write_str(s)?;
+        }
+        write_zeros(zeros, f)?;
+        self.write_digits(nf, f)?;
+        for s in suffix {
+            f.write_str(s)?;
+        }
+        for _ in 0..post {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
 
-    match precision {
-        // no precition set, remove fraction tailing zeros
-        None => {
-            if frac.is_zero() {
-                return dump_single(int, buf);
+    fn write_digits(&self, nf: &NumberFormat, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf: [MaybeUninit<u8>; 40] = [MaybeUninit::uninit(); 40];
+        let int = single_str(self.int, &mut buf);
+
+        let mut group = GroupWriter::new(nf, int.len().saturating_add(self.int_zeros));
+        group.write_str(int, f)?;
+        group.write_zeros(self.int_zeros, f)?;
+
+        if let Some((frac, width)) = self.frac {
+            f.write_char(nf.decimal_mark)?;
+            write_zeros(width - count_digits(frac), f)?;
+            write_single(frac, f)?;
+            write_zeros(self.tail_zeros, f)?;
+        }
+
+        if let Some((e, exp)) = self.exp {
+            f.write_char(e as char)?;
+            if exp < 0 {
+                f.write_str("-")?;
             }
+            write_single(exp.unsigned_abs(), f)?;
+        }
+        Ok(())
+    }
+}
 
-            // remove fraction tailing zeros
-            let (frac, zeros) = {
-                let mut zeros = 0;
-                let mut n = frac;
-                while (n % I::TEN).is_zero() {
-                    n = n / I::TEN;
-                    zeros += 1;
-                }
-                (n, zeros)
-            };
+fn count_digits<I: FpdecInner>(mut n: I) -> usize {
+    let mut count = 1;
+    while n >= I::TEN {
+        n = n / I::TEN;
+        count += 1;
+    }
+    count
+}
+
+// Write the integer digits with group separators.
+struct GroupWriter {
+    separator: char,
+    sizes: Option<(usize, usize)>, // primary and secondary group sizes
+    remain: usize,                 // count of digits not written yet
+}
 
-            dump_decimal(int, frac, scale - zeros, buf)
+impl GroupWriter {
+    fn new(nf: &NumberFormat, digits: usize) -> Self {
+        let sizes = match nf.group_separator {
+            Some(_) if nf.primary_group != 0 => {
+                let primary = nf.primary_group as usize;
+                let secondary = match nf.secondary_group {
+                    0 => primary,
+                    n => n as usize,
+                };
+                Some((primary, secondary))
+            }
+            _ => None,
+        };
+        GroupWriter {
+            separator: nf.group_separator.unwrap_or(','),
+            sizes,
+            remain: digits,
         }
+    }
 
-        // set precision = 0, do not show the '.' char
-        Some(0) => match exp {
-            Some(exp) => {
-                if frac.saturating_add(frac) >= exp {
-                    dump_single(int + I::ONE, buf)
-                } else {
-                    dump_single(int, buf)
-                }
+    // count of separators in the remaining digits
+    fn separators(&self) -> usize {
+        match self.sizes {
+            Some((primary, secondary)) if self.remain > primary => {
+                (self.remain - primary - 1) / secondary + 1
             }
-            None => dump_single(I::ZERO, buf),
-        },
+            _ => 0,
+        }
+    }
 
-        // set precision > 0
-        Some(precision) => {
-            if precision == scale {
-                dump_decimal(int, frac, scale, buf)
-            } else if precision > scale {
-                assert!(precision <= 200);
-                let offset = pad_zeros(precision - scale, buf);
-                dump_decimal(int, frac, scale, &mut buf[..offset])
-            } else {
-                let frac = match I::get_exp(scale - precision) {
-                    Some(exp) => {
-                        let frac = frac.rounding_div(exp, Rounding::Round).unwrap();
-
-                        if let Some(prec_exp) = I::get_exp(precision) {
-                            // fraction digits adds 1 more.
-                            // for example: 12.99967 with precision=3, the fraction 99967
-                            // is round to 1000 whose digits is more than 3.
-                            if frac == prec_exp {
-                                return dump_decimal(int + I::ONE, I::ZERO, precision, buf);
-                            }
-                        }
+    // count of digits before the next separator
+    fn group_left(&self) -> usize {
+        match self.sizes {
+            Some((primary, secondary)) if self.remain > primary => {
+                (self.remain - primary - 1) % secondary + 1
+            }
+            _ => self.remain,
+        }
+    }
 
-                        frac
-                    }
-                    None => I::ZERO,
-                };
-                dump_decimal(int, frac, precision, buf)
+    // Write `n` digits by `write`, which is called per group.
+    fn write_groups(
+        &mut self,
+        mut n: usize,
+        f: &mut fmt::Formatter,
+        mut write: impl FnMut(usize, &mut fmt::Formatter) -> fmt::Result,
+    ) -> fmt::Result {
+        while n > 0 {
+            let left = self.group_left();
+            let len = left.min(n);
+            write(len, f)?;
+            n -= len;
+            self.remain -= len;
+            if len == left && self.remain > 0 {
+                f.write_char(self.separator)?;
             }
         }
+        Ok(())
+    }
+
+    fn write_str(&mut self, s: &str, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut pos = 0;
+        self.write_groups(s.len(), f, |len, f| {
+            pos += len;
+            f.write_str(&s[pos - len..pos])
+        })
+    }
+
+    fn write_zeros(&mut self, n: usize, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_groups(n, f, write_zeros)
     }
 }
 
-// dump: "int . frac"
-fn dump_decimal<I>(int: I, frac: I, scale: usize, buf: &mut [MaybeUninit<u8>]) -> usize
+fn single_str<I>(n: I, buf: &mut [MaybeUninit<u8>; 40]) -> &str
 where
     I: FpdecInner + AsPrimitive<u8> + AsPrimitive<usize>,
 {
-    let mut offset = dump_single(frac, buf);
+    // 40 is enough for u128::MAX
+    let offset = dump_single(n, buf);
 
-    offset = pad_zeros(scale - (buf.len() - offset), &mut buf[..offset]);
+    // SAFETY: offset is updated along with buf, and all data is valid charactor
+    unsafe {
+        let buf = core::slice::from_raw_parts(buf[offset..].as_ptr().cast(), buf.len() - offset);
+        str::from_utf8_unchecked(buf)
+    }
+}
 
-    offset -= 1;
-    buf[offset].write(b'.');
+fn write_single<I>(n: I, f: &mut fmt::Formatter) -> fmt::Result
+where
+    I: FpdecInner + AsPrimitive<u8> + AsPrimitive<usize>,
+{
+    let mut buf: [MaybeUninit<u8>; 40] = [MaybeUninit::uninit(); 40];
+    f.write_str(single_str(n, &mut buf))
+}
 
-    dump_single(int, &mut buf[..offset])
+fn write_zeros(mut n: usize, f: &mut fmt::Formatter) -> fmt::Result {
+    const ZEROS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+    while n > ZEROS.len() {
+        f.write_str(ZEROS)?;
+        n -= ZEROS.len();
+    }
+    f.write_str(&ZEROS[..n])
 }
 
 // dump a single integer number
@@ -462,7 +2060,7 @@ where
 
         let pair: usize = (remain % I::HUNDRED).as_();
         remain = remain / I::HUNDRED;
-        buf[offset + 0].write(DECIMAL_PAIRS[pair * 2 + 0]);
+        buf[offset].write(DECIMAL_PAIRS[pair * 2]);
         buf[offset + 1].write(DECIMAL_PAIRS[pair * 2 + 1]);
     }
 
@@ -485,6 +2083,38 @@ fn pad_zeros(n: usize, buf: &mut [MaybeUninit<u8>]) -> usize {
     offset
 }
 
+// Buffer for formatting float, which is at most 24 bytes,
+// e.g. "-2.2250738585072014e-308".
+struct FloatBuf {
+    data: [u8; 32],
+    len: usize,
+}
+
+impl FloatBuf {
+    fn new() -> Self {
+        FloatBuf {
+            data: [0; 32],
+            len: 0,
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+impl Write for FloatBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.data
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -502,7 +2132,7 @@ mod tests {
     }
     fn do_test_format<I>(s: &str, scale: i32, n: I)
     where
-        I: FpdecInner + fmt::Display + fmt::Debug + Num<FromStrRadixErr = ParseIntError>,
+        I: FpdecInner + fmt::Display + fmt::Debug,
     {
         //println!("test: {s}, {scale}, {n}");
         assert_eq!(I::try_from_str(s, scale), Ok(n));
@@ -518,7 +2148,7 @@ mod tests {
 
     fn do_test_format_num_only<I>(n: I)
     where
-        I: FpdecInner + fmt::Display + fmt::Debug + Num<FromStrRadixErr = ParseIntError>,
+        I: FpdecInner + fmt::Display + fmt::Debug,
     {
         for scale in -100..100 {
             let ts = TestFmt { n, scale };
@@ -526,13 +2156,48 @@ mod tests {
 
             //println!("scale:{scale}, n:{n}, out:{out}");
             assert_eq!(I::try_from_str(&out, scale), Ok(n));
+
+            let mut buf = [0; 256];
+            for precision in [None, Some(0), Some(1), Some(3), Some(20)] {
+                let out = match precision {
+                    Some(p) => std::format!("{:.*}", p, ts),
+                    None => std::format!("{}", ts),
+                };
+                let len = n
+                    .try_write_to(scale, precision, Rounding::Round, &mut buf)
+                    .unwrap();
+                assert_eq!(&buf[..len], out.as_bytes());
+                assert_eq!(
+                    n.try_write_to(scale, precision, Rounding::Round, &mut buf[..len - 1]),
+                    None
+                );
+            }
+
+            // same with display_with() for all rounding kinds
+            for rounding in Rounding::ALL {
+                let nf = NumberFormat {
+                    rounding,
+                    ..NumberFormat::DEFAULT
+                };
+                let ts = TestFmtWith { n, scale, nf: &nf };
+                for precision in [0, 1, 3] {
+                    let out = std::format!("{:.*}", precision, ts);
+                    let len = n
+                        .try_write_to(scale, Some(precision), rounding, &mut buf)
+                        .unwrap();
+                    assert_eq!(&buf[..len], out.as_bytes());
+                }
+            }
         }
     }
 
     #[test]
     fn test_format() {
         // empty
-        assert_eq!(i8::try_from_str("", 2), Err(ParseError::Empty));
+        assert_eq!(
+            i8::try_from_str("", 2),
+            Err(ParseError::new(ParseErrorKind::Empty, 0))
+        );
 
         // zero
         assert_eq!(i8::try_from_str("0", 2), Ok(0));
@@ -607,6 +2272,898 @@ mod tests {
         do_test_format("0.000255", 6, 255_u8);
     }
 
+    #[test]
+    fn test_parse_exponent() {
+        assert_eq!(i32::try_from_str("1.5e-7", 8), Ok(15));
+        assert_eq!(i32::try_from_str("1.5E-7", 10), Ok(1500));
+        assert_eq!(i32::try_from_str("3E+4", 0), Ok(30000));
+        assert_eq!(i32::try_from_str("3e4", -4), Ok(3));
+        assert_eq!(i32::try_from_str("-12.34e2", 0), Ok(-1234));
+        assert_eq!(i32::try_from_str("-12.34e-2", 4), Ok(-1234));
+        assert_eq!(i32::try_from_str("1234e-2", 2), Ok(1234));
+        assert_eq!(i32::try_from_str("0e-100", 2), Ok(0));
+        assert_eq!(i8::try_from_str("-1.28e-1", 3), Ok(-128));
+
+        assert_eq!(i32::try_from_str_only("1.5e-7"), Ok((15, 8)));
+        assert_eq!(i32::try_from_str_only("3E+4"), Ok((3, -4)));
+        assert_eq!(i32::try_from_str_only("1200e1"), Ok((12, -3)));
+
+        // errors
+        assert_eq!(
+            i32::try_from_str("1", 20),
+            Err(ParseError::new(ParseErrorKind::IntOverflow, 0))
+        );
+        assert_eq!(i32::try_from_str("0", 20), Ok(0));
+        assert_eq!(
+            i32::try_from_str("1.5e-7", 7),
+            Err(ParseError::new(ParseErrorKind::Precision(1), 2))
+        );
+        assert_eq!(
+            i32::try_from_str("3e10", 0),
+            Err(ParseError::new(ParseErrorKind::IntOverflow, 0))
+        );
+        assert_eq!(
+            i32::try_from_str("3e2147483647", 2),
+            Err(ParseError::new(ParseErrorKind::IntOverflow, 0))
+        );
+        assert_eq!(
+            i32::try_from_str("3e99999999999", 0),
+            Err(ParseError::new(ParseErrorKind::IntOverflow, 0))
+        );
+        assert_eq!(
+            i32::try_from_str("3e-99999999999", 0),
+            Err(ParseError::new(ParseErrorKind::Precision(u32::MAX), 0))
+        );
+        assert_eq!(
+            i32::try_from_str("1.5e", 2),
+            Err(ParseError::new(ParseErrorKind::Invalid, 4))
+        );
+        assert_eq!(
+            i32::try_from_str("1.5e1.2", 2),
+            Err(ParseError::new(ParseErrorKind::Invalid, 5))
+        );
+        assert_eq!(
+            i32::try_from_str("e5", 2),
+            Err(ParseError::new(ParseErrorKind::Invalid, 0))
+        );
+    }
+
+    #[test]
+    fn test_parse_rounding() {
+        // same with exact parsing
+        assert_eq!(i32::try_from_str_ext("1.23", 4, Rounding::Round), Ok(12300));
+        assert_eq!(i32::try_from_str_ext("-1.23", 2, Rounding::Floor), Ok(-123));
+        assert_eq!(i32::try_from_str_ext("0", 2, Rounding::Ceiling), Ok(0));
+
+        // rounding
+        let s = "1.23456";
+        assert_eq!(i32::try_from_str_ext(s, 4, Rounding::Round), Ok(12346));
+        assert_eq!(i32::try_from_str_ext(s, 4, Rounding::Floor), Ok(12345));
+        assert_eq!(i32::try_from_str_ext(s, 4, Rounding::Ceiling), Ok(12346));
+        assert_eq!(
+            i32::try_from_str_ext(s, 4, Rounding::TowardsZero),
+            Ok(12345)
+        );
+        assert_eq!(
+            i32::try_from_str_ext(s, 4, Rounding::AwayFromZero),
+            Ok(12346)
+        );
+        let s = "-1.23456";
+        assert_eq!(i32::try_from_str_ext(s, 4, Rounding::Round), Ok(-12346));
+        assert_eq!(i32::try_from_str_ext(s, 4, Rounding::Floor), Ok(-12346));
+        assert_eq!(i32::try_from_str_ext(s, 4, Rounding::Ceiling), Ok(-12345));
+        assert_eq!(
+            i32::try_from_str_ext(s, 4, Rounding::TowardsZero),
+            Ok(-12345)
+        );
+        assert_eq!(
+            i32::try_from_str_ext(s, 4, Rounding::AwayFromZero),
+            Ok(-12346)
+        );
+
+        // half
+        assert_eq!(i32::try_from_str_ext("2.5", 0, Rounding::Round), Ok(3));
+        assert_eq!(i32::try_from_str_ext("-2.5", 0, Rounding::Round), Ok(-3));
+        assert_eq!(i32::try_from_str_ext("2.4999", 0, Rounding::Round), Ok(2));
+        assert_eq!(i32::try_from_str_ext("2.0001", 0, Rounding::Ceiling), Ok(3));
+        for (s, expect) in [
+            ("2.5", [3, 2, 3, 2, 3, 2, 2, 2, 3, 2]),
+            ("-2.5", [-3, -3, -2, -2, -3, -2, -3, -2, -3, -2]),
+            ("3.5", [4, 3, 4, 3, 4, 4, 3, 3, 3, 3]),
+            ("0.05e1", [1, 0, 1, 0, 1, 0, 0, 0, 1, 1]),
+            ("-5.50", [-6, -6, -5, -5, -6, -6, -6, -5, -5, -6]),
+            ("5.5001", [6, 5, 6, 5, 6, 6, 6, 6, 6, 6]),
+        ] {
+            for (r, expect) in Rounding::ALL.into_iter().zip(expect) {
+                assert_eq!(i32::try_from_str_ext(s, 0, r), Ok(expect), "{s} {r:?}");
+            }
+        }
+
+        // negative scale
+        assert_eq!(i32::try_from_str_ext("12345", -2, Rounding::Round), Ok(123));
+        assert_eq!(
+            i32::try_from_str_ext("12350.1", -2, Rounding::Round),
+            Ok(124)
+        );
+
+        // exponent
+        assert_eq!(i32::try_from_str_ext("1.5e-7", 7, Rounding::Round), Ok(2));
+        assert_eq!(i32::try_from_str_ext("4e-5", 2, Rounding::Round), Ok(0));
+        assert_eq!(i32::try_from_str_ext("4e-5", 2, Rounding::Ceiling), Ok(1));
+        assert_eq!(i32::try_from_str_ext("-4e-5", 2, Rounding::Floor), Ok(-1));
+        assert_eq!(i32::try_from_str_ext("6e-3", 2, Rounding::Round), Ok(1));
+
+        // long strings
+        let s = "1.2345678901234567890123456789012345678901234567890";
+        assert_eq!(i32::try_from_str_ext(s, 4, Rounding::Round), Ok(12346));
+        assert_eq!(i8::try_from_str_ext(s, 1, Rounding::Floor), Ok(12));
+        assert_eq!(
+            i128::try_from_str_ext(s, 38, Rounding::Round),
+            Ok(123456789012345678901234567890123456789)
+        );
+        let s = "-0.0000000000000000000000000000000000000000000000001";
+        assert_eq!(i64::try_from_str_ext(s, 8, Rounding::Round), Ok(0));
+        assert_eq!(i64::try_from_str_ext(s, 8, Rounding::Floor), Ok(-1));
+
+        // MIN and MAX
+        assert_eq!(
+            i8::try_from_str_ext("-1.2849", 2, Rounding::Round),
+            Ok(-128)
+        );
+        assert_eq!(i8::try_from_str_ext("1.2749", 2, Rounding::Round), Ok(127));
+        assert_eq!(u8::try_from_str_ext("2.5549", 2, Rounding::Round), Ok(255));
+
+        // errors
+        let r = Rounding::Round;
+        assert_eq!(
+            i8::try_from_str_ext("-1.285", 2, r),
+            Err(ParseError::new(ParseErrorKind::FracOverflow, 3))
+        );
+        assert_eq!(
+            i8::try_from_str_ext("1.275", 2, r),
+            Err(ParseError::new(ParseErrorKind::FracOverflow, 2))
+        );
+        assert_eq!(
+            u8::try_from_str_ext("2.555", 2, r),
+            Err(ParseError::new(ParseErrorKind::FracOverflow, 2))
+        );
+        assert_eq!(
+            i32::try_from_str_ext("1e10", 2, r),
+            Err(ParseError::new(ParseErrorKind::IntOverflow, 0))
+        );
+        assert_eq!(
+            i32::try_from_str_ext("1.2x345", 2, r),
+            Err(ParseError::new(ParseErrorKind::Invalid, 3))
+        );
+        assert_eq!(
+            i32::try_from_str_ext("1.2345x", 2, r),
+            Err(ParseError::new(ParseErrorKind::Invalid, 6))
+        );
+        assert_eq!(
+            u32::try_from_str_ext("-1.2345", 2, r),
+            Err(ParseError::new(ParseErrorKind::Invalid, 0))
+        );
+        assert_eq!(
+            i32::try_from_str_ext("", 2, r),
+            Err(ParseError::new(ParseErrorKind::Empty, 0))
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        fn check<I: FpdecInner + fmt::Debug>(
+            s: &str,
+            scale: i32,
+            kind: ParseErrorKind,
+            offset: usize,
+        ) {
+            assert_eq!(
+                I::try_from_str(s, scale),
+                Err(ParseError::new(kind, offset))
+            );
+        }
+
+        // invalid
+        check::<i32>("-", 2, ParseErrorKind::Invalid, 1);
+        check::<i32>(".5", 2, ParseErrorKind::Invalid, 0);
+        check::<i32>("5.", 2, ParseErrorKind::Invalid, 2);
+        check::<i32>("+-5", 2, ParseErrorKind::Invalid, 1);
+        check::<i32>("1.2.3", 2, ParseErrorKind::Invalid, 3);
+        check::<i32>(" 1.23", 2, ParseErrorKind::Invalid, 0);
+        check::<i32>("1.23 ", 2, ParseErrorKind::Invalid, 4);
+        check::<i32>("1e+", 2, ParseErrorKind::Invalid, 3);
+        check::<u32>("-1.23", 2, ParseErrorKind::Invalid, 0);
+        assert_eq!(u32::try_from_str("-0.00", 2), Ok(0));
+
+        // overflow
+        check::<i8>("12.7", 2, ParseErrorKind::IntOverflow, 0);
+        check::<i8>("-1.29", 2, ParseErrorKind::FracOverflow, 3);
+        check::<i8>("+1.29", 2, ParseErrorKind::FracOverflow, 3);
+        check::<i8>("129e-2", 2, ParseErrorKind::FracOverflow, 1);
+        check::<i8>("-129", 0, ParseErrorKind::IntOverflow, 1);
+        assert_eq!(i8::try_from_str("-128", 0), Ok(-128));
+        assert_eq!(
+            i8::try_from_str_only("0.0129"),
+            Err(ParseError::new(ParseErrorKind::FracOverflow, 2))
+        );
+        assert_eq!(
+            i8::try_from_str_only("1290.5"),
+            Err(ParseError::new(ParseErrorKind::IntOverflow, 0))
+        );
+
+        // precision
+        check::<i32>("1.2345", 2, ParseErrorKind::Precision(2), 4);
+        check::<i32>("1.2300", 2, ParseErrorKind::Precision(2), 4);
+        check::<i32>("1234", -2, ParseErrorKind::Precision(2), 2);
+        check::<i32>("1230", -2, ParseErrorKind::Precision(1), 2);
+        assert_eq!(i32::try_from_str("1200", -2), Ok(12));
+
+        // from integer
+        assert_eq!(
+            1230_i32.checked_from_int(-4),
+            Err(ParseErrorKind::Precision(3).into())
+        );
+        assert_eq!(
+            i32::MAX.checked_from_int(2),
+            Err(ParseErrorKind::Overflow.into())
+        );
+
+        // display
+        let err = i32::try_from_str("1.2x", 2).unwrap_err();
+        assert_eq!(std::format!("{err}"), "invalid character at byte 3");
+        let err = i32::try_from_str("1.2345", 2).unwrap_err();
+        assert_eq!(
+            std::format!("{err}"),
+            "precision out of range by 2 digits at byte 4"
+        );
+        let err = ParseError::from(ParseErrorKind::Overflow);
+        assert_eq!(err.offset(), None);
+        assert_eq!(std::format!("{err}"), "overflow");
+    }
+
+    #[test]
+    fn test_parse_options() {
+        fn parse(s: &str, scale: i32, opts: ParseOptions) -> Result<i32, ParseError> {
+            i32::try_from_str_opts(s, scale, None, opts)
+        }
+        fn err(kind: ParseErrorKind, offset: usize) -> Result<i32, ParseError> {
+            Err(ParseError::new(kind, offset))
+        }
+        let default = ParseOptions::DEFAULT;
+
+        // plus sign
+        assert_eq!(parse("+1.5", 1, default), Ok(15));
+        let opts = ParseOptions {
+            allow_plus: false,
+            ..default
+        };
+        assert_eq!(parse("+1.5", 1, opts), err(ParseErrorKind::Invalid, 0));
+        assert_eq!(parse("-1.5", 1, opts), Ok(-15));
+
+        // bare point
+        assert_eq!(parse(".5", 1, default), err(ParseErrorKind::Invalid, 0));
+        assert_eq!(parse("5.", 1, default), err(ParseErrorKind::Invalid, 2));
+        let opts = ParseOptions {
+            allow_bare_point: true,
+            ..default
+        };
+        assert_eq!(parse(".5", 1, opts), Ok(5));
+        assert_eq!(parse("-.5", 1, opts), Ok(-5));
+        assert_eq!(parse("5.", 1, opts), Ok(50));
+        assert_eq!(parse("5.e3", 0, opts), Ok(5000));
+        assert_eq!(parse(".", 1, opts), err(ParseErrorKind::Invalid, 1));
+        assert_eq!(parse("-.e1", 1, opts), err(ParseErrorKind::Invalid, 2));
+
+        // whitespace
+        assert_eq!(parse(" 1.5", 1, default), err(ParseErrorKind::Invalid, 0));
+        let opts = ParseOptions {
+            trim_whitespace: true,
+            ..default
+        };
+        assert_eq!(parse("  1.5 \t\n", 1, opts), Ok(15));
+        assert_eq!(parse("   ", 1, opts), err(ParseErrorKind::Empty, 3));
+        assert_eq!(parse(" -1.2x ", 1, opts), err(ParseErrorKind::Invalid, 5));
+        assert_eq!(parse("1. 5", 1, opts), err(ParseErrorKind::Invalid, 2));
+        assert_eq!(
+            parse(" -1.234 ", 2, opts),
+            err(ParseErrorKind::Precision(1), 6)
+        );
+        assert_eq!(
+            u32::try_from_str_opts(" -1", 0, None, opts),
+            Err(ParseError::new(ParseErrorKind::Invalid, 1))
+        );
+
+        // separator
+        assert_eq!(parse("1_000", 0, default), err(ParseErrorKind::Invalid, 1));
+        let opts = ParseOptions {
+            separator: Some(b'_'),
+            ..default
+        };
+        assert_eq!(parse("1_000.000_1", 4, opts), Ok(10000001));
+        assert_eq!(parse("-1_2_3", 0, opts), Ok(-123));
+        assert_eq!(parse("1_200", -2, opts), Ok(12));
+        assert_eq!(i32::try_from_str_only_opts("1_200", opts), Ok((12, -2)));
+        assert_eq!(parse("1__0", 0, opts), err(ParseErrorKind::Invalid, 1));
+        assert_eq!(parse("_1", 0, opts), err(ParseErrorKind::Invalid, 0));
+        assert_eq!(parse("1_", 0, opts), err(ParseErrorKind::Invalid, 1));
+        assert_eq!(parse("1_.5", 1, opts), err(ParseErrorKind::Invalid, 1));
+        assert_eq!(parse("1._5", 1, opts), err(ParseErrorKind::Invalid, 2));
+        assert_eq!(parse("1e1_0", 0, opts), err(ParseErrorKind::Invalid, 3));
+        assert_eq!(
+            parse("1_234.567_8", 2, opts),
+            err(ParseErrorKind::Precision(2), 8)
+        );
+        let opts = ParseOptions {
+            separator: Some(b','),
+            ..default
+        };
+        assert_eq!(parse("1,234,567.25", 2, opts), Ok(123456725));
+
+        // invalid separator or point, which would be confused with the
+        // other parts
+        for sep in [b'0', b'5', b'9', b'+', b'-', b'e', b'E', b'.'] {
+            let opts = ParseOptions {
+                separator: Some(sep),
+                ..default
+            };
+            assert_eq!(parse("100.05", 2, opts), err(ParseErrorKind::Invalid, 0));
+            assert_eq!(parse("1.5", 1, opts), err(ParseErrorKind::Invalid, 0));
+        }
+        for point in [b'0', b'+', b'-', b'e', b'E', b'_'] {
+            let opts = ParseOptions {
+                separator: Some(b'_'),
+                point,
+                ..default
+            };
+            assert_eq!(parse("1", 0, opts), err(ParseErrorKind::Invalid, 0));
+        }
+
+        // point
+        let opts = ParseOptions {
+            separator: Some(b'.'),
+            point: b',',
+            ..default
+        };
+        assert_eq!(parse("1.234.567,25", 2, opts), Ok(123456725));
+        assert_eq!(parse("-0,5e1", 0, opts), Ok(-5));
+        assert_eq!(parse("1,2,3", 2, opts), err(ParseErrorKind::Invalid, 3));
+
+        // JSON
+        let opts = ParseOptions::JSON;
+        assert_eq!(parse("0", 2, opts), Ok(0));
+        assert_eq!(parse("-0.05", 2, opts), Ok(-5));
+        assert_eq!(parse("10.5E+1", 0, opts), Ok(105));
+        assert_eq!(parse("1.5e-1", 2, opts), Ok(15));
+        assert_eq!(parse("+1", 0, opts), err(ParseErrorKind::Invalid, 0));
+        assert_eq!(parse("00", 0, opts), err(ParseErrorKind::Invalid, 1));
+        assert_eq!(parse("-01.5", 1, opts), err(ParseErrorKind::Invalid, 2));
+        assert_eq!(parse(".5", 1, opts), err(ParseErrorKind::Invalid, 0));
+        assert_eq!(parse("1.", 1, opts), err(ParseErrorKind::Invalid, 2));
+        assert_eq!(parse(" 1", 0, opts), err(ParseErrorKind::Invalid, 0));
+    }
+
+    #[test]
+    fn test_parse_ascii() {
+        let opts = ParseOptions::DEFAULT;
+        for s in ["0", "-12.34", "+1.5e-1", "12.345", "1e10", "1.2x", "", "-"] {
+            assert_eq!(
+                i32::try_from_ascii_opts(s.as_bytes(), 2, None, opts),
+                i32::try_from_str(s, 2)
+            );
+            assert_eq!(
+                i32::try_from_ascii_only_opts(s.as_bytes(), opts),
+                i32::try_from_str_only(s)
+            );
+        }
+
+        // non-ASCII
+        assert_eq!(
+            i32::try_from_ascii_opts("1.5€".as_bytes(), 2, None, opts),
+            Err(ParseError::new(ParseErrorKind::Invalid, 3))
+        );
+        assert_eq!(
+            i32::try_from_ascii_opts(b"\xff1.5", 2, None, opts),
+            Err(ParseError::new(ParseErrorKind::Invalid, 0))
+        );
+        assert_eq!(
+            i32::try_from_ascii_opts(b"1.5\x80", 2, None, opts),
+            Err(ParseError::new(ParseErrorKind::Invalid, 3))
+        );
+    }
+
+    #[test]
+    fn test_parse_fast() {
+        assert_eq!(swar_parse_8_digits(b""), (0, 0));
+        assert_eq!(swar_parse_8_digits(b"x1"), (0, 0));
+        assert_eq!(swar_parse_8_digits(b"1"), (1, 1));
+        assert_eq!(swar_parse_8_digits(b"0123.5"), (123, 4));
+        assert_eq!(swar_parse_8_digits(b"12345678"), (12345678, 8));
+        assert_eq!(swar_parse_8_digits(b"987654321"), (98765432, 8));
+        assert_eq!(swar_parse_8_digits(b"9999999/"), (9999999, 7));
+        assert_eq!(swar_parse_8_digits(b"99:"), (99, 2));
+        assert_eq!(swar_parse_8_digits("9€".as_bytes()), (9, 1));
+
+        assert_eq!(
+            fast_parse_digits(b"1234567890123456789"),
+            Some((1234567890123456789, 19))
+        );
+        assert_eq!(fast_parse_digits(b"12345678901234567890"), None);
+        assert_eq!(
+            fast_parse_digits(b"1234567890123456.78"),
+            Some((1234567890123456, 16))
+        );
+
+        #[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "ssse3"))]
+        {
+            let parse = simd::parse_16_digits;
+            assert_eq!(parse(b"1234567890123456"), Some(1234567890123456));
+            assert_eq!(parse(b"98765432109876543"), Some(9876543210987654));
+            assert_eq!(parse(b"0000000000000009"), Some(9));
+            assert_eq!(parse(b"123456789012345/"), None);
+            assert_eq!(parse(b":234567890123456"), None);
+            assert_eq!(parse(b"123456789012345"), None);
+        }
+
+        // the fast path and the general path must be the same
+        fn check<I: FpdecInner + fmt::Debug>(s: &str) {
+            for scale in [-2, 0, 1, 2, 5, 18, 19, 40] {
+                assert_eq!(
+                    I::try_from_str(s, scale),
+                    I::try_from_str_opts(s, scale, None, ParseOptions::DEFAULT),
+                    "{s} {scale}"
+                );
+            }
+        }
+        let digits = "98765432109876543210987654321";
+        let mut buf = std::string::String::new();
+        for sign in ["", "-", "+"] {
+            for int_len in 0..digits.len() {
+                for frac_len in [None, Some(0), Some(1), Some(3), Some(8), Some(17)] {
+                    buf.clear();
+                    buf.push_str(sign);
+                    buf.push_str(&digits[..int_len]);
+                    if let Some(frac_len) = frac_len {
+                        buf.push('.');
+                        buf.push_str(&digits[int_len..(int_len + frac_len).min(digits.len())]);
+                    }
+                    check::<i8>(&buf);
+                    check::<u16>(&buf);
+                    check::<i32>(&buf);
+                    check::<i64>(&buf);
+                    check::<u64>(&buf);
+                    check::<i128>(&buf);
+                }
+            }
+        }
+        for s in [
+            "0",
+            "-0",
+            "-0.00",
+            "00000000000000000000000000001",
+            "9223372036854775807",
+            "-9223372036854775808",
+            "18446744073709551615",
+            "1.5e3",
+            "1.5x",
+            "1..5",
+            "1.-5",
+        ] {
+            check::<i64>(s);
+            check::<u64>(s);
+        }
+    }
+
+    #[test]
+    fn test_format_long() {
+        let zeros = |n| "0".repeat(n);
+
+        // big precision
+        let ts = TestFmt {
+            n: -314_i32,
+            scale: 2,
+        };
+        let s = std::format!("-3.14{}", zeros(298));
+        assert_eq!(std::format!("{:.300}", ts), s);
+        assert_eq!(
+            std::format!("{:*^1000.300}", ts),
+            std::format!("{:*^1000}", s)
+        );
+        assert_eq!(
+            std::format!("{:<1000.300}", ts),
+            std::format!("{:<1000}", s)
+        );
+        assert_eq!(std::format!("{:1000.300}", ts), std::format!("{:>1000}", s));
+        assert_eq!(
+            std::format!(
+                "{:+01000.300}",
+                TestFmt {
+                    n: 314_i32,
+                    scale: 2
+                }
+            ),
+            std::format!("+{}3.14{}", zeros(1000 - 5 - 298), zeros(298))
+        );
+
+        // big scale
+        let ts = TestFmt {
+            n: 5_u8,
+            scale: 300,
+        };
+        assert_eq!(std::format!("{}", ts), std::format!("0.{}5", zeros(299)));
+        assert_eq!(
+            std::format!("{:.299}", ts),
+            std::format!("0.{}1", zeros(298))
+        );
+        assert_eq!(
+            std::format!("{:.298}", ts),
+            std::format!("0.{}", zeros(298))
+        );
+        assert_eq!(std::format!("{:.0}", ts), "0");
+
+        let ts = TestFmt {
+            n: -5_i8,
+            scale: -300,
+        };
+        assert_eq!(std::format!("{}", ts), std::format!("-5{}", zeros(300)));
+        assert_eq!(
+            std::format!("{:.2}", ts),
+            std::format!("-5{}.00", zeros(300))
+        );
+
+        let mut buf = [0; 400];
+        let len = (-5_i8)
+            .try_write_to(-300, Some(2), Rounding::Round, &mut buf)
+            .unwrap();
+        assert_eq!(&buf[..len], std::format!("-5{}.00", zeros(300)).as_bytes());
+
+        // extreme scales, check the length only
+        let mut w = LenWriter(0);
+        fmt::write(
+            &mut w,
+            format_args!(
+                "{}",
+                TestFmt {
+                    n: 1_i8,
+                    scale: i32::MAX
+                }
+            ),
+        )
+        .unwrap();
+        assert_eq!(w.0, i32::MAX as usize + 2);
+        let mut w = LenWriter(0);
+        fmt::write(
+            &mut w,
+            format_args!(
+                "{}",
+                TestFmt {
+                    n: -1_i8,
+                    scale: i32::MIN
+                }
+            ),
+        )
+        .unwrap();
+        assert_eq!(w.0, i32::MAX as usize + 3);
+    }
+
+    struct TestExpFmt<I> {
+        n: I,
+        scale: i32,
+        eng: bool,
+    }
+    impl<I: FpdecInner> fmt::LowerExp for TestExpFmt<I> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+            self.n.display_exp_fmt(self.scale, self.eng, false, f)
+        }
+    }
+    impl<I: FpdecInner> fmt::UpperExp for TestExpFmt<I> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+            self.n.display_exp_fmt(self.scale, self.eng, true, f)
+        }
+    }
+
+    fn do_test_format_exp<I>(n: I)
+    where
+        I: FpdecInner + fmt::Display + fmt::Debug + fmt::LowerExp,
+    {
+        for scale in -50..50 {
+            for eng in [false, true] {
+                let ts = TestExpFmt { n, scale, eng };
+                let out = std::format!("{:e}", ts);
+
+                // exact, so load back the same
+                assert_eq!(I::try_from_str(&out, scale), Ok(n), "{out}");
+
+                let (int, exp) = out.split_once('e').unwrap();
+                let exp: i32 = exp.parse().unwrap();
+                let int_len = int.trim_start_matches('-').split('.').next().unwrap().len();
+                if eng {
+                    assert_eq!(exp % 3, 0);
+                    assert!((1..=3).contains(&int_len));
+                } else {
+                    assert_eq!(int_len, 1);
+                    if scale == 0 {
+                        assert_eq!(out, std::format!("{:e}", n));
+                    }
+                }
+
+                let upper = std::format!("{:E}", ts);
+                assert_eq!(upper, out.replace('e', "E"));
+
+                for precision in [0, 1, 3, 20] {
+                    let out = std::format!("{:.*e}", precision, ts);
+                    let (int, _) = out.split_once('e').unwrap();
+                    let frac_len = int.split_once('.').map_or(0, |(_, f)| f.len());
+                    assert_eq!(frac_len, precision);
+                }
+            }
+        }
+    }
+
+    fn format_exp<I: FpdecInner>(
+        n: I,
+        scale: i32,
+        eng: bool,
+        p: Option<usize>,
+    ) -> std::string::String {
+        let ts = TestExpFmt { n, scale, eng };
+        match p {
+            Some(p) => std::format!("{:.*e}", p, ts),
+            None => std::format!("{:e}", ts),
+        }
+    }
+
+    #[test]
+    fn test_format_exp() {
+        assert_eq!(format_exp(0_i32, 4, false, None), "0e0");
+        assert_eq!(format_exp(0_i32, 4, false, Some(2)), "0.00e0");
+        assert_eq!(format_exp(4658_i64, 9, false, None), "4.658e-6");
+        assert_eq!(format_exp(-4658_i64, 9, false, Some(1)), "-4.7e-6");
+        assert_eq!(format_exp(4658_i64, 9, false, Some(0)), "5e-6");
+        assert_eq!(format_exp(4658_i64, 9, false, Some(5)), "4.65800e-6");
+        assert_eq!(format_exp(12_i8, -3, false, None), "1.2e4");
+        assert_eq!(format_exp(-128_i8, 0, false, None), "-1.28e2");
+        assert_eq!(format_exp(-128_i8, 0, false, Some(1)), "-1.3e2");
+        assert_eq!(format_exp(255_u8, 1, false, Some(0)), "3e1");
+        assert_eq!(format_exp(9996_i16, 3, false, Some(2)), "1.00e1");
+        assert_eq!(format_exp(9995_i16, 3, false, Some(2)), "1.00e1");
+        assert_eq!(format_exp(9994_i16, 3, false, Some(2)), "9.99e0");
+        assert_eq!(format_exp(u128::MAX, 38, false, Some(3)), "3.403e0");
+        assert_eq!(
+            format_exp(i128::MIN, 0, false, None),
+            std::format!("{:e}", i128::MIN)
+        );
+
+        assert_eq!(format_exp(0_i32, 4, true, None), "0e0");
+        assert_eq!(format_exp(4658_i64, 9, true, None), "4.658e-6");
+        assert_eq!(format_exp(4658_i64, 10, true, None), "465.8e-9");
+        assert_eq!(format_exp(4658_i64, 11, true, None), "46.58e-9");
+        assert_eq!(format_exp(1_i64, 4, true, None), "100e-6");
+        assert_eq!(format_exp(1_i64, -4, true, None), "10e3");
+        assert_eq!(format_exp(1_i64, -4, true, Some(2)), "10.00e3");
+        assert_eq!(format_exp(12345678_i64, 3, true, None), "12.345678e3");
+        assert_eq!(format_exp(12345678_i64, 3, true, Some(2)), "12.35e3");
+        assert_eq!(format_exp(12345678_i64, 3, true, Some(0)), "12e3");
+        assert_eq!(format_exp(-99996_i64, 2, true, Some(1)), "-1.0e3");
+        assert_eq!(format_exp(99996_i64, 3, true, Some(1)), "100.0e0");
+        assert_eq!(format_exp(99996_i64, 3, true, Some(3)), "99.996e0");
+        assert_eq!(format_exp(999996_i64, 3, true, Some(2)), "1.00e3");
+
+        // formatting options
+        let ts = TestExpFmt {
+            n: -4658_i64,
+            scale: 9,
+            eng: false,
+        };
+        assert_eq!(std::format!("{:>12e}", ts), "   -4.658e-6");
+        assert_eq!(std::format!("{:*<12E}", ts), "-4.658E-6***");
+        assert_eq!(std::format!("{:012.1e}", ts), "-000004.7e-6");
+        let ts = TestExpFmt {
+            n: 4658_i64,
+            scale: 9,
+            eng: false,
+        };
+        assert_eq!(std::format!("{:+e}", ts), "+4.658e-6");
+
+        // big precision and scale
+        assert_eq!(
+            format_exp(5_i8, 1000, false, Some(200)),
+            std::format!("5.{}e-1000", "0".repeat(200))
+        );
+        assert_eq!(format_exp(5_i8, i32::MIN, true, None), "500e2147483646");
+        assert_eq!(format_exp(5_i8, i32::MAX, true, None), "500e-2147483649");
+
+        for n in [1, 12, 123, -1, -12, -123, i8::MIN, i8::MAX] {
+            do_test_format_exp(n);
+        }
+        for n in [0, 1, 12, 123, 255, 100] {
+            do_test_format_exp(n as u8);
+        }
+        for n in [i64::MAX, i64::MIN, 4658, -9999999, 1000000] {
+            do_test_format_exp(n);
+        }
+        for n in [i128::MAX, i128::MIN, 1, -123456789] {
+            do_test_format_exp(n);
+        }
+        do_test_format_exp(u128::MAX);
+    }
+
+    struct TestFmtWith<'a, I> {
+        n: I,
+        scale: i32,
+        nf: &'a NumberFormat,
+    }
+    impl<I: FpdecInner> fmt::Display for TestFmtWith<'_, I> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+            self.n.display_with_fmt(self.scale, self.nf, f)
+        }
+    }
+
+    // group the output of Display as reference
+    fn group_str(s: &str, nf: &NumberFormat) -> std::string::String {
+        let (sign, s) = match s.strip_prefix('-') {
+            Some(s) => ("-", s),
+            None => ("", s),
+        };
+        let (int, frac) = match s.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (s, None),
+        };
+
+        let mut groups = std::vec::Vec::new();
+        let mut end = int.len();
+        let mut size = nf.primary_group as usize;
+        while nf.group_separator.is_some() && size != 0 && end > size {
+            groups.push(&int[end - size..end]);
+            end -= size;
+            if nf.secondary_group != 0 {
+                size = nf.secondary_group as usize;
+            }
+        }
+        groups.push(&int[..end]);
+        groups.reverse();
+
+        let sep = nf.group_separator.map(std::string::String::from);
+        let mut out = std::string::String::from(sign) + &groups.join(sep.as_deref().unwrap_or(""));
+        if let Some(frac) = frac {
+            out.push(nf.decimal_mark);
+            out.push_str(frac);
+        }
+        out
+    }
+
+    #[test]
+    fn test_format_with() {
+        let custom = NumberFormat {
+            group_separator: Some('\u{202F}'),
+            primary_group: 4,
+            secondary_group: 1,
+            decimal_mark: '\u{2396}',
+            rounding: Rounding::Round,
+        };
+        let formats = [
+            NumberFormat::DEFAULT,
+            NumberFormat::EN,
+            NumberFormat::DE,
+            NumberFormat::FR,
+            NumberFormat::IN,
+            NumberFormat {
+                primary_group: 0,
+                ..NumberFormat::EN
+            },
+            custom,
+        ];
+
+        for nf in &formats {
+            for n in [0, 1, -12, 123, 1234, -123456, 1234567, i64::MAX, i64::MIN] {
+                for scale in [-30, -5, -1, 0, 1, 2, 3, 6, 10, 30] {
+                    let ts = TestFmt { n, scale };
+                    let tw = TestFmtWith { n, scale, nf };
+                    for precision in [None, Some(0), Some(2), Some(8)] {
+                        let (out, s) = match precision {
+                            Some(p) => (std::format!("{:.*}", p, tw), std::format!("{:.*}", p, ts)),
+                            None => (std::format!("{}", tw), std::format!("{}", ts)),
+                        };
+                        let expect = group_str(&s, nf);
+                        assert_eq!(out, expect);
+
+                        // formatting options, all chars counted in width
+                        let width = expect.chars().count() + 5;
+                        let p = precision.unwrap_or(20);
+                        let expect = match precision {
+                            Some(_) => expect,
+                            None => group_str(&std::format!("{:.1$}", ts, p), nf),
+                        };
+                        assert_eq!(
+                            std::format!("{:>1$.2$}", tw, width, p),
+                            std::format!("{:>1$}", expect, width)
+                        );
+                        assert_eq!(
+                            std::format!("{:*^1$.2$}", tw, width, p),
+                            std::format!("{:*^1$}", expect, width)
+                        );
+                        assert_eq!(
+                            std::format!("{:<1$.2$}", tw, width, p),
+                            std::format!("{:<1$}", expect, width)
+                        );
+                    }
+                }
+            }
+        }
+
+        let tw = TestFmtWith {
+            n: 1234_i32,
+            scale: 1,
+            nf: &NumberFormat::EN,
+        };
+        assert_eq!(std::format!("{:+}", tw), "+123.4");
+        assert_eq!(std::format!("{:+08}", tw), "+00123.4");
+        assert_eq!(std::format!("{:08.2}", tw), "00123.40");
+
+        // long integer part
+        let tw = TestFmtWith {
+            n: 1_i8,
+            scale: -300,
+            nf: &NumberFormat::IN,
+        };
+        let expect = std::format!("10{},000", ",00".repeat(148));
+        assert_eq!(std::format!("{}", tw), expect);
+        assert_eq!(std::format!("{:.2}", tw), expect + ".00");
+
+        // rounding
+        fn check<I>(n: I, scale: i32, precision: usize, expect: [&str; 10])
+        where
+            I: FpdecInner + fmt::Display,
+        {
+            for (rounding, expect) in Rounding::ALL.into_iter().zip(expect) {
+                let nf = NumberFormat {
+                    rounding,
+                    ..NumberFormat::EN
+                };
+                let tw = TestFmtWith { n, scale, nf: &nf };
+                assert_eq!(std::format!("{:.*}", precision, tw), expect, "{rounding:?}");
+            }
+        }
+        check(
+            -1225_i32,
+            3,
+            2,
+            [
+                "-1.23", "-1.23", "-1.22", "-1.22", "-1.23", "-1.22", "-1.23", "-1.22", "-1.23",
+                "-1.22",
+            ],
+        );
+        check(
+            15_i32,
+            1,
+            0,
+            ["2", "1", "2", "1", "2", "2", "1", "1", "1", "1"],
+        );
+        check(
+            12345005_i64,
+            4,
+            2,
+            [
+                "1,234.50", "1,234.50", "1,234.51", "1,234.50", "1,234.51", "1,234.50", "1,234.50",
+                "1,234.50", "1,234.50", "1,234.51",
+            ],
+        );
+        // 10^scale out of u16
+        check(
+            50000_u16,
+            5,
+            0,
+            ["1", "0", "1", "0", "1", "0", "0", "0", "1", "1"],
+        );
+    }
+
+    struct LenWriter(usize);
+    impl fmt::Write for LenWriter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 += s.len();
+            Ok(())
+        }
+    }
+
     #[test]
     fn test_format_num_only() {
         do_test_format_num_only(0);
@@ -816,5 +3373,566 @@ mod tests {
         assert_eq!(199_u32.rounding_div(100_u32, Rounding::Round).unwrap(), 2);
         assert_eq!(149_u32.rounding_div(100_u32, Rounding::Round).unwrap(), 1);
         assert_eq!(150_u32.rounding_div(100_u32, Rounding::Round).unwrap(), 2);
+
+        // ties of negative / negative, which were rounded towards zero
+        assert_eq!((-3_i32).rounding_div(-2, Rounding::Round), Some(2));
+        assert_eq!((-25_i32).rounding_div(-10, Rounding::Round), Some(3));
+        assert_eq!((-5_i64).rounding_div(-10, Rounding::Round), Some(1));
+        assert_eq!((-3_i128).rounding_div(-2, Rounding::Round), Some(2));
+        assert_eq!((-3_i32).rounding_div(2, Rounding::Round), Some(-2));
+    }
+
+    fn ref_rounding_div(a: i32, b: i32, rounding: Rounding) -> i32 {
+        let (a, b) = if b < 0 { (-a, -b) } else { (a, b) };
+        let floor = a.div_euclid(b);
+        let rem = a.rem_euclid(b);
+        if rem == 0 {
+            return floor;
+        }
+        let is_half = matches!(
+            rounding,
+            Rounding::Round
+                | Rounding::HalfEven
+                | Rounding::HalfDown
+                | Rounding::HalfTowardsZero
+                | Rounding::HalfOdd
+        );
+        let up = if is_half && rem * 2 != b {
+            rem * 2 > b
+        } else {
+            let towards_zero = if a < 0 { floor + 1 } else { floor };
+            rounding.tie_up(a < 0, floor % 2 != 0, towards_zero % 5 == 0)
+        };
+        floor + up as i32
+    }
+
+    #[test]
+    fn test_rounding_div_all() {
+        for r in Rounding::ALL {
+            for a in i8::MIN..=i8::MAX {
+                for b in (i8::MIN..=i8::MAX).filter(|&b| b != 0) {
+                    let expect = ref_rounding_div(a as i32, b as i32, r);
+                    let expect = i8::try_from(expect).ok();
+                    assert_eq!(a.rounding_div(b, r), expect, "{a} / {b} {r:?}");
+                }
+                assert_eq!(a.rounding_div(0, r), None);
+            }
+            for a in u8::MIN..=u8::MAX {
+                for b in 1..=u8::MAX {
+                    let expect = ref_rounding_div(a as i32, b as i32, r) as u8;
+                    assert_eq!(a.rounding_div(b, r), Some(expect), "{a} / {b} {r:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_div_rem() {
+        // sign extended to 128 bits
+        fn to_bits<I: FpdecInner>(x: I) -> u128 {
+            x.to_i128()
+                .map_or_else(|| x.to_u128().unwrap(), |x| x as u128)
+        }
+
+        // a * b == q * c + r, and q is same with calc_mul_div()
+        fn check<I: FpdecInner + fmt::Debug>(a: I, b: I, c: I) {
+            for r in Rounding::ALL {
+                let q = a.calc_mul_div(b, c, r);
+                let Some((q2, rem)) = a.calc_mul_div_rem(b, c, r) else {
+                    // overflow, or negative remainder for unsigned
+                    assert!(q.is_none() || I::MIN == I::ZERO, "{a:?} {b:?} {c:?} {r:?}");
+                    continue;
+                };
+                assert_eq!(q, Some(q2), "{a:?} {b:?} {c:?} {r:?}");
+                assert!(rem.unsigned_abs() < c.unsigned_abs());
+                let wrapping = |x: I, y: I| to_bits(x).wrapping_mul(to_bits(y));
+                assert_eq!(
+                    wrapping(a, b),
+                    wrapping(q2, c).wrapping_add(to_bits(rem)),
+                    "{a:?} {b:?} {c:?} {r:?}"
+                );
+            }
+        }
+        for (a, b, c) in [
+            (7, 3, 10),
+            (-7, 3, 10),
+            (7, -3, -10),
+            (-7, -3, 10),
+            (9, 2, 6),
+            (5, 1, 2),
+        ] {
+            check(a as i8, b as i8, c as i8);
+            check(a, b, c);
+            check(a as i64, b as i64, c as i64);
+            check(a as i128, b as i128, c as i128);
+            check(a.unsigned_abs(), b.unsigned_abs(), c.unsigned_abs());
+            check(
+                a.unsigned_abs() as u64,
+                b.unsigned_abs() as u64,
+                c.unsigned_abs() as u64,
+            );
+            check(
+                a.unsigned_abs() as u128,
+                b.unsigned_abs() as u128,
+                c.unsigned_abs() as u128,
+            );
+        }
+        for (a, b, c) in [
+            (i128::MAX, 3, 7),
+            (i128::MAX, -3, 7),
+            (i128::MIN, 3, -7),
+            (i128::MAX, i128::MAX, i128::MAX - 1),
+        ] {
+            check(a, b, c);
+        }
+        check(i64::MAX, i64::MAX, i64::MAX - 3);
+        check(u128::MAX, 3, 7);
+        check(u128::MAX, 7, u128::MAX - 1);
+        check(u128::MAX, u128::MAX, u128::MAX);
+        check(u64::MAX, u64::MAX, u64::MAX - 1);
+        assert_eq!(1.calc_mul_div_rem(1, 0, Rounding::Round), None);
+
+        // decimal operations
+        let r = Rounding::Round;
+        assert_eq!(1234_i64.checked_mul_rem(125, 4, r), Some((15, 4250)));
+        assert_eq!(1234_i64.checked_mul_rem(125, 0, r), Some((154250, 0)));
+        assert_eq!(1234_i64.checked_mul_rem(125, -1, r), Some((1542500, 0)));
+        assert_eq!(1234_u64.checked_mul_rem(125, 4, Rounding::Ceiling), None);
+        assert_eq!(
+            1234_u64.checked_mul_rem(125, 4, Rounding::Floor),
+            Some((15, 4250))
+        );
+        assert_eq!(
+            13_i64.checked_div_rem(300, -4, Rounding::Ceiling),
+            Some((434, -200))
+        );
+        assert_eq!(13_i64.checked_div_rem(3, 0, r), Some((4, 1)));
+        assert_eq!(13_i64.checked_div_rem(3, 1, r), Some((0, 13)));
+        assert_eq!(1300_i64.checked_div_rem(3, 1, r), Some((43, 10)));
+        assert_eq!(1300_i64.checked_div_rem(0, 1, r), None);
+
+        // two roundings as checked_div_ext(): 1.45 / 1.0 -> 2, not 1
+        assert_eq!(145_i32.checked_div_rem(10, 1, r), Some((2, -55)));
+        // no overflow of `rhs * diff_exp`
+        assert_eq!(100_i32.checked_div_rem(i32::MAX, 2, r), Some((0, 100)));
+    }
+
+    #[test]
+    fn test_div_rem_same_with_div_ext() {
+        let nums = [
+            0,
+            1,
+            -1,
+            7,
+            -13,
+            145,
+            -2500,
+            123456,
+            -98765432,
+            i32::MAX,
+            i32::MIN,
+            i32::MAX / 3,
+            i32::MIN / 7,
+        ];
+        for rounding in Rounding::ALL {
+            for diff_scale in -9..=9 {
+                for a in nums {
+                    for b in nums {
+                        if b == 0 {
+                            continue;
+                        }
+                        let q = a.checked_div_ext(b, diff_scale, rounding);
+                        let Some((q2, rem)) = a.checked_div_rem(b, diff_scale, rounding) else {
+                            // fails only if the quotient or the remainder overflows
+                            let Some(q) = q else {
+                                continue;
+                            };
+                            let exp = 10_i128.pow(diff_scale.unsigned_abs());
+                            let rem = if diff_scale > 0 {
+                                a as i128 - q as i128 * b as i128 * exp
+                            } else {
+                                a as i128 * exp - q as i128 * b as i128
+                            };
+                            assert!(i32::try_from(rem).is_err(), "{a} {b} {diff_scale}");
+                            continue;
+                        };
+                        assert_eq!(q, Some(q2), "{a} {b} {diff_scale} {rounding:?}");
+
+                        // self == result * rhs + remainder
+                        let exp = 10_i128.pow(diff_scale.unsigned_abs());
+                        let (a, b) = if diff_scale > 0 {
+                            (a as i128, b as i128 * exp)
+                        } else {
+                            (a as i128 * exp, b as i128)
+                        };
+                        assert_eq!(a, q2 as i128 * b + rem as i128);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrapping() {
+        // compare with the exact result wrapped to 8 bits
+        for r in Rounding::ALL {
+            for c in [1, -1, 3, -7, 10, 100, -100] {
+                for a in i8::MIN..=i8::MAX {
+                    for b in (-60..=60).step_by(3) {
+                        let exact = ref_rounding_div(a as i32 * b, c, r);
+                        let expect = (exact as i8, i8::try_from(exact).is_err());
+                        let got = a.overflowing_mul_div(b as i8, c as i8, r);
+                        assert_eq!(got, expect, "{a} * {b} / {c} {r:?}");
+                    }
+                }
+            }
+            for c in [1, 3, 10, 100] {
+                for a in u8::MIN..=u8::MAX {
+                    for b in (0..=120).step_by(3) {
+                        let exact = ref_rounding_div(a as i32 * b, c, r);
+                        let expect = (exact as u8, u8::try_from(exact).is_err());
+                        let got = a.overflowing_mul_div(b as u8, c as u8, r);
+                        assert_eq!(got, expect, "{a} * {b} / {c} {r:?}");
+                    }
+                }
+            }
+        }
+
+        // exact: i128::MAX * 2.5 == 5 * 2^126 - 2.5, which wraps to 2^126 - 2.5
+        let a = i128::MAX;
+        let w = 1_i128 << 126;
+        assert_eq!(a.wrapping_mul_div(25, 10, Rounding::Floor), w - 3);
+        assert_eq!(a.wrapping_mul_div(25, 10, Rounding::Round), w - 2);
+        assert_eq!(a.wrapping_mul_div(25, 10, Rounding::HalfEven), w - 2);
+        assert_eq!(a.wrapping_mul_div(25, 10, Rounding::HalfDown), w - 3);
+        assert_eq!(a.wrapping_mul_div(-25, -10, Rounding::Ceiling), w - 2);
+        assert_eq!(a.wrapping_mul_div(20, 10, Rounding::Round), -2);
+        assert_eq!(
+            i128::MIN.wrapping_mul_div(3, -1, Rounding::Round),
+            i128::MIN
+        );
+        assert_eq!(
+            u128::MAX.wrapping_mul_div(25, 10, Rounding::Floor),
+            (1_u128 << 127) - 3
+        );
+
+        // decimal operations
+        let r = Rounding::Round;
+        assert_eq!(12345_i16.overflowing_mul_ext(3, 1, r), (3704, false));
+        assert_eq!(
+            12345_i16.overflowing_mul_ext(30, 1, r),
+            (37035_i32 as i16, true)
+        );
+        assert_eq!(
+            12345_i16.overflowing_mul_ext(3, -1, r),
+            (370350_i32 as i16, true)
+        );
+        assert_eq!(
+            12345_i16.overflowing_div_ext(3, -1, r),
+            (41150_i32 as i16, true)
+        );
+        assert_eq!(i16::MIN.overflowing_div_ext(-1, 0, r), (i16::MIN, true));
+        assert_eq!(i16::MIN.overflowing_div_ext(-1, 1, r), (3277, false));
+        assert_eq!(12345_i16.overflowing_div_ext(7, 1, r), (176, false));
+
+        assert_eq!(12345_i16.saturating_mul_ext(30, 1, r), i16::MAX);
+        assert_eq!(12345_i16.saturating_mul_ext(-30, 1, r), i16::MIN);
+        assert_eq!((-12345_i16).saturating_mul_ext(-3, -1, r), i16::MAX);
+        assert_eq!(i16::MIN.saturating_div_ext(-1, 0, r), i16::MAX);
+        assert_eq!(12345_i16.saturating_div_ext(-1, -1, r), i16::MIN);
+        assert_eq!(200_u8.saturating_mul_ext(2, 0, r), u8::MAX);
+        assert_eq!(200_u8.saturating_div_ext(1, -1, r), u8::MAX);
+        assert_eq!(200_u8.saturating_div_ext(3, 1, r), 7);
+    }
+
+    #[test]
+    fn test_try_arith() {
+        use ArithmeticError::*;
+
+        // same with checked_*(), and the error tells the side of overflow
+        for r in Rounding::ALL {
+            for a in i8::MIN..=i8::MAX {
+                for b in i8::MIN..=i8::MAX {
+                    let exact = a as i32 * b as i32;
+                    let err = if exact < 0 { Underflow } else { Overflow };
+                    for diff in [-1, 0, 1] {
+                        let expect = a.checked_mul_ext(b, diff, r).ok_or(err);
+                        assert_eq!(a.try_mul_ext(b, diff, r), expect, "{a} {b} {diff}");
+                        if b != 0 {
+                            let expect = a.checked_div_ext(b, diff, r).ok_or(err);
+                            assert_eq!(a.try_div_ext(b, diff, r), expect, "{a} {b} {diff}");
+                        }
+                    }
+                    let expect = a.calc_mul_div(b, 3, r).ok_or(err);
+                    assert_eq!(a.try_mul_div(b, 3, r), expect, "{a} {b}");
+                    let expect = a.calc_mul_div(b, -7, r).ok_or(if exact > 0 {
+                        Underflow
+                    } else {
+                        Overflow
+                    });
+                    assert_eq!(a.try_mul_div(b, -7, r), expect, "{a} {b}");
+                }
+            }
+        }
+
+        let r = Rounding::Round;
+        assert_eq!(1_i8.try_div_ext(0, 0, r), Err(DivByZero));
+        assert_eq!(1_i8.try_div_ext(0, 9, r), Err(DivByZero));
+        assert_eq!(1_i8.try_mul_div(1, 0, r), Err(DivByZero));
+        assert_eq!(1_i8.try_mul_ext(1, 3, r), Err(ScaleOutOfRange));
+        assert_eq!(1_i8.try_div_ext(1, -3, r), Err(ScaleOutOfRange));
+        assert_eq!(1_i8.try_mul_ext(1, 2, r), Ok(0));
+        assert_eq!(1_i128.try_mul_ext(1, 38, r), Ok(0));
+        assert_eq!(1_i128.try_mul_ext(1, 39, r), Err(ScaleOutOfRange));
+        assert_eq!(i128::MIN.try_div_ext(-1, 0, r), Err(Overflow));
+        assert_eq!(u64::MAX.try_mul_ext(2, 0, r), Err(Overflow));
+        assert_eq!(u64::MAX.try_mul_div(3, 2, r), Err(Overflow));
+
+        // the other operand out of range
+        assert_eq!(try_into_inner::<i8, i32>(-100), Ok(-100_i8));
+        assert_eq!(try_into_inner::<i8, i32>(200), Err(Overflow));
+        assert_eq!(try_into_inner::<u8, i32>(-1), Err(Underflow));
+    }
+
+    #[test]
+    fn test_add_mixed() {
+        fn check<I, J>(a: I, b: J)
+        where
+            I: FpdecInner + fmt::Debug,
+            J: FpdecInner + fmt::Debug,
+        {
+            let (x, y) = (a.to_i32().unwrap(), b.to_i32().unwrap());
+            let add = checked_add_mixed(a, b, false);
+            assert_eq!(add, I::from(x + y), "{a:?} + {b:?}");
+            let sub = checked_add_mixed(a, b, true);
+            assert_eq!(sub, I::from(x - y), "{a:?} - {b:?}");
+        }
+        for a in i8::MIN..=i8::MAX {
+            for b in u8::MIN..=u8::MAX {
+                check(a, b);
+                check(b, a);
+            }
+            for b in (i16::MIN..=i16::MAX).step_by(7) {
+                check(a, b);
+                check(b, a);
+            }
+        }
+
+        // extreme values
+        assert_eq!(
+            checked_add_mixed(u128::MAX, -1_i8, false),
+            Some(u128::MAX - 1)
+        );
+        assert_eq!(
+            checked_add_mixed(u128::MAX, i128::MIN, false),
+            Some(u128::MAX / 2)
+        );
+        assert_eq!(checked_add_mixed(i128::MIN, u128::MAX / 2, false), Some(-1));
+        assert_eq!(
+            checked_add_mixed(i128::MIN, u128::MAX, false),
+            Some(i128::MAX)
+        );
+        assert_eq!(checked_add_mixed(-1_i128, u128::MAX, true), None);
+        assert_eq!(checked_add_mixed(0_i8, i128::MIN, true), None);
+        assert_eq!(checked_add_mixed(i8::MIN, -128_i128, true), Some(0));
+        assert_eq!(checked_add_mixed(0_u8, 1_u128 << 127, false), None);
+    }
+
+    #[test]
+    fn test_rescale() {
+        fn check<I>(a: I)
+        where
+            I: FpdecInner + fmt::Debug + TryFrom<i64>,
+        {
+            let x = a.to_i64().unwrap();
+            for diff in -7..=7_i32 {
+                let exp = 10_i64.pow(diff.unsigned_abs());
+                for r in Rounding::ALL {
+                    let expect = if diff >= 0 {
+                        I::try_from(ref_rounding_div(x as i32, exp as i32, r) as i64).ok()
+                    } else {
+                        I::try_from(x * exp).ok()
+                    };
+                    assert_eq!(a.checked_rescale(diff, r), expect, "{a:?} {diff} {r:?}");
+                }
+
+                let expect = if diff >= 0 && x % exp != 0 {
+                    Err(ParseErrorKind::Precision(0))
+                } else if diff >= 0 {
+                    Ok(I::try_from(x / exp).ok().unwrap())
+                } else {
+                    I::try_from(x * exp).map_err(|_| ParseErrorKind::Overflow)
+                };
+                let got = a.try_rescale(diff).map_err(|e| match e.kind() {
+                    ParseErrorKind::Precision(_) => ParseErrorKind::Precision(0),
+                    k => k,
+                });
+                assert_eq!(got, expect, "{a:?} {diff}");
+            }
+        }
+        for a in i8::MIN..=i8::MAX {
+            check(a);
+        }
+        for a in u8::MIN..=u8::MAX {
+            check(a);
+        }
+        for a in (i16::MIN..=i16::MAX).step_by(97) {
+            check(a);
+        }
+        for a in (u16::MIN..=u16::MAX).step_by(89) {
+            check(a);
+        }
+        check(u16::MAX);
+        check(i16::MIN);
+
+        // big difference
+        let r = Rounding::Round;
+        assert_eq!(i128::MIN.checked_rescale(40, r), Some(0));
+        assert_eq!(i128::MIN.checked_rescale(40, Rounding::Floor), Some(-1));
+        assert_eq!(u128::MAX.checked_rescale(39, r), Some(0));
+        assert_eq!(u128::MAX.checked_rescale(39, Rounding::Ceiling), Some(1));
+        assert_eq!(
+            u128::MAX.checked_rescale(i32::MAX, Rounding::Ceiling),
+            Some(1)
+        );
+        assert_eq!(0_i128.checked_rescale(i32::MIN, r), Some(0));
+        assert_eq!(1_i128.checked_rescale(i32::MIN, r), None);
+        assert_eq!(1_i128.checked_rescale(-38, r), Some(10_i128.pow(38)));
+        assert_eq!(
+            12_i32.try_rescale(i32::MAX),
+            Err(ParseErrorKind::Precision(i32::MAX as u32).into())
+        );
+        assert_eq!(1200_i32.try_rescale(2), Ok(12));
+        assert_eq!(
+            1200_i32.try_rescale(3),
+            Err(ParseErrorKind::Precision(1).into())
+        );
+    }
+
+    #[test]
+    fn test_stochastic() {
+        use crate::SplitMix64;
+
+        // the result is either the floor or the ceiling
+        fn check<I: FpdecInner + fmt::Debug>(a: I, b: I, c: I, rng: &mut SplitMix64) {
+            let floor = a.calc_mul_div(b, c, Rounding::Floor);
+            let ceiling = a.calc_mul_div(b, c, Rounding::Ceiling);
+            for _ in 0..10 {
+                let q = a.calc_mul_div_stochastic(b, c, rng);
+                assert!(q == floor || q == ceiling, "{a:?} * {b:?} / {c:?}: {q:?}");
+            }
+        }
+        let mut rng = SplitMix64::new(0);
+        for (a, b, c) in [
+            (7, 3, 10),
+            (-7, 3, 10),
+            (7, -3, 10),
+            (-7, -3, 10),
+            (9, 2, 6),
+        ] {
+            check(a as i8, b as i8, c as i8, &mut rng);
+            check(a as i64, b as i64, c as i64, &mut rng);
+            check(a as i128, b as i128, c as i128, &mut rng);
+            check(a.unsigned_abs(), b.unsigned_abs(), c as u32, &mut rng);
+        }
+        for (a, b, c) in [(i128::MAX, 3, 7), (i128::MAX, -3, 7), (i128::MIN, 3, 7)] {
+            check(a, b, c, &mut rng);
+            check(a / 3, b, c, &mut rng);
+        }
+        check(u128::MAX, 3, 7, &mut rng);
+        check(u128::MAX, 7, u128::MAX - 1, &mut rng);
+        check(i64::MAX, i64::MAX, i64::MAX - 3, &mut rng);
+        assert_eq!(i8::MIN.rounding_div_stochastic(-1, &mut rng), None);
+        assert_eq!(1.rounding_div_stochastic(0, &mut rng), None);
+        assert_eq!(i128::MAX.calc_mul_div_stochastic(2, 1, &mut rng), None);
+
+        // unbiased: rounding away with probability of the fraction
+        let n = 3000;
+        for (a, b, c, thirds) in [
+            (1, 1, 3, 1),
+            (-2, 1, 3, 2),
+            (i128::MAX / 2 + 1, 2, 3 << 100, 2), // 2^27 / 3
+            (i128::MIN, 1, 3 << 100, 2),         // -2^27 / 3
+        ] {
+            let truncated = a.calc_mul_div(b, c, Rounding::TowardsZero);
+            let away = (0..n)
+                .filter(|_| a.calc_mul_div_stochastic(b, c, &mut rng) != truncated)
+                .count();
+            assert!(away.abs_diff(n * thirds / 3) < n / 20, "{a} {away}");
+        }
+
+        // decimal operations
+        assert_eq!(
+            12345_i32.checked_mul_stochastic(10, 1, &mut rng),
+            Some(12345)
+        );
+        assert_eq!(
+            12345_i32.checked_mul_stochastic(10, -1, &mut rng),
+            Some(1234500)
+        );
+        assert_eq!(12345_i32.checked_div_stochastic(5, 0, &mut rng), Some(2469));
+        assert_eq!(
+            12345_i32.checked_div_stochastic(5, -1, &mut rng),
+            Some(24690)
+        );
+        let q = 12345_i32.checked_div_stochastic(7, 1, &mut rng).unwrap();
+        assert!(q == 176 || q == 177);
+
+        // deterministic
+        let mut rng1 = SplitMix64::new(9);
+        let mut rng2 = SplitMix64::new(9);
+        for i in 0..100 {
+            assert_eq!(
+                i.checked_div_stochastic(7, -2, &mut rng1),
+                i.checked_div_stochastic(7, -2, &mut rng2)
+            );
+        }
+    }
+
+    #[test]
+    fn test_allocate() {
+        use std::vec::Vec;
+
+        fn allocate<I: FpdecInner>(n: I, ratios: &[I]) -> Option<Vec<I>> {
+            let parts: Vec<I> = n.allocate(ratios)?.collect();
+            assert_eq!(parts.len(), ratios.len());
+            assert!(parts.iter().fold(I::ZERO, |acc, &p| acc + p) == n);
+            Some(parts)
+        }
+        fn split_evenly<I: FpdecInner>(n: I, count: usize) -> Option<Vec<I>> {
+            let parts: Vec<I> = n.split_evenly(count)?.collect();
+            assert_eq!(parts.len(), count);
+            assert!(parts.iter().fold(I::ZERO, |acc, &p| acc + p) == n);
+            Some(parts)
+        }
+
+        assert_eq!(allocate(10000, &[1, 1, 1]).unwrap(), [3334, 3333, 3333]);
+        assert_eq!(allocate(-10000, &[1, 1, 1]).unwrap(), [-3334, -3333, -3333]);
+        assert_eq!(allocate(5, &[0, 1, 0, 1, 1]).unwrap(), [0, 2, 0, 2, 1]);
+        assert_eq!(allocate(-5, &[3, 0, 7]).unwrap(), [-2, 0, -3]);
+        assert_eq!(allocate(0, &[3, 7]).unwrap(), [0, 0]);
+        assert_eq!(allocate(100_u8, &[1, 2]).unwrap(), [33, 67]);
+        assert_eq!(allocate(10, &[2, 3, 2]).unwrap(), [3, 4, 3]);
+        assert_eq!(allocate(-10, &[2, 3, 2]).unwrap(), [-3, -4, -3]);
+        assert_eq!(allocate(8, &[1, 1, 1, 0]).unwrap(), [3, 3, 2, 0]);
+        assert_eq!(allocate(99, &[1, 10, 100]).unwrap(), [1, 9, 89]);
+        assert_eq!(
+            allocate(i128::MAX, &[1, 1]).unwrap(),
+            [i128::MAX / 2 + 1, i128::MAX / 2]
+        );
+        assert_eq!(allocate(i8::MIN, &[100, 27]).unwrap(), [-101, -27]);
+        assert_eq!(allocate(100, &[1, -1, 2]), None);
+        assert_eq!(allocate(100, &[0, 0]), None);
+        assert_eq!(allocate::<i32>(100, &[]), None);
+        assert_eq!(allocate(100_i8, &[100, 100]), None); // sum overflow
+
+        assert_eq!(split_evenly(10000, 3).unwrap(), [3334, 3333, 3333]);
+        assert_eq!(split_evenly(-7, 4).unwrap(), [-2, -2, -2, -1]);
+        assert_eq!(split_evenly(3_i8, 200).unwrap()[..4], [1, 1, 1, 0]);
+        assert_eq!(
+            split_evenly(u64::MAX, 2).unwrap(),
+            [u64::MAX / 2 + 1, u64::MAX / 2]
+        );
+        assert_eq!(split_evenly(100, 0), None);
     }
 }
@@ -1,4 +1,7 @@
-use crate::fpdec_inner::FpdecInner;
+use crate::fpdec_inner::{
+    add_rounding_div, magnitude_rounding, round_by_last_digit, rounding_extra, FpdecInner,
+};
+use crate::stochastic::StochasticRng;
 use crate::Rounding;
 
 impl FpdecInner for i128 {
@@ -8,7 +11,6 @@ impl FpdecInner for i128 {
     const HUNDRED: Self = 100;
     const MAX_POWERS: Self = 10_i128.pow(Self::DIGITS);
     const DIGITS: u32 = Self::MAX.ilog10();
-    const NEG_MIN_STR: &'static str = "170141183460469231731687303715884105728";
 
     type Unsigned = u128;
     fn unsigned_abs(self) -> Self::Unsigned {
@@ -77,76 +79,70 @@ impl FpdecInner for i128 {
         }
 
         // normal path
+        let (q, last_dividend) = reduce_mul_div_i128(self, b, c)?;
 
-        // (mhigh, mlow) = a * b
-        let (mhigh, mlow) = mul2(self.unsigned_abs(), b.unsigned_abs());
-
-        // (last_dividend, q) = (mhigh, mlow) / abs(c)
-        let unsigned_c = c.unsigned_abs();
-        let (last_dividend, mut q) = reduce2(mhigh, mlow, unsigned_c, 2)?;
+        // final division
+        add_rounding_div(q, last_dividend, c, rounding)
+    }
 
-        // back to signed i128: last_dividend
-        let mut last_dividend = match i128::try_from(last_dividend) {
-            Ok(dividend) => dividend,
-            Err(_) => {
-                // one more division
-                q = q.checked_add(last_dividend / unsigned_c)?;
-                (last_dividend % unsigned_c) as i128
-            }
-        };
-        if (self ^ b) < 0 {
-            last_dividend = -last_dividend;
+    fn calc_mul_div_stochastic<R>(self, b: Self, c: Self, rng: &mut R) -> Option<Self>
+    where
+        R: StochasticRng + ?Sized,
+    {
+        // happy path, no overflow
+        if let Some(r) = self.checked_mul(b) {
+            return r.rounding_div_stochastic(c, rng);
         }
 
-        // back to signed i128: quotient
-        let mut q = i128::try_from(q).ok()?;
-        if (self ^ b ^ c) < 0 {
-            q = -q;
-        }
+        // normal path
+        let (q, last_dividend) = reduce_mul_div_i128(self, b, c)?;
+        q.checked_add(last_dividend.rounding_div_stochastic(c, rng)?)
+    }
 
-        // final division
-        let last_q = last_dividend.rounding_div(c, rounding)?;
+    fn calc_mul_div_rem(self, b: Self, c: Self, rounding: Rounding) -> Option<(Self, Self)> {
+        // happy path, no overflow
+        if let Some(p) = self.checked_mul(b) {
+            let q = p.rounding_div(c, rounding)?;
+            // the remainder fits, so the wrapping does not matter
+            return Some((q, p.wrapping_sub(q.wrapping_mul(c))));
+        }
 
-        q.checked_add(last_q)
+        // normal path
+        let (q0, last_dividend) = reduce_mul_div_i128(self, b, c)?;
+        let q = add_rounding_div(q0, last_dividend, c, rounding)?;
+        let r = last_dividend.wrapping_sub(q.wrapping_sub(q0).wrapping_mul(c));
+        Some((q, r))
     }
 
     fn calc_mul_div_exp(self, b: Self, i: usize, rounding: Rounding) -> Option<Self> {
-        let exp = Self::get_exp(i)?;
+        let exp = Self::get_exp(i)? as u128;
 
-        let ua = self.unsigned_abs();
-        let ub = b.unsigned_abs();
-
-        if self ^ b >= 0 {
-            let extra = match rounding {
-                Rounding::Floor | Rounding::TowardsZero => 0,
-                Rounding::Ceiling | Rounding::AwayFromZero => exp - 1,
-                Rounding::Round => exp / 2, // exp is even
-            };
-
-            if ua.leading_zeros() + ub.leading_zeros() >= 128 + 2 {
-                // happy path, (self * b + extra) is not overflow
-                Some(div_exp_fast_1word(ua * ub + extra as u128, i) as i128)
-            } else {
-                let q = div_exp_fast_2word(ua, ub, extra as u128, exp as u128, i)?;
-                i128::try_from(q).ok()
-            }
+        // calculate on the absolute values
+        let is_neg = self ^ b < 0;
+        let rounding = magnitude_rounding(rounding, is_neg);
+        let extra = rounding_extra(rounding, exp);
+        let (ua, ub) = (self.unsigned_abs(), b.unsigned_abs());
+
+        let q = if ua.leading_zeros() + ub.leading_zeros() >= 128 + 2 {
+            // happy path, (ua * ub + extra) is not overflow
+            div_exp_fast_1word(ua * ub + extra, i)
         } else {
-            let exp = exp as u128;
-            let extra = match rounding {
-                Rounding::Ceiling | Rounding::TowardsZero => 0,
-                Rounding::Floor | Rounding::AwayFromZero => exp - 1,
-                Rounding::Round => exp / 2, // exp is even
-            };
-
-            let q = div_exp_fast_2word(ua, ub, extra, exp, i)?;
-
-            if q <= i128::MAX as u128 {
-                Some(-(q as i128))
-            } else if q == i128::MAX as u128 + 1 {
-                Some(i128::MIN)
-            } else {
-                None
-            }
+            div_exp_fast_2word(ua, ub, extra, exp, i)?
+        };
+
+        // the remainder is less than exp, so the lower 128 bits is enough
+        let q = round_by_last_digit(q, exp, rounding, || {
+            ua.wrapping_mul(ub).wrapping_sub(q.wrapping_mul(exp))
+        })?;
+
+        if !is_neg {
+            i128::try_from(q).ok()
+        } else if q <= i128::MAX as u128 {
+            Some(-(q as i128))
+        } else if q == i128::MAX as u128 + 1 {
+            Some(i128::MIN)
+        } else {
+            None
         }
     }
 
@@ -172,9 +168,6 @@ impl FpdecInner for u128 {
     const MAX_POWERS: Self = 10_u128.pow(Self::DIGITS);
     const DIGITS: u32 = Self::MAX.ilog10();
 
-    #[doc(hidden)]
-    const NEG_MIN_STR: &'static str = "unreachable";
-
     type Unsigned = Self;
     fn unsigned_abs(self) -> Self::Unsigned {
         self
@@ -242,38 +235,57 @@ impl FpdecInner for u128 {
         }
 
         // normal path
+        let (q, last_dividend) = reduce_mul_div_u128(self, b, c)?;
 
-        // (mhigh, mlow) = a * b
-        let (mhigh, mlow) = mul2(self, b);
+        // final division
+        add_rounding_div(q, last_dividend, c, rounding)
+    }
 
-        // (last_dividend, q) = (mhigh, mlow) / c
-        let (last_dividend, q) = if c < (1_u128 << 127) {
-            reduce2(mhigh, mlow, c, 1)?
-        } else {
-            reduce2_big(mhigh, mlow, c)?
-        };
+    fn calc_mul_div_stochastic<R>(self, b: Self, c: Self, rng: &mut R) -> Option<Self>
+    where
+        R: StochasticRng + ?Sized,
+    {
+        // happy path, no overflow
+        if let Some(r) = self.checked_mul(b) {
+            return r.rounding_div_stochastic(c, rng);
+        }
 
-        // final division
-        let last_q = last_dividend.rounding_div(c, rounding)?;
+        // normal path
+        let (q, last_dividend) = reduce_mul_div_u128(self, b, c)?;
+        q.checked_add(last_dividend.rounding_div_stochastic(c, rng)?)
+    }
+
+    fn calc_mul_div_rem(self, b: Self, c: Self, rounding: Rounding) -> Option<(Self, Self)> {
+        // happy path, no overflow
+        if let Some(p) = self.checked_mul(b) {
+            let q = p.rounding_div(c, rounding)?;
+            // overflow only if the remainder is negative
+            return Some((q, p.checked_sub(q.checked_mul(c)?)?));
+        }
 
-        q.checked_add(last_q)
+        // normal path
+        let (q0, last_dividend) = reduce_mul_div_u128(self, b, c)?;
+        let q = add_rounding_div(q0, last_dividend, c, rounding)?;
+        let r = last_dividend.checked_sub((q - q0).checked_mul(c)?)?;
+        Some((q, r))
     }
 
     fn calc_mul_div_exp(self, b: Self, i: usize, rounding: Rounding) -> Option<Self> {
         let exp = Self::get_exp(i)?;
 
-        let extra = match rounding {
-            Rounding::Floor | Rounding::TowardsZero => 0,
-            Rounding::Ceiling | Rounding::AwayFromZero => exp - 1,
-            Rounding::Round => exp / 2, // exp is even
-        };
+        let extra = rounding_extra(rounding, exp);
 
-        if self.leading_zeros() + b.leading_zeros() >= 128 + 1 {
+        let q = if self.leading_zeros() + b.leading_zeros() > 128 {
             // happy path, (self * b + extra) is not overflow
-            Some(div_exp_fast_1word(self * b + extra, i))
+            div_exp_fast_1word(self * b + extra, i)
         } else {
-            div_exp_fast_2word(self, b, extra as u128, exp, i)
-        }
+            div_exp_fast_2word(self, b, extra, exp, i)?
+        };
+
+        // the remainder is less than exp, so the lower 128 bits is enough
+        round_by_last_digit(q, exp, rounding, || {
+            self.wrapping_mul(b).wrapping_sub(q.wrapping_mul(exp))
+        })
     }
 
     fn rounding_div(self, b: Self, rounding: Rounding) -> Option<Self> {
@@ -287,6 +299,51 @@ impl FpdecInner for u128 {
     }
 }
 
+// calculate: a * b / c = q .. last_dividend, where the final division
+// `last_dividend / c` is left to the caller for rounding.
+fn reduce_mul_div_i128(a: i128, b: i128, c: i128) -> Option<(i128, i128)> {
+    // (mhigh, mlow) = a * b
+    let (mhigh, mlow) = mul2(a.unsigned_abs(), b.unsigned_abs());
+
+    // (last_dividend, q) = (mhigh, mlow) / abs(c)
+    let unsigned_c = c.unsigned_abs();
+    let (last_dividend, mut q) = reduce2(mhigh, mlow, unsigned_c, 2)?;
+
+    // back to signed i128: last_dividend
+    let mut last_dividend = match i128::try_from(last_dividend) {
+        Ok(dividend) => dividend,
+        Err(_) => {
+            // one more division
+            q = q.checked_add(last_dividend / unsigned_c)?;
+            (last_dividend % unsigned_c) as i128
+        }
+    };
+    if (a ^ b) < 0 {
+        last_dividend = -last_dividend;
+    }
+
+    // back to signed i128: quotient
+    let mut q = i128::try_from(q).ok()?;
+    if (a ^ b ^ c) < 0 {
+        q = -q;
+    }
+    Some((q, last_dividend))
+}
+
+// same with reduce_mul_div_i128() but for unsigned
+fn reduce_mul_div_u128(a: u128, b: u128, c: u128) -> Option<(u128, u128)> {
+    // (mhigh, mlow) = a * b
+    let (mhigh, mlow) = mul2(a, b);
+
+    // (last_dividend, q) = (mhigh, mlow) / c
+    let (last_dividend, q) = if c < (1_u128 << 127) {
+        reduce2(mhigh, mlow, c, 1)?
+    } else {
+        reduce2_big(mhigh, mlow, c)?
+    };
+    Some((q, last_dividend))
+}
+
 // calculate: a * b = (mhigh,mlow)
 const fn mul2(a: u128, b: u128) -> (u128, u128) {
     let (ahigh, alow) = (a >> 64, a & u64::MAX as u128);
@@ -325,7 +382,7 @@ fn reduce2(mhigh: u128, mlow: u128, divisor: u128, rate: u128) -> Option<(u128,
             break;
         }
         dividend = dividend << zeros | mlow << total_shft >> (128 - zeros);
-        q = q << zeros | dividend / divisor;
+        q = (q << zeros) | (dividend / divisor);
 
         dividend %= divisor;
         total_shft += zeros;
@@ -744,53 +801,32 @@ mod tests {
         }
     }
 
-    fn do_test_calc_mul_div(a: u128, b: u128, exp: u128, iexp: usize) {
-        let q1 = a.calc_mul_div(b, exp, Rounding::Floor);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Floor);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Ceiling);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Ceiling);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Round);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Round);
-        assert_eq!(q1, q2);
-    }
-
-    fn do_test_calc_mul_div_signed(a: i128, b: i128, exp: i128, iexp: usize) {
-        // +-
-        let q1 = a.calc_mul_div(b, exp, Rounding::Floor);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Floor);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Ceiling);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Ceiling);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Round);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Round);
-        assert_eq!(q1, q2);
-
-        // --
-        let a = -a;
-        let q1 = a.calc_mul_div(b, exp, Rounding::Floor);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Floor);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Ceiling);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Ceiling);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Round);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Round);
-        assert_eq!(q1, q2);
-
-        // ++
-        let b = b.wrapping_neg();
-        let q1 = a.calc_mul_div(b, exp, Rounding::Floor);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Floor);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Ceiling);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Ceiling);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Round);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Round);
-        assert_eq!(q1, q2);
+    // The original kinds `Round`, `Floor` and `Ceiling` are checked for
+    // all the cases, while the others for every 5th case to save time.
+    fn roundings(j: usize) -> &'static [Rounding] {
+        if j.is_multiple_of(5) {
+            &Rounding::ALL
+        } else {
+            &Rounding::ALL[..3]
+        }
+    }
+
+    fn do_test_calc_mul_div(a: u128, b: u128, exp: u128, iexp: usize, rs: &[Rounding]) {
+        for &r in rs {
+            let q1 = a.calc_mul_div(b, exp, r);
+            let q2 = a.calc_mul_div_exp(b, iexp, r);
+            assert_eq!(q1, q2, "{a} * {b} / {exp} {r:?}");
+        }
+    }
+
+    fn do_test_calc_mul_div_signed(a: i128, b: i128, exp: i128, iexp: usize, rs: &[Rounding]) {
+        for (a, b) in [(a, b), (-a, b), (-a, b.wrapping_neg())] {
+            for &r in rs {
+                let q1 = a.calc_mul_div(b, exp, r);
+                let q2 = a.calc_mul_div_exp(b, iexp, r);
+                assert_eq!(q1, q2, "{a} * {b} / {exp} {r:?}");
+            }
+        }
     }
 
     #[test]
@@ -804,17 +840,17 @@ mod tests {
                 // enlarge this range for more test
                 for j in 0..1000 {
                     let b = u128::MAX - j * 113;
-                    do_test_calc_mul_div(a, b, exp, iexp as usize);
+                    do_test_calc_mul_div(a, b, exp, iexp as usize, roundings(j as usize));
                 }
                 for j in 0..1000 {
                     let b = u128::MAX - j * 11113;
-                    do_test_calc_mul_div(a, b, exp, iexp as usize);
+                    do_test_calc_mul_div(a, b, exp, iexp as usize, roundings(j as usize));
                 }
 
                 // small values
                 for j in 0..1000 {
                     let b = u64::MAX as u128 - j * 113;
-                    do_test_calc_mul_div(a, b, exp, iexp as usize);
+                    do_test_calc_mul_div(a, b, exp, iexp as usize, roundings(j as usize));
                 }
             }
 
@@ -827,17 +863,67 @@ mod tests {
                 // enlarge this range for more test
                 for j in 0..1000 {
                     let b = i128::MIN + j * 113 + 1;
-                    do_test_calc_mul_div_signed(a, b, exp, iexp as usize);
+                    do_test_calc_mul_div_signed(a, b, exp, iexp as usize, roundings(j as usize));
                 }
                 for j in 0..1000 {
                     let b = i128::MIN + j * 111113 + 1;
-                    do_test_calc_mul_div_signed(a, b, exp, iexp as usize);
+                    do_test_calc_mul_div_signed(a, b, exp, iexp as usize, roundings(j as usize));
                 }
 
                 // small values
                 for j in 0..1000 {
                     let b = i64::MIN as i128 + j * 113;
-                    do_test_calc_mul_div_signed(a, b, exp, iexp as usize);
+                    do_test_calc_mul_div_signed(a, b, exp, iexp as usize, roundings(j as usize));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_calc_mul_div_tie() {
+        // half * t / exp == t / 2, for odd t
+        let ts = [
+            1,
+            3,
+            5,
+            7,
+            9,
+            11,
+            19,
+            21,
+            2_000_005,
+            i128::MAX - 10,
+            i128::MAX,
+        ];
+        for iexp in 1..39 {
+            let half = 5 * 10_i128.pow(iexp - 1);
+            let exp = 10_i128.pow(iexp);
+            for t in ts.into_iter().flat_map(|t| [t, -t]) {
+                let f = t.div_euclid(2);
+                let towards_zero = if t < 0 { f + 1 } else { f };
+                for r in Rounding::ALL {
+                    let up = r.tie_up(t < 0, f % 2 != 0, towards_zero % 5 == 0);
+                    let q = Some(f + up as i128);
+                    assert_eq!(half.calc_mul_div(t, exp, r), q, "{t}/2 {r:?}");
+                    assert_eq!(half.calc_mul_div_exp(t, iexp as usize, r), q);
+                    assert_eq!(t.calc_mul_div_exp(half, iexp as usize, r), q);
+                    assert_eq!((-half).calc_mul_div(-t, exp, r), q);
+                    assert_eq!((-half).calc_mul_div_exp(-t, iexp as usize, r), q);
+                }
+            }
+        }
+
+        for iexp in 1..39 {
+            let half = 5 * 10_u128.pow(iexp - 1);
+            let exp = 10_u128.pow(iexp);
+            for t in ts.map(|t| t as u128).into_iter().chain([u128::MAX]) {
+                let f = t / 2;
+                for r in Rounding::ALL {
+                    let up = r.tie_up(false, f % 2 != 0, f % 5 == 0);
+                    let q = Some(f + up as u128);
+                    assert_eq!(half.calc_mul_div(t, exp, r), q, "{t}/2 {r:?}");
+                    assert_eq!(half.calc_mul_div_exp(t, iexp as usize, r), q);
+                    assert_eq!(t.calc_mul_div_exp(half, iexp as usize, r), q);
                 }
             }
         }
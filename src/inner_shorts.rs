@@ -1,4 +1,4 @@
-use crate::fpdec_inner::FpdecInner;
+use crate::fpdec_inner::{magnitude_rounding, round_by_last_digit, rounding_extra, FpdecInner};
 use crate::Rounding;
 
 macro_rules! common_consts {
@@ -21,11 +21,9 @@ macro_rules! common_consts {
 }
 
 macro_rules! signed_consts {
-    ($wider_typ:ty, $uns_typ:ty, $neg_min_str:expr) => {
+    ($wider_typ:ty, $uns_typ:ty) => {
         common_consts!($wider_typ);
 
-        const NEG_MIN_STR: &'static str = $neg_min_str;
-
         type Unsigned = $uns_typ;
         fn unsigned_abs(self) -> Self::Unsigned {
             self.unsigned_abs()
@@ -37,9 +35,6 @@ macro_rules! unsigned_consts {
     ($wider_typ:ty) => {
         common_consts!($wider_typ);
 
-        #[doc(hidden)]
-        const NEG_MIN_STR: &'static str = "unreachable";
-
         type Unsigned = Self;
         fn unsigned_abs(self) -> Self::Unsigned {
             self
@@ -48,7 +43,7 @@ macro_rules! unsigned_consts {
 }
 
 impl FpdecInner for i8 {
-    signed_consts!(i16, u8, "128");
+    signed_consts!(i16, u8);
 
     fn get_exp(i: usize) -> Option<Self> {
         const ALL_EXPS: [i8; 3] = [1, 10_i8.pow(1), 10_i8.pow(2)];
@@ -58,7 +53,7 @@ impl FpdecInner for i8 {
 }
 
 impl FpdecInner for i16 {
-    signed_consts!(i32, u16, "32768");
+    signed_consts!(i32, u16);
 
     fn get_exp(i: usize) -> Option<Self> {
         const ALL_EXPS: [i16; 5] = [
@@ -74,7 +69,7 @@ impl FpdecInner for i16 {
 }
 
 impl FpdecInner for i32 {
-    signed_consts!(i64, u32, "2147483648");
+    signed_consts!(i64, u32);
 
     fn get_exp(i: usize) -> Option<Self> {
         const ALL_EXPS: [i32; 10] = [
@@ -95,7 +90,7 @@ impl FpdecInner for i32 {
 }
 
 impl FpdecInner for i64 {
-    signed_consts!(i128, u64, "9223372036854775808");
+    signed_consts!(i128, u64);
 
     fn get_exp(i: usize) -> Option<Self> {
         const ALL_EXPS: [i64; 19] = [
@@ -124,33 +119,31 @@ impl FpdecInner for i64 {
     }
 
     fn calc_mul_div_exp(self, b: Self, i: usize, rounding: Rounding) -> Option<Self> {
-        let exp = Self::get_exp(i)?;
+        let exp = Self::get_exp(i)? as u64;
 
-        if self ^ b >= 0 {
-            let extra = match rounding {
-                Rounding::Floor | Rounding::TowardsZero => 0,
-                Rounding::Ceiling | Rounding::AwayFromZero => exp - 1,
-                Rounding::Round => exp / 2, // exp is even
-            };
-
-            if self.leading_zeros() + b.leading_zeros() >= 64 + 2 {
-                // happy path, (self * b + extra) is not overflow
-                Some(div_exp_fast_1word((self * b + extra) as u64, i) as i64)
-            } else {
-                let n = self as i128 * b as i128;
-                let q = div_exp_fast_2word(n as u128 + extra as u128, exp as u64, i)?;
-                i64::try_from(q).ok()
-            }
+        // calculate on the absolute values
+        let is_neg = self ^ b < 0;
+        let rounding = magnitude_rounding(rounding, is_neg);
+        let extra = rounding_extra(rounding, exp);
+        let (ua, ub) = (self.unsigned_abs(), b.unsigned_abs());
+
+        let q = if ua.leading_zeros() + ub.leading_zeros() >= 64 + 2 {
+            // happy path, (ua * ub + extra) is not overflow
+            div_exp_fast_1word(ua * ub + extra, i)
         } else {
-            let extra = match rounding {
-                Rounding::Ceiling | Rounding::TowardsZero => 0,
-                Rounding::Floor | Rounding::AwayFromZero => exp - 1,
-                Rounding::Round => exp / 2, // exp is even
-            };
-
-            let n = self as i128 * b as i128;
-            let q = div_exp_fast_2word(-n as u128 + extra as u128, exp as u64, i)?;
+            let n = ua as u128 * ub as u128;
+            div_exp_fast_2word(n + extra as u128, exp, i)?
+        };
+
+        // the remainder is less than exp, so the lower 64 bits is enough
+        let q = round_by_last_digit(q, exp, rounding, || {
+            ua.wrapping_mul(ub).wrapping_sub(q.wrapping_mul(exp))
+        })?;
+
+        if is_neg {
             i64::try_from(-(q as i128)).ok()
+        } else {
+            i64::try_from(q).ok()
         }
     }
 }
@@ -235,19 +228,20 @@ impl FpdecInner for u64 {
     fn calc_mul_div_exp(self, b: Self, i: usize, rounding: Rounding) -> Option<Self> {
         let exp = Self::get_exp(i)?;
 
-        let extra = match rounding {
-            Rounding::Floor | Rounding::TowardsZero => 0,
-            Rounding::Ceiling | Rounding::AwayFromZero => exp - 1,
-            Rounding::Round => exp / 2, // exp is even
-        };
+        let extra = rounding_extra(rounding, exp);
 
-        if self.leading_zeros() + b.leading_zeros() >= 64 + 1 {
+        let q = if self.leading_zeros() + b.leading_zeros() > 64 {
             // happy path, (self * b + extra) is not overflow
-            Some(div_exp_fast_1word(self * b + extra, i))
+            div_exp_fast_1word(self * b + extra, i)
         } else {
             let n = self as u128 * b as u128;
-            div_exp_fast_2word(n + extra as u128, exp, i)
-        }
+            div_exp_fast_2word(n + extra as u128, exp, i)?
+        };
+
+        // the remainder is less than exp, so the lower 64 bits is enough
+        round_by_last_digit(q, exp, rounding, || {
+            self.wrapping_mul(b).wrapping_sub(q.wrapping_mul(exp))
+        })
     }
 }
 
@@ -369,52 +363,21 @@ mod tests {
     use super::*;
 
     fn do_test_calc_mul_div(a: u64, b: u64, exp: u64, iexp: usize) {
-        let q1 = a.calc_mul_div(b, exp, Rounding::Floor);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Floor);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Ceiling);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Ceiling);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Round);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Round);
-        assert_eq!(q1, q2);
+        for r in Rounding::ALL {
+            let q1 = a.calc_mul_div(b, exp, r);
+            let q2 = a.calc_mul_div_exp(b, iexp, r);
+            assert_eq!(q1, q2, "{a} * {b} / {exp} {r:?}");
+        }
     }
 
     fn do_test_calc_mul_div_signed(a: i64, b: i64, exp: i64, iexp: usize) {
-        // +-
-        let q1 = a.calc_mul_div(b, exp, Rounding::Floor);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Floor);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Ceiling);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Ceiling);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Round);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Round);
-        assert_eq!(q1, q2);
-
-        // --
-        let a = -a;
-        let q1 = a.calc_mul_div(b, exp, Rounding::Floor);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Floor);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Ceiling);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Ceiling);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Round);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Round);
-        assert_eq!(q1, q2);
-
-        // ++
-        let b = b.wrapping_neg();
-        let q1 = a.calc_mul_div(b, exp, Rounding::Floor);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Floor);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Ceiling);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Ceiling);
-        assert_eq!(q1, q2);
-        let q1 = a.calc_mul_div(b, exp, Rounding::Round);
-        let q2 = a.calc_mul_div_exp(b, iexp, Rounding::Round);
-        assert_eq!(q1, q2);
+        for (a, b) in [(a, b), (-a, b), (-a, b.wrapping_neg())] {
+            for r in Rounding::ALL {
+                let q1 = a.calc_mul_div(b, exp, r);
+                let q2 = a.calc_mul_div_exp(b, iexp, r);
+                assert_eq!(q1, q2, "{a} * {b} / {exp} {r:?}");
+            }
+        }
     }
 
     #[test]
@@ -467,4 +430,54 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_calc_mul_div_tie() {
+        // half * t / exp == t / 2, for odd t
+        let ts = [
+            1,
+            3,
+            5,
+            7,
+            9,
+            11,
+            19,
+            21,
+            2_000_005,
+            i64::MAX - 10,
+            i64::MAX,
+        ];
+        for iexp in 1..19 {
+            let half = 5 * 10_i64.pow(iexp - 1);
+            let exp = 10_i64.pow(iexp);
+            for t in ts.into_iter().flat_map(|t| [t, -t]) {
+                let f = t.div_euclid(2);
+                let towards_zero = if t < 0 { f + 1 } else { f };
+                for r in Rounding::ALL {
+                    let up = r.tie_up(t < 0, f % 2 != 0, towards_zero % 5 == 0);
+                    let q = Some(f + up as i64);
+                    assert_eq!(half.calc_mul_div(t, exp, r), q, "{t}/2 {r:?}");
+                    assert_eq!(half.calc_mul_div_exp(t, iexp as usize, r), q);
+                    assert_eq!(t.calc_mul_div_exp(half, iexp as usize, r), q);
+                    assert_eq!((-half).calc_mul_div(-t, exp, r), q);
+                    assert_eq!((-half).calc_mul_div_exp(-t, iexp as usize, r), q);
+                }
+            }
+        }
+
+        for iexp in 1..20 {
+            let half = 5 * 10_u64.pow(iexp - 1);
+            let exp = 10_u64.pow(iexp);
+            for t in ts.map(|t| t as u64).into_iter().chain([u64::MAX]) {
+                let f = t / 2;
+                for r in Rounding::ALL {
+                    let up = r.tie_up(false, f % 2 != 0, f % 5 == 0);
+                    let q = Some(f + up as u64);
+                    assert_eq!(half.calc_mul_div(t, exp, r), q, "{t}/2 {r:?}");
+                    assert_eq!(half.calc_mul_div_exp(t, iexp as usize, r), q);
+                    assert_eq!(t.calc_mul_div_exp(half, iexp as usize, r), q);
+                }
+            }
+        }
+    }
 }
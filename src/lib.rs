@@ -1,4 +1,5 @@
 #![no_std]
+#![cfg_attr(test, allow(clippy::approx_constant))]
 #![doc = include_str!("../README.md")]
 
 // modules:
@@ -17,61 +18,494 @@
 // |    inner_shorts: i8,i16,i32,i64    |  |   inner_i128: i128    |
 // |                  u8,u16,u32,u64    |  |               u128    |
 // +------------------------------------+  +-----------------------+
+mod const_literal;
+mod const_ops;
 mod const_scale_fpdec;
+#[cfg(feature = "currency")]
+pub mod currency;
 mod fpdec_inner;
 mod inner_i128;
 mod inner_shorts;
 mod none_scale_common;
 mod oob_scale_fpdec;
+mod rounding_accumulator;
+mod stochastic;
 
 pub use crate::const_scale_fpdec::ConstScaleFpdec;
 pub use crate::fpdec_inner::FpdecInner;
-pub use crate::oob_scale_fpdec::{OobFmt, OobScaleFpdec};
+pub use crate::oob_scale_fpdec::{DisplayWith, EngFmt, OobFmt, OobScaleFpdec};
+pub use crate::rounding_accumulator::RoundingAccumulator;
+pub use crate::stochastic::{SplitMix64, StochasticRng};
 
-/// Error in converting from string.
+#[doc(hidden)]
+pub use crate::const_literal::{const_literal, const_literal_oob, ConstLiteral, ConstLiteralOob};
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use primitive_fixed_point_decimal_macros::fpdec_const as __fpdec_const;
+
+/// Error in converting from string or number.
+///
+/// It contains the kind of the error, and the byte offset in the string
+/// where the error is found if converting from string.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseErrorKind};
+/// type Decimal = ConstScaleFpdec<i32, 2>;
+///
+/// let err = "1.2x".parse::<Decimal>().unwrap_err();
+/// assert_eq!(err.kind(), ParseErrorKind::Invalid);
+/// assert_eq!(err.offset(), Some(3));
+/// assert_eq!(err.to_string(), "invalid character at byte 3");
+///
+/// let err = "3.1415".parse::<Decimal>().unwrap_err();
+/// assert_eq!(err.kind(), ParseErrorKind::Precision(2));
+/// assert_eq!(err.offset(), Some(4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    offset: Option<usize>,
+}
+
+/// Kinds of [`ParseError`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum ParseError {
+#[non_exhaustive]
+pub enum ParseErrorKind {
     /// Empty string.
     Empty,
-    /// Invalid digit in the string.
+    /// Invalid character in the string, or NaN in converting from float.
     Invalid,
-    /// Overflow.
+    /// Overflow, in converting from number.
     Overflow,
-    /// Precision out of range.
-    Precision,
+    /// Overflow in the integer part of the string.
+    IntOverflow,
+    /// Overflow in the fraction part of the string, while the integer
+    /// part fits.
+    FracOverflow,
+    /// Precision out of range, with the number of extra fraction digits.
+    Precision(u32),
+}
+
+impl ParseError {
+    pub(crate) const fn new(kind: ParseErrorKind, offset: usize) -> Self {
+        Self {
+            kind,
+            offset: Some(offset),
+        }
+    }
+
+    /// Return the kind of the error.
+    pub const fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Return the byte offset in the string where the error is found,
+    /// or `None` if not converting from string.
+    ///
+    /// The offset may be equal to the length of the string, if the
+    /// string ends unexpectedly, e.g. `"1."`.
+    pub const fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+impl From<ParseErrorKind> for ParseError {
+    fn from(kind: ParseErrorKind) -> Self {
+        Self { kind, offset: None }
+    }
 }
 
 use core::num::{IntErrorKind, ParseIntError};
 impl From<ParseIntError> for ParseError {
     fn from(pie: ParseIntError) -> Self {
-        match pie.kind() {
-            IntErrorKind::Empty => ParseError::Empty,
-            IntErrorKind::InvalidDigit => ParseError::Invalid,
-            _ => ParseError::Overflow,
-        }
+        let kind = match pie.kind() {
+            IntErrorKind::Empty => ParseErrorKind::Empty,
+            IntErrorKind::InvalidDigit => ParseErrorKind::Invalid,
+            _ => ParseErrorKind::Overflow,
+        };
+        kind.into()
     }
 }
 
 use core::fmt;
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty string"),
+            Self::Invalid => write!(f, "invalid character"),
+            Self::Overflow => write!(f, "overflow"),
+            Self::IntOverflow => write!(f, "overflow in integer part"),
+            Self::FracOverflow => write!(f, "overflow in fraction part"),
+            Self::Precision(n) => write!(f, "precision out of range by {n} digits"),
+        }
+    }
+}
+
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            Self::Empty => "empty string",
-            Self::Invalid => "invalid digit in the string",
-            Self::Overflow => "overflow",
-            Self::Precision => "precision out of range",
-        };
-        write!(f, "{s}")
+        match self.offset {
+            Some(offset) => write!(f, "{} at byte {offset}", self.kind),
+            None => write!(f, "{}", self.kind),
+        }
     }
 }
 
 impl core::error::Error for ParseError {}
 
+/// Error in arithmetic operations.
+///
+/// It's returned by the `try_*` methods, e.g. [`ConstScaleFpdec::try_mul`],
+/// which are same with the `checked_*` ones but tell the reason of failure.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ArithmeticError, ConstScaleFpdec, fpdec};
+/// type Decimal = ConstScaleFpdec<i16, 2>;
+///
+/// let a: Decimal = fpdec!(200);
+/// let zero: Decimal = fpdec!(0);
+/// assert_eq!(a.try_mul::<_, 2, 2>(a), Err(ArithmeticError::Overflow));
+/// assert_eq!(a.try_mul::<_, 2, 2>(-a), Err(ArithmeticError::Underflow));
+/// assert_eq!(a.try_div::<_, 2, 2>(zero), Err(ArithmeticError::DivByZero));
+/// assert_eq!(a.try_mul::<_, 2, -10>(a), Err(ArithmeticError::ScaleOutOfRange));
+/// assert_eq!(ArithmeticError::DivByZero.to_string(), "division by zero");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ArithmeticError {
+    /// The result is greater than the maximum value of the type.
+    Overflow,
+    /// The result is less than the minimum value of the type, e.g. a
+    /// negative result for unsigned types.
+    Underflow,
+    /// Division by zero.
+    DivByZero,
+    /// The scale difference is out of range `[-DIGITS, DIGITS]`.
+    ScaleOutOfRange,
+}
+
+impl ArithmeticError {
+    // Overflow or underflow by the sign of the exact result.
+    pub(crate) const fn out_of_bounds(is_neg: bool) -> Self {
+        if is_neg {
+            Self::Underflow
+        } else {
+            Self::Overflow
+        }
+    }
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "arithmetic overflow"),
+            Self::Underflow => write!(f, "arithmetic underflow"),
+            Self::DivByZero => write!(f, "division by zero"),
+            Self::ScaleOutOfRange => write!(f, "scale difference out of range"),
+        }
+    }
+}
+
+impl core::error::Error for ArithmeticError {}
+
+/// Options for parsing decimal from string.
+///
+/// The [`ParseOptions::DEFAULT`] accepts `[+-]int[.frac][(e|E)[+-]exp]`,
+/// which is used by `FromStr` and other parsing methods without options.
+/// The [`ParseOptions::JSON`] accepts the JSON number grammar only.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, ParseOptions, fpdec};
+/// type Decimal = ConstScaleFpdec<i64, 2>;
+///
+/// let opts = ParseOptions {
+///     trim_whitespace: true,
+///     separator: Some(b','),
+///     ..ParseOptions::DEFAULT
+/// };
+/// assert_eq!(Decimal::from_str_opts(" 1,000.25 ", opts), Ok(fpdec!(1000.25)));
+///
+/// let opts = ParseOptions::JSON;
+/// assert_eq!(Decimal::from_str_opts("-0.25", opts), Ok(fpdec!(-0.25)));
+/// assert!(Decimal::from_str_opts("+0.25", opts).is_err());
+/// assert!(Decimal::from_str_opts("00.25", opts).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    /// Allow leading `+`, e.g. `+1.5`.
+    pub allow_plus: bool,
+    /// Allow the point without digits before or after it, e.g. `.5`
+    /// and `5.`. But the bare `.` is always invalid.
+    pub allow_bare_point: bool,
+    /// Allow leading zeros in the integer part, e.g. `01.5`.
+    pub allow_leading_zeros: bool,
+    /// Trim the ASCII whitespaces at both ends.
+    pub trim_whitespace: bool,
+    /// Digit-group separator, e.g. `Some(b'_')` for `1_000.25` or
+    /// `Some(b',')` for `1,000.25`. It's allowed only between two digits
+    /// in the integer or fraction part. The group sizes are not checked.
+    ///
+    /// It must not be a digit, `+`, `-`, `e`, `E` or the decimal point.
+    /// Otherwise parsing fails with `ParseErrorKind::Invalid`.
+    pub separator: Option<u8>,
+    /// Decimal point, e.g. `b','` for `1.000,25` with `Some(b'.')`
+    /// separator.
+    ///
+    /// It must not be a digit, `+`, `-`, `e` or `E`. Otherwise parsing
+    /// fails with `ParseErrorKind::Invalid`.
+    pub point: u8,
+}
+
+impl ParseOptions {
+    /// The default options, which allow the leading `+` and zeros only,
+    /// with `.` as the decimal point.
+    pub const DEFAULT: Self = Self {
+        allow_plus: true,
+        allow_bare_point: false,
+        allow_leading_zeros: true,
+        trim_whitespace: false,
+        separator: None,
+        point: b'.',
+    };
+
+    /// The strict options matching the JSON number grammar.
+    pub const JSON: Self = Self {
+        allow_plus: false,
+        allow_bare_point: false,
+        allow_leading_zeros: false,
+        trim_whitespace: false,
+        separator: None,
+        point: b'.',
+    };
+
+    // The separator and point must not be confused with the other parts
+    // of the string, or with each other.
+    pub(crate) const fn is_valid(&self) -> bool {
+        const fn is_reserved(ch: u8) -> bool {
+            matches!(ch, b'0'..=b'9' | b'+' | b'-' | b'e' | b'E')
+        }
+        if is_reserved(self.point) {
+            return false;
+        }
+        match self.separator {
+            Some(sep) => !is_reserved(sep) && sep != self.point,
+            None => true,
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Number format for displaying decimal, with grouping separator and
+/// decimal mark.
+///
+/// It is used by [`ConstScaleFpdec::display_with`] and [`OobFmt::display_with`].
+/// The integer part is grouped from the decimal mark: the first group
+/// has `primary_group` digits, and the others have `secondary_group`
+/// digits.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, NumberFormat, Rounding, fpdec};
+/// type Decimal = ConstScaleFpdec<i64, 2>;
+///
+/// let d: Decimal = fpdec!(1234567.89);
+/// assert_eq!(format!("{}", d.display_with(&NumberFormat::EN)), "1,234,567.89");
+/// assert_eq!(format!("{}", d.display_with(&NumberFormat::DE)), "1.234.567,89");
+/// assert_eq!(format!("{}", d.display_with(&NumberFormat::FR)), "1 234 567,89");
+/// assert_eq!(format!("{}", d.display_with(&NumberFormat::IN)), "12,34,567.89");
+///
+/// // custom format
+/// let nf = NumberFormat {
+///     group_separator: Some('\''),
+///     ..NumberFormat::EN
+/// };
+/// assert_eq!(format!("{}", d.display_with(&nf)), "1'234'567.89");
+///
+/// // banker's rounding for the precision
+/// let nf = NumberFormat {
+///     rounding: Rounding::HalfEven,
+///     ..NumberFormat::EN
+/// };
+/// let d: Decimal = fpdec!(1234.25);
+/// assert_eq!(format!("{:.1}", d.display_with(&nf)), "1,234.2");
+/// assert_eq!(format!("{:.1}", d.display_with(&NumberFormat::EN)), "1,234.3");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumberFormat {
+    /// Grouping separator of the integer part, e.g. `Some(',')` for
+    /// `1,234,567`. `None` for no grouping.
+    pub group_separator: Option<char>,
+    /// Size of the group next to the decimal mark. No grouping if 0.
+    pub primary_group: u8,
+    /// Size of the other groups, e.g. 2 for the Indian `12,34,567`.
+    /// The same as `primary_group` if 0.
+    pub secondary_group: u8,
+    /// Decimal mark, e.g. `'.'` or `','`.
+    pub decimal_mark: char,
+    /// Rounding for the precision, e.g. `{:.2}`.
+    pub rounding: Rounding,
+}
+
+impl NumberFormat {
+    /// No grouping and `.` decimal mark, the same as `Display`: `1234567.89`.
+    pub const DEFAULT: Self = Self {
+        group_separator: None,
+        primary_group: 3,
+        secondary_group: 3,
+        decimal_mark: '.',
+        rounding: Rounding::Round,
+    };
+
+    /// English style: `1,234,567.89`.
+    pub const EN: Self = Self {
+        group_separator: Some(','),
+        ..Self::DEFAULT
+    };
+
+    /// German style: `1.234.567,89`.
+    pub const DE: Self = Self {
+        group_separator: Some('.'),
+        decimal_mark: ',',
+        ..Self::DEFAULT
+    };
+
+    /// French style: `1 234 567,89`. The separator is ASCII space here,
+    /// set `'\u{202F}'` if you want the narrow no-break space.
+    pub const FR: Self = Self {
+        group_separator: Some(' '),
+        decimal_mark: ',',
+        ..Self::DEFAULT
+    };
+
+    /// Indian style: `12,34,567.89`.
+    pub const IN: Self = Self {
+        group_separator: Some(','),
+        secondary_group: 2,
+        ..Self::DEFAULT
+    };
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Stack buffer of the formatted decimal, returned by
+/// [`ConstScaleFpdec::to_buffer`] and [`OobFmt::to_buffer`].
+///
+/// It holds at most [`FmtBuffer::CAPACITY`] bytes, which is enough for
+/// any decimal whose scale is in range `[-DIGITS, DIGITS]` of the inner
+/// integer type, with a reasonable precision.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+/// type Decimal = ConstScaleFpdec<i64, 4>;
+///
+/// let d: Decimal = fpdec!(-12.347);
+/// let buf = d.to_buffer(Some(2)).unwrap();
+/// assert_eq!(buf.as_str(), "-12.35");
+/// assert_eq!(buf.as_bytes(), b"-12.35");
+/// ```
+#[derive(Clone, Copy)]
+pub struct FmtBuffer {
+    data: [u8; FmtBuffer::CAPACITY],
+    len: usize,
+}
+
+impl FmtBuffer {
+    /// The capacity of the buffer.
+    pub const CAPACITY: usize = 128;
+
+    // Build by writing into the empty buffer. Return `None` if the
+    // buffer is too small.
+    pub(crate) fn build(write: impl FnOnce(&mut [u8]) -> Option<usize>) -> Option<Self> {
+        let mut data = [0; Self::CAPACITY];
+        let len = write(&mut data)?;
+        Some(Self { data, len })
+    }
+
+    /// Return the formatted bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Return the formatted string.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: only ASCII bytes are written in.
+        unsafe { core::str::from_utf8_unchecked(self.as_bytes()) }
+    }
+}
+
+impl core::ops::Deref for FmtBuffer {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for FmtBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for FmtBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
 /// Rounding kinds.
+///
+/// The `Half*` kinds round towards the nearest integer, and differ only
+/// in the ties.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+/// type Decimal = ConstScaleFpdec<i32, 2>;
+///
+/// let round = |d: Decimal, rounding| d.round_ext(0, rounding);
+/// let a: Decimal = fpdec!(2.5);
+/// let b: Decimal = fpdec!(-2.5);
+/// assert_eq!(round(a, Rounding::Round), fpdec!(3));
+/// assert_eq!(round(b, Rounding::Round), fpdec!(-3));
+/// assert_eq!(round(a, Rounding::HalfEven), fpdec!(2));
+/// assert_eq!(round(b, Rounding::HalfEven), fpdec!(-2));
+/// assert_eq!(round(a, Rounding::HalfDown), fpdec!(2));
+/// assert_eq!(round(b, Rounding::HalfDown), fpdec!(-3));
+/// assert_eq!(round(a, Rounding::HalfTowardsZero), fpdec!(2));
+/// assert_eq!(round(b, Rounding::HalfTowardsZero), fpdec!(-2));
+/// assert_eq!(round(a, Rounding::HalfOdd), fpdec!(3));
+/// assert_eq!(round(b, Rounding::HalfOdd), fpdec!(-3));
+///
+/// let c: Decimal = fpdec!(1.01);
+/// let d: Decimal = fpdec!(5.01);
+/// assert_eq!(round(c, Rounding::ZeroFiveUp), fpdec!(1));
+/// assert_eq!(round(d, Rounding::ZeroFiveUp), fpdec!(6));
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
 pub enum Rounding {
-    /// towards the nearest integer
+    /// towards the nearest integer, and ties away from zero
     #[default]
     Round,
     /// towards negative infinity
@@ -82,6 +516,47 @@ pub enum Rounding {
     TowardsZero,
     /// away from zero
     AwayFromZero,
+    /// towards the nearest integer, and ties to even, i.e. banker's rounding
+    HalfEven,
+    /// towards the nearest integer, and ties towards negative infinity
+    HalfDown,
+    /// towards the nearest integer, and ties towards zero
+    HalfTowardsZero,
+    /// towards the nearest integer, and ties to odd
+    HalfOdd,
+    /// towards zero, but away from zero if the last digit would be 0 or 5,
+    /// i.e. `ROUND_05UP` in some other libraries
+    ZeroFiveUp,
+}
+
+#[cfg(test)]
+impl Rounding {
+    pub(crate) const ALL: [Rounding; 10] = [
+        Rounding::Round,
+        Rounding::Floor,
+        Rounding::Ceiling,
+        Rounding::TowardsZero,
+        Rounding::AwayFromZero,
+        Rounding::HalfEven,
+        Rounding::HalfDown,
+        Rounding::HalfTowardsZero,
+        Rounding::HalfOdd,
+        Rounding::ZeroFiveUp,
+    ];
+
+    // Reference for ties `f + 0.5`: return if rounding up to `f + 1`.
+    // `towards_zero` is `f` if positive or `f + 1` if negative.
+    pub(crate) fn tie_up(self, is_neg: bool, floor_odd: bool, towards_zero_05: bool) -> bool {
+        match self {
+            Rounding::Floor | Rounding::HalfDown => false,
+            Rounding::Ceiling => true,
+            Rounding::Round | Rounding::AwayFromZero => !is_neg,
+            Rounding::TowardsZero | Rounding::HalfTowardsZero => is_neg,
+            Rounding::HalfEven => floor_odd,
+            Rounding::HalfOdd => !floor_odd,
+            Rounding::ZeroFiveUp => is_neg != towards_zero_05,
+        }
+    }
 }
 
 /// Build decimal from integer or float number easily.
@@ -113,6 +588,75 @@ macro_rules! fpdec {
     };
 }
 
+/// Build decimal from literal, checked at compile time.
+///
+/// Compared to `fpdec!`, the literal is parsed exactly at compile time,
+/// without converting through `f64`. The result is checked against the
+/// target type, and it's a compile error if the literal has more precision
+/// than the scale, or overflows the inner integer. It can be used in
+/// `const` context too.
+///
+/// The literal can be a string, e.g. `"12.34"` and `"-1.5e-3"`, or a
+/// number literal, e.g. `12.34` and `-1_000`. Add the scale as the second
+/// argument for `OobScaleFpdec`.
+///
+/// This is available under the `macros` feature.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, OobScaleFpdec, fpdec, fpdec_const};
+/// type Balance = ConstScaleFpdec<i64, 4>;
+///
+/// const FEE: Balance = fpdec_const!("0.0015");
+/// assert_eq!(FEE, fpdec!(0.0015));
+///
+/// let b: Balance = fpdec_const!(-12.5e2);
+/// assert_eq!(b, fpdec!(-1250));
+///
+/// // exact for long literals
+/// type Big = ConstScaleFpdec<i128, 30>;
+/// let b: Big = fpdec_const!("1.000000000000000000000000000001");
+/// assert_eq!(b.mantissa(), 10_i128.pow(30) + 1);
+///
+/// // OobScaleFpdec, with scale
+/// const PRICE: OobScaleFpdec<i32> = fpdec_const!("12.34", 4);
+/// assert_eq!(PRICE, fpdec!(12.34, 4));
+///
+/// // works under a renamed path too
+/// use primitive_fixed_point_decimal as fpd;
+/// let b: Balance = fpd::fpdec_const!("1.5");
+/// assert_eq!(b, fpdec!(1.5));
+/// ```
+///
+/// Precision out of range:
+///
+/// ```compile_fail
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec_const};
+/// const FEE: ConstScaleFpdec<i64, 2> = fpdec_const!("0.0015");
+/// ```
+///
+/// Overflow:
+///
+/// ```compile_fail
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec_const};
+/// let b: ConstScaleFpdec<i16, 2> = fpdec_const!(400);
+/// ```
+///
+/// Invalid literal:
+///
+/// ```compile_fail
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec_const};
+/// let b: ConstScaleFpdec<i16, 2> = fpdec_const!("1.2.3");
+/// ```
+#[cfg(feature = "macros")]
+#[macro_export]
+macro_rules! fpdec_const {
+    ($($t:tt)*) => {
+        $crate::__fpdec_const!([$crate] $($t)*)
+    };
+}
+
 /// Used by method `checked_mul_ratio()` only.
 pub trait IntoRatioInt<T> {
     fn to_int(self) -> T;
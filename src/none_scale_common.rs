@@ -41,6 +41,74 @@ macro_rules! define_none_scale_common {
             self.0.checked_sub(&rhs.0).map(Self)
         }
 
+        /// Saturating addition. Computes `self + rhs`, saturating at the
+        /// numeric bounds instead of overflowing.
+        ///
+        /// The right operand must have the same scale with self.
+        ///
+        /// Examples:
+        ///
+        /// ```
+        /// // Here we use `ConstScaleFpdec` as example. It's same for `OobScaleFpdec`.
+        /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+        /// type Decimal = ConstScaleFpdec<i16, 2>;
+        ///
+        /// let a: Decimal = fpdec!(300);
+        /// assert_eq!(a.saturating_add(a), Decimal::MAX);
+        /// assert_eq!(a.wrapping_add(a), fpdec!(-55.36));
+        /// assert_eq!(a.overflowing_add(a), (fpdec!(-55.36), true));
+        /// assert_eq!((-a).saturating_sub(a), Decimal::MIN);
+        /// ```
+        #[must_use]
+        pub fn saturating_add(self, rhs: Self) -> Self {
+            Self(self.0.saturating_add(rhs.0))
+        }
+
+        /// Saturating subtraction. Computes `self - rhs`, saturating at the
+        /// numeric bounds instead of overflowing.
+        ///
+        /// The right operand must have the same scale with self.
+        #[must_use]
+        pub fn saturating_sub(self, rhs: Self) -> Self {
+            Self(self.0.saturating_sub(rhs.0))
+        }
+
+        /// Wrapping addition. Computes `self + rhs`, wrapping around at the
+        /// boundary of the type.
+        ///
+        /// The right operand must have the same scale with self.
+        #[must_use]
+        pub fn wrapping_add(self, rhs: Self) -> Self {
+            Self(self.0.wrapping_add(&rhs.0))
+        }
+
+        /// Wrapping subtraction. Computes `self - rhs`, wrapping around at the
+        /// boundary of the type.
+        ///
+        /// The right operand must have the same scale with self.
+        #[must_use]
+        pub fn wrapping_sub(self, rhs: Self) -> Self {
+            Self(self.0.wrapping_sub(&rhs.0))
+        }
+
+        /// Computes `self + rhs`, returning the wrapped result along with
+        /// a boolean indicating whether overflow occurred.
+        ///
+        /// The right operand must have the same scale with self.
+        #[must_use]
+        pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+            (self.wrapping_add(rhs), self.checked_add(rhs).is_none())
+        }
+
+        /// Computes `self - rhs`, returning the wrapped result along with
+        /// a boolean indicating whether overflow occurred.
+        ///
+        /// The right operand must have the same scale with self.
+        #[must_use]
+        pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+            (self.wrapping_sub(rhs), self.checked_sub(rhs).is_none())
+        }
+
         /// Checked multiplication with integer. Computes `self * n`, returning
         /// `None` if overflow occurred.
         ///
@@ -96,6 +164,80 @@ macro_rules! define_none_scale_common {
                 .map(Self)
         }
 
+        /// Computes `self * a/b` with rounding, and the remainder where
+        /// `self * a == result * b + remainder` exactly. So the rounding
+        /// difference is `remainder / b`, taking `b` as integer (i.e. its
+        /// mantissa if it's a decimal).
+        ///
+        /// Returns `None` if overflow occurred. For unsigned types, also
+        /// returns `None` if the result is rounded up, since the remainder
+        /// is negative.
+        ///
+        /// Examples:
+        ///
+        /// ```
+        /// // Here we use `ConstScaleFpdec` as example. It's same for `OobScaleFpdec`.
+        /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, fpdec};
+        /// type Balance = ConstScaleFpdec<i64, 2>;
+        ///
+        /// let total: Balance = fpdec!(100);
+        /// let (share, rem) = total.checked_mul_ratio_rem(1, 3, Rounding::Round).unwrap();
+        /// assert_eq!(share, fpdec!(33.33));
+        /// assert_eq!(rem, fpdec!(0.01)); // 100 * 1 == 33.33 * 3 + 0.01
+        /// ```
+        #[must_use]
+        pub fn checked_mul_ratio_rem<R>(
+            self,
+            a: R,
+            b: R,
+            rounding: Rounding,
+        ) -> Option<(Self, Self)>
+        where
+            R: IntoRatioInt<I>,
+        {
+            let (q, r) = self.0.calc_mul_div_rem(a.to_int(), b.to_int(), rounding)?;
+            Some((Self(q), Self(r)))
+        }
+
+        /// Computes `self * a/b` with [`RoundingAccumulator`](crate::RoundingAccumulator).
+        /// Same with [`Self::checked_mul_ratio_ext`], but adds the residue
+        /// kept in `acc` before rounding, and keeps the new residue in `acc`.
+        ///
+        /// Return `None` if overflow occurred, or `acc` has non-zero residue
+        /// of another divisor, i.e. different `b` here. `acc` is not changed
+        /// in this case, so use one `acc` for each `b`, or
+        /// [`reset()`](crate::RoundingAccumulator::reset) it before another.
+        /// For unsigned types, also return `None` if the residue is negative.
+        ///
+        /// Examples:
+        ///
+        /// ```
+        /// // Here we use `ConstScaleFpdec` as example. It's same for `OobScaleFpdec`.
+        /// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, RoundingAccumulator, fpdec};
+        /// type Balance = ConstScaleFpdec<i64, 2>;
+        ///
+        /// let mut acc = RoundingAccumulator::new();
+        /// let fee: Balance = fpdec!(0.10);
+        /// let parts: [Balance; 4] = core::array::from_fn(|_| {
+        ///     fee.checked_mul_ratio_acc(1, 4, Rounding::Floor, &mut acc).unwrap()
+        /// });
+        /// assert_eq!(parts, [fpdec!(0.02), fpdec!(0.03), fpdec!(0.02), fpdec!(0.03)]);
+        /// ```
+        #[must_use]
+        pub fn checked_mul_ratio_acc<R>(
+            self,
+            a: R,
+            b: R,
+            rounding: Rounding,
+            acc: &mut crate::RoundingAccumulator<I>,
+        ) -> Option<Self>
+        where
+            R: IntoRatioInt<I>,
+        {
+            acc.calc_mul_div(self.0, a.to_int(), b.to_int(), rounding)
+                .map(Self)
+        }
+
         /// Checked division by integer, with `Rounding::Round`.
         ///
         /// Computes `self / n`, returning `None` if `n == 0` or overflow occurres.
@@ -115,6 +257,182 @@ macro_rules! define_none_scale_common {
             self.0.rounding_div(n.into(), rounding).map(Self)
         }
 
+        /// Addition returning [`ArithmeticError`](crate::ArithmeticError) on
+        /// failure, which is `Overflow` or `Underflow`.
+        ///
+        /// Examples:
+        ///
+        /// ```
+        /// // Here we use `ConstScaleFpdec` as example. It's same for `OobScaleFpdec`.
+        /// use primitive_fixed_point_decimal::{ArithmeticError, ConstScaleFpdec, fpdec};
+        /// type Balance = ConstScaleFpdec<u32, 2>;
+        ///
+        /// let a: Balance = fpdec!(1.5);
+        /// let b: Balance = fpdec!(2);
+        /// assert_eq!(a.try_add(b), Ok(fpdec!(3.5)));
+        /// assert_eq!(Balance::MAX.try_add(a), Err(ArithmeticError::Overflow));
+        /// assert_eq!(a.try_sub(b), Err(ArithmeticError::Underflow));
+        /// ```
+        pub fn try_add(self, rhs: Self) -> Result<Self, crate::ArithmeticError> {
+            self.0
+                .checked_add(&rhs.0)
+                .map(Self)
+                .ok_or(crate::ArithmeticError::out_of_bounds(rhs.0 < I::ZERO))
+        }
+
+        /// Subtraction returning [`ArithmeticError`](crate::ArithmeticError)
+        /// on failure, which is `Overflow` or `Underflow`.
+        pub fn try_sub(self, rhs: Self) -> Result<Self, crate::ArithmeticError> {
+            self.0
+                .checked_sub(&rhs.0)
+                .map(Self)
+                .ok_or(crate::ArithmeticError::out_of_bounds(rhs.0 > I::ZERO))
+        }
+
+        /// Multiplication with integer, returning
+        /// [`ArithmeticError`](crate::ArithmeticError) on failure.
+        pub fn try_mul_int(self, n: impl Into<I>) -> Result<Self, crate::ArithmeticError> {
+            let n = n.into();
+            self.0
+                .checked_mul(&n)
+                .map(Self)
+                .ok_or(crate::ArithmeticError::out_of_bounds(
+                    (self.0 ^ n) < I::ZERO,
+                ))
+        }
+
+        /// Division by integer, returning [`ArithmeticError`](crate::ArithmeticError)
+        /// on failure.
+        ///
+        /// Equivalent to [`Self::try_div_int_ext`] with `Rounding::Round`.
+        pub fn try_div_int(self, n: impl Into<I>) -> Result<Self, crate::ArithmeticError> {
+            self.try_div_int_ext(n, Rounding::Round)
+        }
+
+        /// Division by integer with rounding, returning
+        /// [`ArithmeticError`](crate::ArithmeticError) on failure.
+        pub fn try_div_int_ext(
+            self,
+            n: impl Into<I>,
+            rounding: Rounding,
+        ) -> Result<Self, crate::ArithmeticError> {
+            self.0.try_div_ext(n.into(), 0, rounding).map(Self)
+        }
+
+        /// Computes `self * a/b`, returning [`ArithmeticError`](crate::ArithmeticError)
+        /// on failure.
+        ///
+        /// Equivalent to [`Self::try_mul_ratio_ext`] with `Rounding::Round`.
+        pub fn try_mul_ratio<R>(self, a: R, b: R) -> Result<Self, crate::ArithmeticError>
+        where
+            R: IntoRatioInt<I>,
+        {
+            self.try_mul_ratio_ext(a, b, Rounding::Round)
+        }
+
+        /// Computes `self * a/b` with rounding, returning
+        /// [`ArithmeticError`](crate::ArithmeticError) on failure.
+        ///
+        /// Same with [`Self::checked_mul_ratio_ext`], but tells the reason
+        /// of failure: `DivByZero` if `b` is zero, otherwise `Overflow` or
+        /// `Underflow` by the sign of the exact result.
+        ///
+        /// Examples:
+        ///
+        /// ```
+        /// // Here we use `ConstScaleFpdec` as example. It's same for `OobScaleFpdec`.
+        /// use primitive_fixed_point_decimal::{ArithmeticError, ConstScaleFpdec, Rounding, fpdec};
+        /// type Balance = ConstScaleFpdec<i16, 2>;
+        ///
+        /// let margin: Balance = fpdec!(300);
+        /// assert_eq!(margin.try_mul_ratio(2_i16, 3), Ok(fpdec!(200)));
+        /// assert_eq!(margin.try_mul_ratio(2_i16, 0), Err(ArithmeticError::DivByZero));
+        /// assert_eq!(margin.try_mul_ratio(-3_i16, 2), Err(ArithmeticError::Underflow));
+        /// ```
+        pub fn try_mul_ratio_ext<R>(
+            self,
+            a: R,
+            b: R,
+            rounding: Rounding,
+        ) -> Result<Self, crate::ArithmeticError>
+        where
+            R: IntoRatioInt<I>,
+        {
+            self.0
+                .try_mul_div(a.to_int(), b.to_int(), rounding)
+                .map(Self)
+        }
+
+        /// Splits `self` into parts by the ratios, without losing any unit.
+        ///
+        /// Each part is truncated towards zero first, and then the remainder
+        /// units (10<sup>-scale</sup> each) are given one by one to the parts
+        /// with the largest remainders, and to the first ones in ties, i.e.
+        /// the largest remainder method. So the parts always sum exactly to
+        /// `self`, and the result is deterministic.
+        ///
+        /// The ratios could be primitive integers, `ConstScaleFpdec` or
+        /// `OobScaleFpdec` with same scale, as [`Self::checked_mul_ratio`].
+        ///
+        /// Returns `None` if any ratio is negative, or the sum of the ratios
+        /// is zero or overflows.
+        ///
+        /// Examples:
+        ///
+        /// ```
+        /// // Here we use `ConstScaleFpdec` as example. It's same for `OobScaleFpdec`.
+        /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+        /// type Balance = ConstScaleFpdec<i64, 2>;
+        ///
+        /// let total: Balance = fpdec!(100);
+        /// let mut parts = total.allocate(&[1, 1, 1]).unwrap();
+        /// assert_eq!(parts.next(), Some(fpdec!(33.34)));
+        /// assert_eq!(parts.next(), Some(fpdec!(33.33)));
+        /// assert_eq!(parts.next(), Some(fpdec!(33.33)));
+        /// assert_eq!(parts.next(), None);
+        ///
+        /// // the unit goes to the part with the largest remainder
+        /// let parts: Vec<Balance> = total.allocate(&[1, 2]).unwrap().collect();
+        /// assert_eq!(parts, [fpdec!(33.33), fpdec!(66.67)]);
+        ///
+        /// // by percentage, and the sum is kept
+        /// let total: Balance = fpdec!(-0.05);
+        /// let parts: Vec<Balance> = total.allocate(&[70, 20, 10]).unwrap().collect();
+        /// assert_eq!(parts, [fpdec!(-0.04), fpdec!(-0.01), fpdec!(0)]);
+        ///
+        /// assert!(total.allocate(&[0, 0]).is_none());
+        /// ```
+        #[must_use]
+        pub fn allocate<'a, R>(self, ratios: &'a [R]) -> Option<impl Iterator<Item = Self> + 'a>
+        where
+            I: 'a,
+            R: IntoRatioInt<I> + Copy,
+        {
+            self.0.allocate(ratios).map(|parts| parts.map(Self))
+        }
+
+        /// Splits `self` into `n` equal parts, without losing any unit.
+        ///
+        /// This is the same as [`Self::allocate`] with `n` equal ratios.
+        /// Returns `None` if `n` is zero.
+        ///
+        /// Examples:
+        ///
+        /// ```
+        /// // Here we use `ConstScaleFpdec` as example. It's same for `OobScaleFpdec`.
+        /// use primitive_fixed_point_decimal::{ConstScaleFpdec, fpdec};
+        /// type Balance = ConstScaleFpdec<i64, 2>;
+        ///
+        /// let total: Balance = fpdec!(100);
+        /// let parts: Vec<Balance> = total.split_evenly(3).unwrap().collect();
+        /// assert_eq!(parts, [fpdec!(33.34), fpdec!(33.33), fpdec!(33.33)]);
+        /// assert_eq!(parts.iter().sum::<Balance>(), total);
+        /// ```
+        #[must_use]
+        pub fn split_evenly(self, n: usize) -> Option<impl Iterator<Item = Self>> {
+            self.0.split_evenly(n).map(|parts| parts.map(Self))
+        }
+
         /// Return if zero.
         #[must_use]
         pub fn is_zero(&self) -> bool {
@@ -217,5 +535,32 @@ macro_rules! define_none_scale_common_signed {
     };
 }
 
+// Call `$m!(From, $from => $($into),*)` for the lossless conversions
+// between inner integer types, and `$m!(TryFrom, ...)` for all others.
+macro_rules! for_each_inner_pair {
+    ($m:ident) => {
+        $m!(From, i8 => i16, i32, i64, i128);
+        $m!(From, i16 => i32, i64, i128);
+        $m!(From, i32 => i64, i128);
+        $m!(From, i64 => i128);
+        $m!(From, u8 => u16, u32, u64, u128, i16, i32, i64, i128);
+        $m!(From, u16 => u32, u64, u128, i32, i64, i128);
+        $m!(From, u32 => u64, u128, i64, i128);
+        $m!(From, u64 => u128, i128);
+
+        $m!(TryFrom, i8 => u8, u16, u32, u64, u128);
+        $m!(TryFrom, i16 => i8, u8, u16, u32, u64, u128);
+        $m!(TryFrom, i32 => i8, i16, u8, u16, u32, u64, u128);
+        $m!(TryFrom, i64 => i8, i16, i32, u8, u16, u32, u64, u128);
+        $m!(TryFrom, i128 => i8, i16, i32, i64, u8, u16, u32, u64, u128);
+        $m!(TryFrom, u8 => i8);
+        $m!(TryFrom, u16 => i8, i16, u8);
+        $m!(TryFrom, u32 => i8, i16, i32, u8, u16);
+        $m!(TryFrom, u64 => i8, i16, i32, i64, u8, u16, u32);
+        $m!(TryFrom, u128 => i8, i16, i32, i64, i128, u8, u16, u32, u64);
+    };
+}
+
 pub(crate) use define_none_scale_common;
 pub(crate) use define_none_scale_common_signed;
+pub(crate) use for_each_inner_pair;
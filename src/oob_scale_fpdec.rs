@@ -1,8 +1,11 @@
 use crate::const_scale_fpdec::ConstScaleFpdec;
-use crate::fpdec_inner::FpdecInner;
-use crate::{IntoRatioInt, ParseError, Rounding};
+use crate::fpdec_inner::{checked_add_mixed, try_into_inner, FpdecInner};
+use crate::{
+    ArithmeticError, FmtBuffer, IntoRatioInt, NumberFormat, ParseError, ParseErrorKind,
+    ParseOptions, Rounding, RoundingAccumulator, StochasticRng,
+};
 
-use core::{fmt, num::ParseIntError, ops, str::FromStr};
+use core::{fmt, ops, str::FromStr};
 
 use num_traits::{cast::FromPrimitive, float::FloatCore, Num, Signed};
 
@@ -39,6 +42,53 @@ where
 {
     crate::none_scale_common::define_none_scale_common!();
 
+    /// Checked addition with decimal of different inner integer `J` but
+    /// same scale. Computes `self + rhs`, returning `None` if the result
+    /// overflows `I`.
+    ///
+    /// Use `From` and `TryFrom` to convert between the inner integer types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, fpdec};
+    /// type Balance = OobScaleFpdec<i64>;
+    /// type Amount = OobScaleFpdec<i32>;
+    /// type Quantity = OobScaleFpdec<u16>;
+    ///
+    /// let balance: Balance = fpdec!(100, 4); // scale=4
+    /// let amount: Amount = fpdec!(2.5, 4); // scale=4 too
+    /// assert_eq!(balance.checked_add_from(amount), Some(fpdec!(102.5, 4)));
+    /// assert_eq!(balance.checked_sub_from(amount), Some(fpdec!(97.5, 4)));
+    /// assert_eq!(amount.checked_add_from(Balance::MAX), None);
+    ///
+    /// // lossless widening, and narrowing with overflow check
+    /// let wide: Balance = amount.into();
+    /// assert_eq!(Amount::try_from(wide), Ok(amount));
+    /// assert!(Amount::try_from(Balance::MAX).is_err());
+    /// assert!(Quantity::try_from(-wide).is_err());
+    /// ```
+    #[must_use]
+    pub fn checked_add_from<J>(self, rhs: OobScaleFpdec<J>) -> Option<Self>
+    where
+        J: FpdecInner,
+    {
+        checked_add_mixed(self.0, rhs.0, false).map(Self)
+    }
+
+    /// Checked subtraction with decimal of different inner integer `J` but
+    /// same scale. Computes `self - rhs`, returning `None` if the result
+    /// overflows `I`.
+    ///
+    /// See [`Self::checked_add_from`] for examples.
+    #[must_use]
+    pub fn checked_sub_from<J>(self, rhs: OobScaleFpdec<J>) -> Option<Self>
+    where
+        J: FpdecInner,
+    {
+        checked_add_mixed(self.0, rhs.0, true).map(Self)
+    }
+
     /// Checked multiplication.
     ///
     /// Equivalent to [`Self::checked_mul_ext`] with `Rounding::Round`.
@@ -205,6 +255,586 @@ where
             .map(Self)
     }
 
+    /// Multiplication returning [`ArithmeticError`] on failure.
+    ///
+    /// Equivalent to [`Self::try_mul_ext`] with `Rounding::Round`.
+    pub fn try_mul<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+    ) -> Result<Self, ArithmeticError>
+    where
+        J: FpdecInner,
+    {
+        self.try_mul_ext(rhs, diff_scale, Rounding::Round)
+    }
+
+    /// Multiplication returning [`ArithmeticError`] on failure.
+    ///
+    /// Same with [`Self::checked_mul_ext`], but tells the reason of failure:
+    /// `Overflow` or `Underflow` by the sign of the exact result, or
+    /// `ScaleOutOfRange` if argument `diff_scale` is out of range
+    /// `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ArithmeticError, OobScaleFpdec, Rounding, fpdec};
+    /// type Balance = OobScaleFpdec<i32>;
+    /// type FeeRate = OobScaleFpdec<i16>; // different types
+    ///
+    /// let balance: Balance = fpdec!(12.30, 2); // scale=2
+    /// let rate: FeeRate = fpdec!(0.01, 4); // scale=4
+    /// let fee = balance.try_mul_ext(rate, 4, Rounding::Ceiling).unwrap();
+    /// assert_eq!(fee, fpdec!(0.13, 2));
+    ///
+    /// let r = balance.try_mul_ext(rate, 20, Rounding::Ceiling);
+    /// assert_eq!(r, Err(ArithmeticError::ScaleOutOfRange));
+    /// ```
+    pub fn try_mul_ext<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+        rounding: Rounding,
+    ) -> Result<Self, ArithmeticError>
+    where
+        J: FpdecInner,
+    {
+        self.0
+            .try_mul_ext(try_into_inner(rhs.0)?, diff_scale, rounding)
+            .map(Self)
+    }
+
+    /// Division returning [`ArithmeticError`] on failure.
+    ///
+    /// Equivalent to [`Self::try_div_ext`] with `Rounding::Round`.
+    pub fn try_div<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+    ) -> Result<Self, ArithmeticError>
+    where
+        J: FpdecInner,
+    {
+        self.try_div_ext(rhs, diff_scale, Rounding::Round)
+    }
+
+    /// Division returning [`ArithmeticError`] on failure.
+    ///
+    /// Same with [`Self::checked_div_ext`], but tells the reason of failure:
+    /// `DivByZero`, `Overflow` or `Underflow` by the sign of the exact
+    /// result, or `ScaleOutOfRange` if argument `diff_scale` is out of
+    /// range `[-Self::DIGITS, Self::DIGITS]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{ArithmeticError, OobScaleFpdec, Rounding, fpdec};
+    /// type Balance = OobScaleFpdec<i32>;
+    /// type FeeRate = OobScaleFpdec<i16>;
+    ///
+    /// let fee: Balance = fpdec!(0.13, 2); // scale=2
+    /// let rate: FeeRate = fpdec!(0.03, 4); // scale=4
+    /// let balance = fee.try_div_ext(rate, -4, Rounding::Ceiling).unwrap();
+    /// assert_eq!(balance, fpdec!(4.34, 2));
+    ///
+    /// let r = Balance::MIN.try_div_ext(rate, -4, Rounding::Ceiling);
+    /// assert_eq!(r, Err(ArithmeticError::Underflow));
+    /// ```
+    pub fn try_div_ext<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+        rounding: Rounding,
+    ) -> Result<Self, ArithmeticError>
+    where
+        J: FpdecInner,
+    {
+        self.0
+            .try_div_ext(try_into_inner(rhs.0)?, diff_scale, rounding)
+            .map(Self)
+    }
+
+    /// Saturating multiplication.
+    ///
+    /// Equivalent to [`Self::saturating_mul_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn saturating_mul<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+    ) -> Self
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.saturating_mul_ext(rhs, diff_scale, Rounding::Round)
+    }
+
+    /// Saturating multiplication. Computes `self * rhs`, saturating at the
+    /// numeric bounds instead of overflowing.
+    ///
+    /// Same with [`Self::checked_mul_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly.
+    ///
+    /// The `diff_scale` out of range `[-Self::DIGITS, Self::DIGITS]` is
+    /// taken as overflow, as in [`Self::checked_mul_ext`], so the result
+    /// saturates too, unless it's zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, Rounding, fpdec};
+    /// type Decimal = OobScaleFpdec<i16>;
+    ///
+    /// let a: Decimal = fpdec!(200, 2); // scale=2
+    /// let b: Decimal = fpdec!(-0.5, 2); // scale=2
+    /// assert_eq!(a.saturating_mul(a, 2), Decimal::MAX);
+    /// assert_eq!(a.saturating_mul(b, 2), fpdec!(-100, 2));
+    /// assert_eq!(a.wrapping_mul(a, 2), fpdec!(23.04, 2));
+    /// assert_eq!(a.overflowing_mul(a, 2), (fpdec!(23.04, 2), true));
+    /// ```
+    #[must_use]
+    pub fn saturating_mul_ext<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+        rounding: Rounding,
+    ) -> Self
+    where
+        J: FpdecInner + Into<I>,
+    {
+        Self(
+            self.0
+                .saturating_mul_ext(rhs.0.into(), diff_scale, rounding),
+        )
+    }
+
+    /// Wrapping multiplication.
+    ///
+    /// Equivalent to [`Self::wrapping_mul_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn wrapping_mul<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+    ) -> Self
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.wrapping_mul_ext(rhs, diff_scale, Rounding::Round)
+    }
+
+    /// Wrapping multiplication. Computes `self * rhs`, wrapping around at
+    /// the boundary of the type.
+    ///
+    /// Same with [`Self::checked_mul_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly.
+    ///
+    /// The `diff_scale` out of range `[-Self::DIGITS, Self::DIGITS]` is
+    /// taken as overflow, as in [`Self::checked_mul_ext`]. There is no
+    /// wrapped result then, so it saturates instead.
+    #[must_use]
+    pub fn wrapping_mul_ext<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+        rounding: Rounding,
+    ) -> Self
+    where
+        J: FpdecInner + Into<I>,
+    {
+        Self(
+            self.0
+                .overflowing_mul_ext(rhs.0.into(), diff_scale, rounding)
+                .0,
+        )
+    }
+
+    /// Multiplication with overflow flag.
+    ///
+    /// Equivalent to [`Self::overflowing_mul_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn overflowing_mul<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+    ) -> (Self, bool)
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.overflowing_mul_ext(rhs, diff_scale, Rounding::Round)
+    }
+
+    /// Multiplication with overflow flag. Computes `self * rhs`, returning
+    /// the wrapped result along with a boolean indicating whether overflow
+    /// occurred.
+    ///
+    /// Same with [`Self::checked_mul_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly.
+    ///
+    /// The `diff_scale` out of range `[-Self::DIGITS, Self::DIGITS]` is
+    /// taken as overflow, as in [`Self::checked_mul_ext`]. There is no
+    /// wrapped result then, so it returns the saturated one with `true`.
+    #[must_use]
+    pub fn overflowing_mul_ext<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+        rounding: Rounding,
+    ) -> (Self, bool)
+    where
+        J: FpdecInner + Into<I>,
+    {
+        let (r, o) = self
+            .0
+            .overflowing_mul_ext(rhs.0.into(), diff_scale, rounding);
+        (Self(r), o)
+    }
+
+    /// Saturating division.
+    ///
+    /// Equivalent to [`Self::saturating_div_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn saturating_div<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+    ) -> Self
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.saturating_div_ext(rhs, diff_scale, Rounding::Round)
+    }
+
+    /// Saturating division. Computes `self / rhs`, saturating at the
+    /// numeric bounds instead of overflowing.
+    ///
+    /// Same with [`Self::checked_div_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly.
+    ///
+    /// The `diff_scale` out of range `[-Self::DIGITS, Self::DIGITS]` is
+    /// taken as overflow, as in [`Self::checked_div_ext`], so the result
+    /// saturates too, unless it's zero.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    #[must_use]
+    pub fn saturating_div_ext<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+        rounding: Rounding,
+    ) -> Self
+    where
+        J: FpdecInner + Into<I>,
+    {
+        Self(
+            self.0
+                .saturating_div_ext(rhs.0.into(), diff_scale, rounding),
+        )
+    }
+
+    /// Wrapping division.
+    ///
+    /// Equivalent to [`Self::wrapping_div_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn wrapping_div<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+    ) -> Self
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.wrapping_div_ext(rhs, diff_scale, Rounding::Round)
+    }
+
+    /// Wrapping division. Computes `self / rhs`, wrapping around at
+    /// the boundary of the type.
+    ///
+    /// Same with [`Self::checked_div_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly.
+    ///
+    /// The `diff_scale` out of range `[-Self::DIGITS, Self::DIGITS]` is
+    /// taken as overflow, as in [`Self::checked_div_ext`]. There is no
+    /// wrapped result then, so it saturates instead.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    #[must_use]
+    pub fn wrapping_div_ext<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+        rounding: Rounding,
+    ) -> Self
+    where
+        J: FpdecInner + Into<I>,
+    {
+        Self(
+            self.0
+                .overflowing_div_ext(rhs.0.into(), diff_scale, rounding)
+                .0,
+        )
+    }
+
+    /// Division with overflow flag.
+    ///
+    /// Equivalent to [`Self::overflowing_div_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn overflowing_div<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+    ) -> (Self, bool)
+    where
+        J: FpdecInner + Into<I>,
+    {
+        self.overflowing_div_ext(rhs, diff_scale, Rounding::Round)
+    }
+
+    /// Division with overflow flag. Computes `self / rhs`, returning
+    /// the wrapped result along with a boolean indicating whether overflow
+    /// occurred.
+    ///
+    /// Same with [`Self::checked_div_ext`] otherwise, but the inner integer
+    /// `J` of `rhs` must be converted into `I` losslessly.
+    ///
+    /// The `diff_scale` out of range `[-Self::DIGITS, Self::DIGITS]` is
+    /// taken as overflow, as in [`Self::checked_div_ext`]. There is no
+    /// wrapped result then, so it returns the saturated one with `true`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    #[must_use]
+    pub fn overflowing_div_ext<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+        rounding: Rounding,
+    ) -> (Self, bool)
+    where
+        J: FpdecInner + Into<I>,
+    {
+        let (r, o) = self
+            .0
+            .overflowing_div_ext(rhs.0.into(), diff_scale, rounding);
+        (Self(r), o)
+    }
+
+    /// Checked multiplication with the remainder. Same with
+    /// [`Self::checked_mul_ext`], but returns the rounding remainder too,
+    /// where `self * rhs == result + remainder` exactly.
+    ///
+    /// The remainder has the scale of the exact product, which is
+    /// `scale(self) + scale(rhs)`. It is zero if no rounding.
+    ///
+    /// For unsigned types, return `None` if the result is rounded up,
+    /// since the remainder is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, Rounding, fpdec};
+    /// type Balance = OobScaleFpdec<i64>;
+    /// type FeeRate = OobScaleFpdec<i16>;
+    ///
+    /// let balance: Balance = fpdec!(12.34, 2); // scale=2
+    /// let rate: FeeRate = fpdec!(0.0125, 4); // scale=4
+    ///
+    /// let (fee, rem) = balance.checked_mul_rem(rate, 4, Rounding::Round).unwrap();
+    /// assert_eq!(fee, fpdec!(0.15, 2));
+    /// assert_eq!(rem, fpdec!(0.00425, 6)); // scale=2+4
+    /// ```
+    #[must_use]
+    pub fn checked_mul_rem<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+        rounding: Rounding,
+    ) -> Option<(Self, Self)>
+    where
+        J: FpdecInner,
+    {
+        let (q, r) = self
+            .0
+            .checked_mul_rem(I::from(rhs.0)?, diff_scale, rounding)?;
+        Some((Self(q), Self(r)))
+    }
+
+    /// Checked division with the remainder. Same with
+    /// [`Self::checked_div_ext`], but returns the rounding remainder too,
+    /// where `self == result * rhs + remainder` exactly.
+    ///
+    /// The remainder has the larger scale of `self` and `result * rhs`,
+    /// which is `max(scale(self), scale(result) + scale(rhs))`.
+    ///
+    /// For unsigned types, return `None` if the result is rounded up,
+    /// since the remainder is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, Rounding, fpdec};
+    /// type Balance = OobScaleFpdec<i64>;
+    /// type FeeRate = OobScaleFpdec<i16>;
+    ///
+    /// let rate: FeeRate = fpdec!(0.03, 4); // scale=4
+    /// let fee: Balance = fpdec!(0.13, 2); // scale=2
+    ///
+    /// let (balance, rem) = fee.checked_div_rem(rate, -4, Rounding::Ceiling).unwrap();
+    /// assert_eq!(balance, fpdec!(4.34, 2));
+    /// assert_eq!(rem, fpdec!(-0.0002, 6)); // scale=max(2, 2+4)
+    /// ```
+    #[must_use]
+    pub fn checked_div_rem<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+        rounding: Rounding,
+    ) -> Option<(Self, Self)>
+    where
+        J: FpdecInner,
+    {
+        let (q, r) = self
+            .0
+            .checked_div_rem(I::from(rhs.0)?, diff_scale, rounding)?;
+        Some((Self(q), Self(r)))
+    }
+
+    /// Checked multiplication with [`RoundingAccumulator`]. Same with
+    /// [`Self::checked_mul_ext`], but adds the residue kept in `acc` before
+    /// rounding, and keeps the new residue in `acc`.
+    ///
+    /// Return `None` if overflow occurred, or `acc` has non-zero residue of
+    /// another divisor, i.e. different `diff_scale` here. `acc` is not
+    /// changed in this case, so use one `acc` for each divisor, or
+    /// [`reset()`](RoundingAccumulator::reset) it before another. For
+    /// unsigned types, also return `None` if the residue is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, Rounding, RoundingAccumulator, fpdec};
+    /// type Balance = OobScaleFpdec<i64>;
+    /// type Rate = OobScaleFpdec<i64>;
+    ///
+    /// let balance: Balance = fpdec!(10, 2); // scale=2
+    /// let daily_rate: Rate = fpdec!(0.0003, 4); // scale=4
+    ///
+    /// let mut acc = RoundingAccumulator::new();
+    /// let total: Balance = (0..30)
+    ///     .map(|_| balance.checked_mul_acc(daily_rate, 4, Rounding::Round, &mut acc).unwrap())
+    ///     .sum();
+    /// assert_eq!(total, fpdec!(0.09, 2));
+    /// ```
+    #[must_use]
+    pub fn checked_mul_acc<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+        rounding: Rounding,
+        acc: &mut RoundingAccumulator<I>,
+    ) -> Option<Self>
+    where
+        J: FpdecInner,
+    {
+        acc.checked_mul(self.0, I::from(rhs.0)?, diff_scale, rounding)
+            .map(Self)
+    }
+
+    /// Checked division with [`RoundingAccumulator`]. Same with
+    /// [`Self::checked_div_ext`], but adds the residue kept in `acc` before
+    /// rounding, and keeps the new residue in `acc`.
+    ///
+    /// The divisor is `rhs`, times `10^diff_scale` if `diff_scale > 0`.
+    /// The quotient is rounded once by the whole divisor, while
+    /// `checked_div_ext` rounds twice in this case, so they may differ in
+    /// the last unit.
+    ///
+    /// Return `None` if division by 0, overflow occurred, or `acc` has
+    /// non-zero residue of another divisor, i.e. different `rhs` or
+    /// `diff_scale` here. `acc` is not changed in this case, so use one
+    /// `acc` for each `rhs`, or [`reset()`](RoundingAccumulator::reset) it
+    /// before another. For unsigned types, also return `None` if the
+    /// residue is negative.
+    #[must_use]
+    pub fn checked_div_acc<J>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+        rounding: Rounding,
+        acc: &mut RoundingAccumulator<I>,
+    ) -> Option<Self>
+    where
+        J: FpdecInner,
+    {
+        acc.checked_div(self.0, I::from(rhs.0)?, diff_scale, rounding)
+            .map(Self)
+    }
+
+    /// Checked multiplication with stochastic rounding.
+    ///
+    /// Same with [`Self::checked_mul_ext`], but rounds away from zero with
+    /// probability equal to the fraction of the remainder, by the random
+    /// bits from `rng`. So the rounding errors do not bias the total in
+    /// large aggregation. The result is deterministic if `rng` is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, SplitMix64, fpdec};
+    /// type Balance = OobScaleFpdec<i64>;
+    /// type FeeRate = OobScaleFpdec<i16>;
+    ///
+    /// let balance: Balance = fpdec!(0.50, 2); // scale=2
+    /// let rate: FeeRate = fpdec!(0.01, 4); // scale=4, and fee is 0.005
+    ///
+    /// let mut rng = SplitMix64::new(42);
+    /// let total: Balance = (0..1000)
+    ///     .map(|_| balance.checked_mul_stochastic(rate, 4, &mut rng).unwrap())
+    ///     .sum();
+    /// assert!(total > fpdec!(4.5, 2) && total < fpdec!(5.5, 2));
+    /// ```
+    #[must_use]
+    pub fn checked_mul_stochastic<J, R>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self + rhs - result)
+        rng: &mut R,
+    ) -> Option<Self>
+    where
+        J: FpdecInner,
+        R: StochasticRng + ?Sized,
+    {
+        self.0
+            .checked_mul_stochastic(I::from(rhs.0)?, diff_scale, rng)
+            .map(Self)
+    }
+
+    /// Checked division with stochastic rounding.
+    ///
+    /// Same with [`Self::checked_div_ext`], but with stochastic rounding.
+    /// See [`Self::checked_mul_stochastic`] for details.
+    #[must_use]
+    pub fn checked_div_stochastic<J, R>(
+        self,
+        rhs: OobScaleFpdec<J>,
+        diff_scale: i32, // scale (self - rhs - result)
+        rng: &mut R,
+    ) -> Option<Self>
+    where
+        J: FpdecInner,
+        R: StochasticRng + ?Sized,
+    {
+        self.0
+            .checked_div_stochastic(I::from(rhs.0)?, diff_scale, rng)
+            .map(Self)
+    }
+
     /// Checked division with `ConstScaleFpdec`.
     ///
     /// Equivalent to [`Self::checked_div_const_scale_ext`] with `Rounding::Round`.
@@ -257,62 +887,239 @@ where
             .map(Self)
     }
 
-    /// Round the decimal.
+    /// Round the decimal.
+    ///
+    /// Equivalent to [`Self::round_diff_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn round_diff(self, diff_scale: i32) -> Self {
+        self.round_diff_ext(diff_scale, Rounding::Round)
+    }
+
+    /// Round the decimal with rounding type.
+    ///
+    /// The argument `diff_scale` is `original_scale - round_scale`.
+    /// Return the original decimal if `diff_scale <= 0`.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, Rounding, fpdec};
+    /// type Price = OobScaleFpdec<i64>;
+    ///
+    /// let price: Price = fpdec!(12.12345678, 8); // scale=8
+    ///
+    /// assert_eq!(price.round_diff(8 - 6), // reduce 2 scale
+    ///     fpdec!(12.123457, 8)); // `Rounding::Round` as default
+    ///
+    /// assert_eq!(price.round_diff_ext(8 - 6, Rounding::Floor),
+    ///     fpdec!(12.123456, 8));
+    /// ```
+    #[must_use]
+    pub fn round_diff_ext(self, diff_scale: i32, rounding: Rounding) -> Self {
+        Self(self.0.round_diff_with_rounding(diff_scale, rounding))
+    }
+
+    /// Convert from scale `scale` to scale `scale2`, with `Rounding::Round`
+    /// if `scale2 < scale`.
+    ///
+    /// Equivalent to [`Self::rescale_ext`] with `Rounding::Round`.
+    #[must_use]
+    pub fn rescale(self, scale: i32, scale2: i32) -> Option<Self> {
+        self.rescale_ext(scale, scale2, Rounding::Round)
+    }
+
+    /// Convert from scale `scale` to scale `scale2`, with rounding if
+    /// `scale2 < scale`. Returning `None` if overflow occurred.
+    ///
+    /// See [`Self::try_rescale`] if you want to keep the precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, Rounding, fpdec};
+    /// type Decimal = OobScaleFpdec<i64>;
+    ///
+    /// let price: Decimal = fpdec!(12.34567, 8); // scale=8
+    /// assert_eq!(price.rescale(8, 2), Some(fpdec!(12.35, 2)));
+    /// assert_eq!(price.rescale_ext(8, 2, Rounding::Floor), Some(fpdec!(12.34, 2)));
+    /// assert_eq!(price.rescale(8, 10), Some(fpdec!(12.34567, 10)));
+    /// assert_eq!(price.rescale(8, 20), None); // overflow
+    /// ```
+    #[must_use]
+    pub fn rescale_ext(self, scale: i32, scale2: i32, rounding: Rounding) -> Option<Self> {
+        self.0
+            .checked_rescale(scale.saturating_sub(scale2), rounding)
+            .map(Self)
+    }
+
+    /// Convert from scale `scale` to scale `scale2` without rounding.
+    ///
+    /// Return error of `ParseErrorKind::Overflow` if overflow occurred (to
+    /// bigger scale), or `ParseErrorKind::Precision` if precision lost (to
+    /// smaller scale).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseErrorKind, fpdec};
+    /// type Decimal = OobScaleFpdec<i64>;
+    ///
+    /// let price: Decimal = fpdec!(12.34, 8); // scale=8
+    /// assert_eq!(price.try_rescale(8, 2), Ok(fpdec!(12.34, 2)));
+    /// assert_eq!(price.try_rescale(8, 1).map_err(|e| e.kind()), Err(ParseErrorKind::Precision(1)));
+    /// assert_eq!(price.try_rescale(8, 20).map_err(|e| e.kind()), Err(ParseErrorKind::Overflow));
+    /// ```
+    pub fn try_rescale(self, scale: i32, scale2: i32) -> Result<Self, ParseError> {
+        self.0.try_rescale(scale.saturating_sub(scale2)).map(Self)
+    }
+
+    /// Read decimal from string.
+    ///
+    /// Both decimal format (e.g. `123.45`) and scientific notation (e.g.
+    /// `1.2345e2`) are supported. The exponent is applied exactly, without
+    /// any float conversion.
+    ///
+    /// Return error of `ParseErrorKind::Precision` if the string has more
+    /// precision.
+    /// Use [`Self::try_from_str_ext`] if you want to round it instead.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseErrorKind, fpdec};
+    /// type Decimal = OobScaleFpdec<i16>;
+    ///
+    /// assert_eq!(Decimal::try_from_str("1.23", 4).unwrap(), fpdec!(1.23, 4));
+    /// assert_eq!(Decimal::try_from_str("1.5e-3", 4).unwrap(), fpdec!(0.0015, 4));
+    /// assert_eq!(Decimal::try_from_str("9999", 4).map_err(|e| e.kind()), Err(ParseErrorKind::IntOverflow));
+    /// assert_eq!(Decimal::try_from_str("1.23456", 4).map_err(|e| e.kind()), Err(ParseErrorKind::Precision(1)));
+    /// assert_eq!(Decimal::try_from_str("1.5e-7", 4).map_err(|e| e.kind()), Err(ParseErrorKind::Precision(4)));
+    /// ```
+    pub fn try_from_str(s: &str, scale: i32) -> Result<Self, ParseError> {
+        I::try_from_str(s, scale).map(Self)
+    }
+
+    /// Read decimal from string with scale and rounding.
+    ///
+    /// Compared to [`Self::try_from_str`], this method rounds the extra
+    /// fraction digits by the rounding type, instead of returning
+    /// `ParseErrorKind::Precision`. The string can have any number of fraction digits.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseErrorKind, Rounding, fpdec};
+    /// type Decimal = OobScaleFpdec<i16>;
+    ///
+    /// assert_eq!(Decimal::try_from_str_ext("1.23456", 4, Rounding::Round), Ok(fpdec!(1.2346, 4)));
+    /// assert_eq!(Decimal::try_from_str_ext("1.23456", 4, Rounding::Floor), Ok(fpdec!(1.2345, 4)));
+    /// let res = Decimal::try_from_str_ext("9999", 4, Rounding::Round);
+    /// assert_eq!(res.unwrap_err().kind(), ParseErrorKind::IntOverflow);
+    /// ```
+    pub fn try_from_str_ext(s: &str, scale: i32, rounding: Rounding) -> Result<Self, ParseError> {
+        I::try_from_str_ext(s, scale, rounding).map(Self)
+    }
+
+    /// Convert from `f64` exactly, with scale and rounding.
+    ///
+    /// Compared to `TryFrom<(f64, i32)>`, which multiplies the float by
+    /// `10^scale` in binary, this method converts through the shortest
+    /// decimal representation of the float that round-trips, and then
+    /// rounds it into the scale by the rounding type, like
+    /// [`Self::try_from_str_ext`].
+    ///
+    /// Return error of `ParseErrorKind::Invalid` if the float is NaN,
+    /// `ParseErrorKind::Overflow` if it's infinite, or the kind of
+    /// [`Self::try_from_str_ext`] if overflow occurred.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseErrorKind, Rounding, fpdec};
+    /// type Decimal = OobScaleFpdec<i64>;
+    ///
+    /// assert_eq!(Decimal::try_from_f64_ext(0.1, 17, Rounding::Round), Ok(fpdec!(0.1, 17)));
+    /// assert_eq!(Decimal::try_from_f64_ext(1.005, 2, Rounding::Round), Ok(fpdec!(1.01, 2)));
+    /// assert_eq!(Decimal::try_from_f64_ext(1e300, -290, Rounding::Round), Ok(fpdec!(1e300, -290)));
+    /// let res = Decimal::try_from_f64_ext(f64::NAN, 2, Rounding::Round);
+    /// assert_eq!(res.unwrap_err().kind(), ParseErrorKind::Invalid);
+    /// let res = Decimal::try_from_f64_ext(f64::INFINITY, 2, Rounding::Round);
+    /// assert_eq!(res.unwrap_err().kind(), ParseErrorKind::Overflow);
+    /// ```
+    pub fn try_from_f64_ext(f: f64, scale: i32, rounding: Rounding) -> Result<Self, ParseError> {
+        I::try_from_float_ext(f, scale, rounding).map(Self)
+    }
+
+    /// Convert from `f32` exactly, with scale and rounding.
     ///
-    /// Equivalent to [`Self::round_diff_ext`] with `Rounding::Round`.
-    #[must_use]
-    pub fn round_diff(self, diff_scale: i32) -> Self {
-        self.round_diff_ext(diff_scale, Rounding::Round)
+    /// Same with [`Self::try_from_f64_ext`] but for `f32`.
+    pub fn try_from_f32_ext(f: f32, scale: i32, rounding: Rounding) -> Result<Self, ParseError> {
+        I::try_from_float_ext(f, scale, rounding).map(Self)
     }
 
-    /// Round the decimal with rounding type.
+    /// Read decimal from string with scale and parsing options.
     ///
-    /// The argument `diff_scale` is `original_scale - round_scale`.
-    /// Return the original decimal if `diff_scale <= 0`.
+    /// See [`ParseOptions`] for the options. Return error of
+    /// `ParseErrorKind::Precision` if the string has more precision.
     ///
     /// Examples:
     ///
     /// ```
-    /// use primitive_fixed_point_decimal::{OobScaleFpdec, Rounding, fpdec};
-    /// type Price = OobScaleFpdec<i64>;
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseOptions, fpdec};
+    /// type Decimal = OobScaleFpdec<i32>;
     ///
-    /// let price: Price = fpdec!(12.12345678, 8); // scale=8
+    /// let opts = ParseOptions { separator: Some(b','), ..ParseOptions::DEFAULT };
+    /// assert_eq!(Decimal::try_from_str_opts("1,000.25", 2, opts), Ok(fpdec!(1000.25, 2)));
+    /// assert!(Decimal::try_from_str_opts("+1.25", 2, ParseOptions::JSON).is_err());
+    /// ```
+    pub fn try_from_str_opts(s: &str, scale: i32, opts: ParseOptions) -> Result<Self, ParseError> {
+        I::try_from_str_opts(s, scale, None, opts).map(Self)
+    }
+
+    /// Read decimal from ASCII bytes with scale.
     ///
-    /// assert_eq!(price.round_diff(8 - 6), // reduce 2 scale
-    ///     fpdec!(12.123457, 8)); // `Rounding::Round` as default
+    /// This is the same as [`Self::try_from_str`] but without the UTF-8
+    /// validation, which is useful for binary protocol decoders. Non-ASCII
+    /// bytes are invalid, and the offset in the error is the byte offset.
+    ///
+    /// Examples:
     ///
-    /// assert_eq!(price.round_diff_ext(8 - 6, Rounding::Floor),
-    ///     fpdec!(12.123456, 8));
     /// ```
-    #[must_use]
-    pub fn round_diff_ext(self, diff_scale: i32, rounding: Rounding) -> Self {
-        Self(self.0.round_diff_with_rounding(diff_scale, rounding))
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseErrorKind, fpdec};
+    /// type Decimal = OobScaleFpdec<i32>;
+    ///
+    /// assert_eq!(Decimal::try_from_ascii(b"12.34", 4), Ok(fpdec!(12.34, 4)));
+    ///
+    /// let err = Decimal::try_from_ascii(b"12.345", 2).unwrap_err();
+    /// assert_eq!(err.kind(), ParseErrorKind::Precision(1));
+    /// assert_eq!(err.offset(), Some(5));
+    /// ```
+    pub fn try_from_ascii(s: &[u8], scale: i32) -> Result<Self, ParseError> {
+        I::try_from_ascii(s, scale).map(Self)
     }
 
-    /// Read decimal from string.
+    /// Read decimal from string with scale, rounding and parsing options.
     ///
-    /// This method has 2 limitations:
-    /// 1. Support decimal format only but not scientific notation;
-    /// 2. Return `ParseError::Precision` if the string has more precision.
-    ///
-    /// If you want to skip these limitations, you can parse the string
-    /// to float number first and then convert the number to this decimal.
+    /// This is the combination of [`Self::try_from_str_ext`] and
+    /// [`Self::try_from_str_opts`].
     ///
     /// Examples:
     ///
     /// ```
-    /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseError, fpdec};
-    /// type Decimal = OobScaleFpdec<i16>;
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseOptions, Rounding, fpdec};
+    /// type Decimal = OobScaleFpdec<i32>;
     ///
-    /// assert_eq!(Decimal::try_from_str("1.23", 4).unwrap(), fpdec!(1.23, 4));
-    /// assert_eq!(Decimal::try_from_str("9999", 4), Err(ParseError::Overflow));
-    /// assert_eq!(Decimal::try_from_str("1.23456", 4), Err(ParseError::Precision));
+    /// let opts = ParseOptions { allow_bare_point: true, ..ParseOptions::DEFAULT };
+    /// let res = Decimal::try_from_str_ext_opts(".255", 2, Rounding::Ceiling, opts);
+    /// assert_eq!(res, Ok(fpdec!(0.26, 2)));
     /// ```
-    pub fn try_from_str(s: &str, scale: i32) -> Result<Self, ParseError>
-    where
-        I: Num<FromStrRadixErr = ParseIntError>,
-    {
-        I::try_from_str(s, scale).map(Self)
+    pub fn try_from_str_ext_opts(
+        s: &str,
+        scale: i32,
+        rounding: Rounding,
+        opts: ParseOptions,
+    ) -> Result<Self, ParseError> {
+        I::try_from_str_opts(s, scale, Some(rounding), opts).map(Self)
     }
 
     /// Convert into `f32`.
@@ -397,6 +1204,33 @@ where
     }
 }
 
+macro_rules! convert_between_inner {
+    (From, $from:ty => $($into:ty),*) => {$(
+        impl From<OobScaleFpdec<$from>> for OobScaleFpdec<$into> {
+            /// Convert from decimal of narrower inner integer, losslessly.
+            /// The scale is not changed.
+            fn from(d: OobScaleFpdec<$from>) -> Self {
+                Self(d.0.into())
+            }
+        }
+    )*};
+    (TryFrom, $from:ty => $($into:ty),*) => {$(
+        impl TryFrom<OobScaleFpdec<$from>> for OobScaleFpdec<$into> {
+            type Error = ParseError;
+
+            /// Convert from decimal of other inner integer. Returning error
+            /// of `ParseErrorKind::Overflow` if out of range. The scale is
+            /// not changed.
+            fn try_from(d: OobScaleFpdec<$from>) -> Result<Self, Self::Error> {
+                <$into>::try_from(d.0)
+                    .map(Self)
+                    .map_err(|_| ParseErrorKind::Overflow.into())
+            }
+        }
+    )*};
+}
+crate::none_scale_common::for_each_inner_pair!(convert_between_inner);
+
 macro_rules! convert_from_int {
     ($from_int_type:ty) => {
         impl<I> TryFrom<($from_int_type, i32)> for OobScaleFpdec<I>
@@ -412,24 +1246,24 @@ macro_rules! convert_from_int {
             ///
             /// ```
             /// use core::str::FromStr;
-            /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseError};
+            /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseError, ParseErrorKind};
             /// type Decimal = OobScaleFpdec<i32>;
             ///
             /// assert_eq!(Decimal::try_from((123, 4)).unwrap(), Decimal::try_from_str("123", 4).unwrap());
             /// assert_eq!(Decimal::try_from((123_i8, 4)).unwrap(), Decimal::try_from_str("123", 4).unwrap());
             /// assert_eq!(Decimal::try_from((120000000000_i64, -10)).unwrap(), Decimal::try_from_str("120000000000", -10).unwrap());
-            /// assert_eq!(Decimal::try_from((9999999, 4)), Err(ParseError::Overflow));
-            /// assert_eq!(Decimal::try_from((123, -4)), Err(ParseError::Precision));
+            /// assert_eq!(Decimal::try_from((9999999, 4)), Err(ParseError::from(ParseErrorKind::Overflow)));
+            /// assert_eq!(Decimal::try_from((123, -4)), Err(ParseError::from(ParseErrorKind::Precision(4))));
             /// ```
             fn try_from(i: ($from_int_type, i32)) -> Result<Self, Self::Error> {
                 if i.1 > 0 {
                     // convert from type i to I first
-                    let i2 = I::from(i.0).ok_or(ParseError::Overflow)?;
+                    let i2 = I::from(i.0).ok_or(ParseErrorKind::Overflow)?;
                     I::checked_from_int(i2, i.1).map(Self)
                 } else {
                     // convert to fpdec inner first
                     let i2 = i.0.checked_from_int(i.1)?;
-                    I::from(i2).ok_or(ParseError::Overflow).map(Self)
+                    I::from(i2).ok_or(ParseErrorKind::Overflow.into()).map(Self)
                 }
             }
         }
@@ -458,13 +1292,15 @@ macro_rules! convert_from_float {
             ///
             /// Since it's hard for the float types to represent decimal fraction
             /// exactly, so this method always rounds the float number into
-            /// OobScaleFpdec.
+            /// OobScaleFpdec. The multiplication by `10^scale` is in float, so
+            /// the result may be off for big scales or magnitudes. See
+            /// [`OobScaleFpdec::try_from_f64_ext`] for exact conversion.
             ///
             /// Examples:
             ///
             /// ```
             /// use core::str::FromStr;
-            /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseError};
+            /// use primitive_fixed_point_decimal::{OobScaleFpdec, ParseError, ParseErrorKind};
             /// type Decimal = OobScaleFpdec<i32>;
             ///
             /// assert_eq!(Decimal::try_from((1.23, 4)).unwrap(), Decimal::try_from_str("1.23", 4).unwrap());
@@ -481,7 +1317,7 @@ macro_rules! convert_from_float {
                 };
                 I::$from_fn(inner_f.round())
                     .map(Self)
-                    .ok_or(ParseError::Overflow)
+                    .ok_or(ParseErrorKind::Overflow.into())
             }
         }
     };
@@ -695,8 +1531,12 @@ pub struct OobFmt<I>(pub OobScaleFpdec<I>, pub i32);
 /// It supports some [formatting options](https://doc.rust-lang.org/std/fmt/index.html#formatting-parameters):
 /// width, fill, alignment, precision, sign and 0-fill.
 ///
-/// **Panic**: if the scale is too big (>200 or <-200) or the specified
-/// precision is too big (>200).
+/// Any scale and precision are supported. The long zero paddings of big
+/// scales or precisions are written in chunks, without big buffer.
+///
+/// The number is rounded by [`Rounding::Round`] if the precision is smaller
+/// than the scale. Use `display_with()` with [`NumberFormat::rounding`] or
+/// `write_to_ext()` for other rounding kinds.
 ///
 /// Examples:
 ///
@@ -722,6 +1562,160 @@ where
     }
 }
 
+/// Display the decimal in scientific notation, e.g. `1.2345e3`.
+///
+/// The output is exact from the mantissa and scale, without any float
+/// conversion. The tailing zeros are omitted if no precision is set.
+/// The number is rounded by [`Rounding::Round`](crate::Rounding::Round)
+/// if the precision is smaller.
+///
+/// It supports the same formatting options as [`Display`](fmt::Display).
+/// See [`OobFmt::eng`] for engineering notation.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{OobScaleFpdec, OobFmt, fpdec};
+/// type Decimal = OobScaleFpdec<i64>;
+///
+/// let d: Decimal = fpdec!(0.000004658, 9);
+/// assert_eq!(format!("{:e}", OobFmt(d, 9)), "4.658e-6");
+/// assert_eq!(format!("{:.1e}", OobFmt(d, 9)), "4.7e-6");
+///
+/// let d: Decimal = fpdec!(12000, -2);
+/// assert_eq!(format!("{:e}", OobFmt(d, -2)), "1.2e4");
+/// ```
+impl<I> fmt::LowerExp for OobFmt<I>
+where
+    I: FpdecInner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0 .0.display_exp_fmt(self.1, false, false, f)
+    }
+}
+
+/// Display the decimal in scientific notation with upper case `E`,
+/// e.g. `1.2345E3`.
+///
+/// See [`LowerExp`](fmt::LowerExp) for details.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{OobScaleFpdec, OobFmt, fpdec};
+/// type Decimal = OobScaleFpdec<i64>;
+///
+/// let d: Decimal = fpdec!(0.000004658, 9);
+/// assert_eq!(format!("{:E}", OobFmt(d, 9)), "4.658E-6");
+/// ```
+impl<I> fmt::UpperExp for OobFmt<I>
+where
+    I: FpdecInner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0 .0.display_exp_fmt(self.1, false, true, f)
+    }
+}
+
+/// Wrapper to display decimal in engineering notation, whose exponent
+/// is multiple of 3, e.g. `12.345e3`.
+///
+/// Like [`OobFmt`], it merges the decimal and scale together. It is
+/// usually made by [`ConstScaleFpdec::eng`](crate::ConstScaleFpdec::eng)
+/// or [`OobFmt::eng`].
+///
+/// It implements [`LowerExp`](fmt::LowerExp) and [`UpperExp`](fmt::UpperExp)
+/// for `{:e}` and `{:E}`, and [`Display`](fmt::Display) the same as
+/// `LowerExp`. The output is exact, and there are 1 to 3 digits before
+/// the point. The precision is the count of digits after the point, and
+/// the number is rounded by [`Rounding::Round`](crate::Rounding::Round)
+/// if the precision is smaller.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{OobScaleFpdec, EngFmt, fpdec};
+/// type Decimal = OobScaleFpdec<i64>;
+///
+/// let d: Decimal = fpdec!(12345.678, 3);
+/// assert_eq!(format!("{:e}", EngFmt(d, 3)), "12.345678e3");
+/// assert_eq!(format!("{:.2E}", EngFmt(d, 3)), "12.35E3");
+/// assert_eq!(format!("{:.0e}", EngFmt(d, 3)), "12e3");
+///
+/// let d: Decimal = fpdec!(0.0001, 4);
+/// assert_eq!(format!("{}", EngFmt(d, 4)), "100e-6");
+///
+/// // round up to the next exponent
+/// let d: Decimal = fpdec!(999.96, 2);
+/// assert_eq!(format!("{:.1e}", EngFmt(d, 2)), "1.0e3");
+/// ```
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Default, Debug)]
+pub struct EngFmt<I>(pub OobScaleFpdec<I>, pub i32);
+
+impl<I> fmt::Display for EngFmt<I>
+where
+    I: FpdecInner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0 .0.display_exp_fmt(self.1, true, false, f)
+    }
+}
+
+impl<I> fmt::LowerExp for EngFmt<I>
+where
+    I: FpdecInner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0 .0.display_exp_fmt(self.1, true, false, f)
+    }
+}
+
+impl<I> fmt::UpperExp for EngFmt<I>
+where
+    I: FpdecInner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0 .0.display_exp_fmt(self.1, true, true, f)
+    }
+}
+
+/// Adaptor to display decimal with [`NumberFormat`], which sets the
+/// grouping separator and decimal mark.
+///
+/// It is made by [`ConstScaleFpdec::display_with`](crate::ConstScaleFpdec::display_with)
+/// or [`OobFmt::display_with`]. It supports the same formatting options
+/// as `Display`: width, fill, alignment, precision, sign and 0-fill.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{OobScaleFpdec, OobFmt, NumberFormat, fpdec};
+/// type Decimal = OobScaleFpdec<i64>;
+///
+/// let d: Decimal = fpdec!(-1234567.891, 3);
+/// let f = OobFmt(d, 3);
+///
+/// assert_eq!(format!("{}", f.display_with(&NumberFormat::DE)), "-1.234.567,891");
+/// assert_eq!(format!("{:.2}", f.display_with(&NumberFormat::EN)), "-1,234,567.89");
+/// assert_eq!(format!("{:>16.1}", f.display_with(&NumberFormat::IN)), "    -12,34,567.9");
+/// assert_eq!(format!("{:016.0}", f.display_with(&NumberFormat::FR)), "-0000001 234 568");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct DisplayWith<'a, I> {
+    dec: OobFmt<I>,
+    nf: &'a NumberFormat,
+}
+
+impl<I> fmt::Display for DisplayWith<'_, I>
+where
+    I: FpdecInner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let OobFmt(dec, scale) = self.dec;
+        dec.0.display_with_fmt(scale, self.nf, f)
+    }
+}
+
 /// Load from string and guess the scale by counting the fraction part.
 ///
 /// Generally you should then call [`OobFmt::rescale()`] to convert to the target
@@ -733,7 +1727,7 @@ where
 /// Examples:
 ///
 /// ```
-/// use primitive_fixed_point_decimal::{OobScaleFpdec, OobFmt, fpdec, ParseError};
+/// use primitive_fixed_point_decimal::{OobScaleFpdec, OobFmt, fpdec, ParseErrorKind};
 /// type DecFmt = OobFmt<i16>;
 ///
 /// // normal cases
@@ -749,15 +1743,18 @@ where
 /// // negative scale
 /// assert_eq!("314000000000".parse::<DecFmt>(), Ok(OobFmt(fpdec!(3.14e11, -9), -9)));
 ///
-/// // too large scale
-/// assert_eq!("1.000000000314".parse::<DecFmt>(), Err(ParseError::Precision));
+/// // scientific notation, the exponent is folded into the scale
+/// assert_eq!("3.14e-10".parse::<DecFmt>(), Ok(OobFmt(fpdec!(3.14e-10, 12), 12)));
+/// assert_eq!("3.14E+11".parse::<DecFmt>(), Ok(OobFmt(fpdec!(3.14e11, -9), -9)));
 ///
-/// // overflow
-/// assert_eq!("31415.926".parse::<DecFmt>(), Err(ParseError::Overflow));
+/// // overflow, in fraction part or integer part
+/// assert_eq!("1.000000000314".parse::<DecFmt>().map_err(|e| e.kind()), Err(ParseErrorKind::FracOverflow));
+/// assert_eq!("31415.926".parse::<DecFmt>().map_err(|e| e.kind()), Err(ParseErrorKind::FracOverflow));
+/// assert_eq!("99999".parse::<DecFmt>().map_err(|e| e.kind()), Err(ParseErrorKind::IntOverflow));
 /// ```
 impl<I> FromStr for OobFmt<I>
 where
-    I: FpdecInner + Num<FromStrRadixErr = ParseIntError>,
+    I: FpdecInner,
 {
     type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -770,6 +1767,178 @@ impl<I> OobFmt<I>
 where
     I: FpdecInner,
 {
+    /// Read decimal from string with parsing options, and guess the
+    /// scale like [`FromStr`].
+    ///
+    /// See [`ParseOptions`] for the options.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobFmt, ParseOptions, fpdec};
+    /// type DecFmt = OobFmt<i32>;
+    ///
+    /// let opts = ParseOptions { separator: Some(b'_'), ..ParseOptions::DEFAULT };
+    /// assert_eq!(DecFmt::from_str_opts("1_000.25", opts), Ok(OobFmt(fpdec!(1000.25, 2), 2)));
+    /// ```
+    pub fn from_str_opts(s: &str, opts: ParseOptions) -> Result<Self, ParseError> {
+        let (inner, scale) = I::try_from_str_only_opts(s, opts)?;
+        Ok(Self(OobScaleFpdec(inner), scale))
+    }
+
+    /// Write the decimal into the buffer, and return the length.
+    ///
+    /// The output is the same as [`Display`](fmt::Display) with the
+    /// precision only, but much faster for it does not go through the
+    /// `core::fmt` machinery. The decimal is written at the beginning of
+    /// the buffer.
+    ///
+    /// Panics:
+    ///
+    /// If the buffer is too small. See [`Self::try_write_to`] for the
+    /// non-panicking version.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobFmt, fpdec};
+    /// type DecFmt = OobFmt<i32>;
+    ///
+    /// let f: DecFmt = OobFmt(fpdec!(12.3470, 4), 4);
+    /// let mut buf = [0; 32];
+    ///
+    /// let len = f.write_to(&mut buf, None);
+    /// assert_eq!(&buf[..len], b"12.347");
+    ///
+    /// let len = f.write_to(&mut buf, Some(6));
+    /// assert_eq!(&buf[..len], b"12.347000");
+    /// ```
+    pub fn write_to(self, buf: &mut [u8], precision: Option<usize>) -> usize {
+        self.try_write_to(buf, precision)
+            .expect("buffer too small for the decimal")
+    }
+
+    /// Write the decimal into the buffer, and return the length.
+    ///
+    /// Same with [`Self::write_to`], but return `None` if the buffer is
+    /// too small, and the buffer is not changed then.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobFmt, fpdec};
+    /// type DecFmt = OobFmt<i32>;
+    ///
+    /// let f: DecFmt = OobFmt(fpdec!(12.3470, 4), 4);
+    /// let mut buf = [0; 6];
+    ///
+    /// assert_eq!(f.try_write_to(&mut buf, Some(6)), None);
+    /// assert_eq!(f.try_write_to(&mut buf, None), Some(6));
+    /// assert_eq!(&buf, b"12.347");
+    /// ```
+    pub fn try_write_to(self, buf: &mut [u8], precision: Option<usize>) -> Option<usize> {
+        self.try_write_to_ext(buf, precision, Rounding::Round)
+    }
+
+    /// Write the decimal into the buffer with rounding, and return the
+    /// length.
+    ///
+    /// Same with [`Self::write_to`], but the number is rounded by the
+    /// rounding type if the precision is smaller.
+    ///
+    /// Panics:
+    ///
+    /// If the buffer is too small. See [`Self::try_write_to_ext`] for the
+    /// non-panicking version.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobFmt, Rounding, fpdec};
+    /// type DecFmt = OobFmt<i32>;
+    ///
+    /// let f: DecFmt = OobFmt(fpdec!(12.345, 4), 4);
+    /// let mut buf = [0; 32];
+    ///
+    /// let len = f.write_to_ext(&mut buf, Some(2), Rounding::HalfEven);
+    /// assert_eq!(&buf[..len], b"12.34");
+    ///
+    /// let len = f.write_to_ext(&mut buf, Some(2), Rounding::Ceiling);
+    /// assert_eq!(&buf[..len], b"12.35");
+    /// ```
+    pub fn write_to_ext(
+        self,
+        buf: &mut [u8],
+        precision: Option<usize>,
+        rounding: Rounding,
+    ) -> usize {
+        self.try_write_to_ext(buf, precision, rounding)
+            .expect("buffer too small for the decimal")
+    }
+
+    /// Write the decimal into the buffer with rounding, and return the
+    /// length.
+    ///
+    /// Same with [`Self::write_to_ext`], but return `None` if the buffer
+    /// is too small, and the buffer is not changed then.
+    pub fn try_write_to_ext(
+        self,
+        buf: &mut [u8],
+        precision: Option<usize>,
+        rounding: Rounding,
+    ) -> Option<usize> {
+        self.0 .0.try_write_to(self.1, precision, rounding, buf)
+    }
+
+    /// Write the decimal into a stack buffer, like [`Self::write_to`].
+    ///
+    /// Return `None` if the output is longer than [`FmtBuffer::CAPACITY`].
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobFmt, fpdec};
+    /// type DecFmt = OobFmt<i32>;
+    ///
+    /// let f: DecFmt = OobFmt(fpdec!(12.3470, 4), 4);
+    /// assert_eq!(f.to_buffer(Some(6)).unwrap().as_str(), "12.347000");
+    /// ```
+    pub fn to_buffer(self, precision: Option<usize>) -> Option<FmtBuffer> {
+        FmtBuffer::build(|buf| self.try_write_to(buf, precision))
+    }
+
+    /// Display with [`NumberFormat`], which sets the grouping separator
+    /// and decimal mark.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobFmt, NumberFormat, fpdec};
+    /// type DecFmt = OobFmt<i64>;
+    ///
+    /// let f: DecFmt = OobFmt(fpdec!(1234567.89, 2), 2);
+    /// assert_eq!(format!("{}", f.display_with(&NumberFormat::FR)), "1 234 567,89");
+    /// ```
+    pub fn display_with(self, nf: &NumberFormat) -> DisplayWith<'_, I> {
+        DisplayWith { dec: self, nf }
+    }
+
+    /// Convert to [`EngFmt`] to display in engineering notation, whose
+    /// exponent is multiple of 3, e.g. `12.345e3`.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use primitive_fixed_point_decimal::{OobFmt, fpdec};
+    /// type DecFmt = OobFmt<i64>;
+    ///
+    /// let f: DecFmt = OobFmt(fpdec!(123456700, -2), -2);
+    /// assert_eq!(format!("{:e}", f.eng()), "123.4567e6");
+    /// ```
+    pub fn eng(self) -> EngFmt<I> {
+        EngFmt(self.0, self.1)
+    }
+
     /// Convert to OobScaleFpdec with scale specified.
     ///
     /// Return error if overflow occurred (to bigger scale) or precision
@@ -778,34 +1947,17 @@ where
     /// Examples:
     ///
     /// ```
-    /// use primitive_fixed_point_decimal::{OobScaleFpdec, OobFmt, fpdec, ParseError};
+    /// use primitive_fixed_point_decimal::{OobScaleFpdec, OobFmt, fpdec, ParseErrorKind};
     /// type DecFmt = OobFmt<i16>;
     ///
     /// let df = "3.14".parse::<DecFmt>().unwrap();
     /// assert_eq!(df.rescale(4), Ok(fpdec!(3.14, 4)));
-    /// assert_eq!(df.rescale(1), Err(ParseError::Precision));
-    /// assert_eq!(df.rescale(10), Err(ParseError::Overflow));
+    /// assert_eq!(df.rescale(1).map_err(|e| e.kind()), Err(ParseErrorKind::Precision(1)));
+    /// assert_eq!(df.rescale(10).map_err(|e| e.kind()), Err(ParseErrorKind::Overflow));
     /// ```
     pub fn rescale(self, scale2: i32) -> Result<OobScaleFpdec<I>, ParseError> {
         let Self(dec, scale0) = self;
-
-        if scale2 == scale0 {
-            Ok(dec)
-        } else if scale2 > scale0 {
-            let inner = I::get_exp((scale2 - scale0) as usize)
-                .ok_or(ParseError::Overflow)?
-                .checked_mul(&dec.0)
-                .ok_or(ParseError::Overflow)?;
-            Ok(OobScaleFpdec(inner))
-        } else {
-            let diff_exp = I::get_exp((scale0 - scale2) as usize).ok_or(ParseError::Precision)?;
-            let inner = dec.0 / diff_exp;
-            if (dec.0 % diff_exp).is_zero() {
-                Ok(OobScaleFpdec(inner))
-            } else {
-                Err(ParseError::Precision)
-            }
-        }
+        dec.try_rescale(scale0, scale2)
     }
 }
 
@@ -839,7 +1991,7 @@ where
 #[cfg(feature = "serde")]
 impl<'de, I> Deserialize<'de> for OobFmt<I>
 where
-    I: FromPrimitive + FpdecInner + Num<FromStrRadixErr = ParseIntError>,
+    I: FromPrimitive + FpdecInner,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -853,7 +2005,7 @@ where
 
         impl<'de, I> Visitor<'de> for OobFmtVistor<I>
         where
-            I: FromPrimitive + FpdecInner + Num<FromStrRadixErr = ParseIntError>,
+            I: FromPrimitive + FpdecInner,
         {
             type Value = OobFmt<I>;
 
@@ -869,7 +2021,6 @@ where
         // TODO:
         // 1. why deserialize_any() works for ConstScaleFpdec?
         // 2. move to serde.rs?
-        // 3. more rescale() to fpdec_inner.rs?
         deserializer.deserialize_str(OobFmtVistor(PhantomData))
     }
 }
@@ -938,6 +2089,30 @@ mod tests {
         assert_eq!(max.checked_mul(ten_p6, 10), None);
         assert_eq!(max.checked_mul(max, -10), None);
         assert_eq!(max.checked_mul(ten_p6, -10), None);
+
+        // saturating and overflowing take the out of range as overflow
+        let zero = Dec32::ZERO;
+        assert_eq!(ten_p6.saturating_mul(ten_p6, 10), max);
+        assert_eq!(ten_p6.saturating_mul(-ten_p6, -10), min);
+        assert_eq!(ten_p6.overflowing_mul(-ten_p6, 10), (min, true));
+        assert_eq!(ten_p6.wrapping_mul(ten_p6, -10), max);
+        assert_eq!(zero.overflowing_mul(ten_p6, -10), (zero, false));
+        assert_eq!(ten_p6.saturating_div(-ten_p6, 10), min);
+        assert_eq!(ten_p6.overflowing_div(ten_p6, -10), (max, true));
+        assert_eq!(zero.wrapping_div(ten_p6, 10), zero);
+
+        // rhs of other inner integer
+        let two: OobScaleFpdec<i8> = fpdec!(2, 0);
+        let five: Dec32 = fpdec!(5, 0);
+        assert_eq!(
+            half_max.saturating_mul(two, 0),
+            max.checked_sub(fpdec!(1, 0)).unwrap()
+        );
+        assert_eq!(max.saturating_mul(two, 0), max);
+        assert_eq!(
+            max.overflowing_div(two, -1),
+            (max.wrapping_mul(five, 0), true)
+        );
     }
 
     #[test]
@@ -989,7 +2164,10 @@ mod tests {
     #[test]
     fn test_from_int() {
         assert_eq!(Dec32::try_from((1_i16, 2)).unwrap().mantissa(), 100);
-        assert_eq!(Dec32::try_from((i32::MAX, 2)), Err(ParseError::Overflow));
+        assert_eq!(
+            Dec32::try_from((i32::MAX, 2)),
+            Err(ParseErrorKind::Overflow.into())
+        );
 
         // avoid overflow for: i16::MAX * 100
         assert_eq!(
@@ -1006,10 +2184,13 @@ mod tests {
         );
 
         // overflow
-        assert_eq!(Dec32::try_from((i32::MAX, 2)), Err(ParseError::Overflow));
+        assert_eq!(
+            Dec32::try_from((i32::MAX, 2)),
+            Err(ParseErrorKind::Overflow.into())
+        );
         assert_eq!(
             Dec32::try_from((i32::MAX as i64 * 1000, -2)),
-            Err(ParseError::Overflow)
+            Err(ParseErrorKind::Overflow.into())
         );
     }
 
@@ -1018,8 +2199,52 @@ mod tests {
         assert_eq!(Dec32::try_from((3.1415, 2)).unwrap().mantissa(), 314);
         assert_eq!(Dec32::try_from((31415.16, -2)).unwrap().mantissa(), 314);
 
-        assert_eq!(Dec32::try_from((3.14e10, 2)), Err(ParseError::Overflow));
-        assert_eq!(Dec32::try_from((3.14e16, -2)), Err(ParseError::Overflow));
+        assert_eq!(
+            Dec32::try_from((3.14e10, 2)),
+            Err(ParseErrorKind::Overflow.into())
+        );
+        assert_eq!(
+            Dec32::try_from((3.14e16, -2)),
+            Err(ParseErrorKind::Overflow.into())
+        );
+    }
+
+    #[test]
+    fn test_from_float_ext() {
+        let r = Rounding::Round;
+        assert_eq!(Dec32::try_from_f64_ext(3.1415, 2, r), Ok(fpdec!(3.14, 2)));
+        assert_eq!(
+            Dec32::try_from_f32_ext(-3.1415, 4, r),
+            Ok(fpdec!(-3.1415, 4))
+        );
+        assert_eq!(
+            Dec32::try_from_f64_ext(31450.0, -2, r),
+            Ok(fpdec!(31500, -2))
+        );
+        assert_eq!(
+            Dec32::try_from_f64_ext(31450.0, -2, Rounding::HalfEven),
+            Ok(fpdec!(31400, -2))
+        );
+        assert_eq!(
+            Dec32::try_from_f64_ext(-1e-300, 2, Rounding::Floor),
+            Ok(fpdec!(-0.01, 2))
+        );
+        assert_eq!(
+            Dec32::try_from_f64_ext(1e300, -295, r),
+            Ok(Dec32::from_mantissa(100000))
+        );
+        assert_eq!(
+            Dec32::try_from_f64_ext(3.14e10, 2, r).map_err(|e| e.kind()),
+            Err(ParseErrorKind::IntOverflow)
+        );
+        assert_eq!(
+            Dec32::try_from_f32_ext(f32::NAN, 2, r).map_err(|e| e.kind()),
+            Err(ParseErrorKind::Invalid)
+        );
+        assert_eq!(
+            Dec32::try_from_f32_ext(f32::NEG_INFINITY, 2, r).map_err(|e| e.kind()),
+            Err(ParseErrorKind::Overflow)
+        );
     }
 
     #[test]
@@ -1032,9 +2257,26 @@ mod tests {
         assert_eq!(Fmt32::from_str("-0.12"), Ok(Fmt32(fpdec!(-0.12, 2), 2)));
         assert_eq!(Fmt32::from_str("3.14"), Ok(Fmt32(fpdec!(3.14, 2), 2)));
         assert_eq!(Fmt32::from_str("-3.14"), Ok(Fmt32(fpdec!(-3.14, 2), 2)));
+        assert_eq!(Fmt32::from_str("1.5e-7"), Ok(Fmt32(fpdec!(1.5e-7, 8), 8)));
+        assert_eq!(Fmt32::from_str("3E+4"), Ok(Fmt32(fpdec!(3e4, -4), -4)));
         assert_eq!(
             Fmt32::from_str("3.14159265359879"),
-            Err(ParseError::Overflow)
+            Err(ParseError::new(ParseErrorKind::FracOverflow, 2))
+        );
+    }
+
+    #[test]
+    fn test_from_str_ext() {
+        let r = Rounding::Round;
+        assert_eq!(Dec32::try_from_str_ext("3.1415", 2, r), Ok(fpdec!(3.14, 2)));
+        assert_eq!(
+            Dec32::try_from_str_ext("-3.145", 2, r),
+            Ok(fpdec!(-3.15, 2))
+        );
+        assert_eq!(Dec32::try_from_str_ext("1050", -2, r), Ok(fpdec!(1100, -2)));
+        assert_eq!(
+            Dec32::try_from_str_ext("3.14159265358979323846", 8, r),
+            Ok(fpdec!(3.14159265, 8))
         );
     }
 
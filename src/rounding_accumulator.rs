@@ -0,0 +1,330 @@
+use crate::fpdec_inner::{add_rounding_div, round_away, FpdecInner};
+use crate::Rounding;
+use core::cmp::Ordering;
+use num_traits::{identities::Zero, ops::checked::CheckedMul};
+
+/// Accumulator of the rounding residues across a series of operations.
+///
+/// Each rounding in multiplication or division discards a sub-unit
+/// residue. The `*_acc` methods, e.g.
+/// [`ConstScaleFpdec::checked_mul_acc`](crate::ConstScaleFpdec::checked_mul_acc),
+/// add the residue kept in this accumulator back to the next operation,
+/// and keep the new one. So the sum of the results reconciles with the
+/// exact sum, with a difference of less than one unit at any time, which
+/// is the residue kept here.
+///
+/// The residue is kept as a fraction of the result's unit, with the
+/// divisor of the operation, e.g. `10^4` in multiplication of scales
+/// `2 + 4 -> 2`, `rhs` in division of scales `2 - 2 -> 2`, or `rhs * 10^2`
+/// in division of scales `6 - 2 -> 2`. So the accumulator should be used
+/// in a series of operations with the same divisor, e.g. one accumulator
+/// for each `rhs` in division, or [`reset()`](Self::reset) it before
+/// another divisor. The operation returns `None` if the divisor is
+/// different while the residue is not zero.
+///
+/// Division is rounded once by the whole divisor, even if `rhs * 10^2`
+/// overflows. So it may differ in the last unit from `checked_div_ext()`,
+/// which rounds twice by `rhs` and then by `10^2`. In the overflow case,
+/// the operation also returns `None` if the residue overflows.
+///
+/// This is the *Cumulative Error* removed in v0.11, now as an opt-in type.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{ConstScaleFpdec, Rounding, RoundingAccumulator, fpdec};
+/// type Balance = ConstScaleFpdec<i64, 2>;
+/// type Rate = ConstScaleFpdec<i64, 4>;
+///
+/// let balance: Balance = fpdec!(10);
+/// let daily_rate: Rate = fpdec!(0.0003); // exact daily interest is 0.003
+///
+/// // each day is rounded to zero
+/// let total: Balance = (0..30).map(|_| balance.checked_mul(daily_rate).unwrap()).sum();
+/// assert_eq!(total, fpdec!(0));
+///
+/// // the residues are collected and fed back
+/// let mut acc = RoundingAccumulator::new();
+/// let total: Balance = (0..30)
+///     .map(|_| balance.checked_mul_acc(daily_rate, Rounding::Round, &mut acc).unwrap())
+///     .sum();
+/// assert_eq!(total, fpdec!(0.09));
+/// assert!(acc.is_zero());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RoundingAccumulator<I> {
+    residue: I,
+    divisor: I,
+    divisor_exp: u32,
+}
+
+impl<I> RoundingAccumulator<I>
+where
+    I: FpdecInner,
+{
+    /// Make an empty accumulator.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            residue: I::ZERO,
+            divisor: I::ZERO,
+            divisor_exp: 0,
+        }
+    }
+
+    /// Return the residue, whose value is
+    /// `residue / (divisor * 10^divisor_exp)` of the result's unit.
+    pub const fn residue(&self) -> I {
+        self.residue
+    }
+
+    /// Return the divisor of the last operation, or zero if no operation
+    /// yet. The whole divisor is `divisor * 10^divisor_exp`.
+    pub const fn divisor(&self) -> I {
+        self.divisor
+    }
+
+    /// Return the exponent of 10 in the whole divisor. It's not zero only
+    /// if the whole divisor overflows `I`, e.g. in division by a big `rhs`
+    /// with `10^diff_scale`.
+    pub const fn divisor_exp(&self) -> u32 {
+        self.divisor_exp
+    }
+
+    /// Return if the residue is zero.
+    pub fn is_zero(&self) -> bool {
+        self.residue.is_zero()
+    }
+
+    /// Clear the residue, e.g. after booking it somewhere else.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    // Calculate `a * b / c` with rounding, adding the residue to the
+    // dividend and keeping the new one. The accumulator is not changed
+    // if `None` is returned.
+    pub(crate) fn calc_mul_div(&mut self, a: I, b: I, c: I, rounding: Rounding) -> Option<I> {
+        if !self.residue.is_zero() && (self.divisor, self.divisor_exp) != (c, 0) {
+            return None;
+        }
+
+        // a * b == q * c + r, so it's `q + (r + residue) / c` now
+        let (q, r) = a.calc_mul_div_rem(b, c, Rounding::TowardsZero)?;
+        let dividend = r.checked_add(&self.residue)?;
+        let q2 = add_rounding_div(q, dividend, c, rounding)?;
+        let residue = dividend.checked_sub(&(q2 - q).checked_mul(&c)?)?;
+
+        self.residue = residue;
+        self.divisor = c;
+        self.divisor_exp = 0;
+        Some(q2)
+    }
+
+    // Calculate `a / (b * exp)` with rounding like calc_mul_div(), where
+    // `exp` is `10^k` and `b * exp` overflows. So the dividend is smaller
+    // than the divisor, and the quotient is -1, 0 or 1.
+    fn calc_div_big(&mut self, a: I, b: I, k: u32, exp: I, rounding: Rounding) -> Option<I> {
+        if !self.residue.is_zero() && (self.divisor, self.divisor_exp) != (b, k) {
+            return None;
+        }
+
+        let dividend = a.checked_add(&self.residue)?;
+        let is_neg = (dividend ^ b) < I::ZERO;
+        let d = dividend.unsigned_abs();
+
+        // compare `|dividend|` with the half divisor `|b| * 10^k / 2`
+        let half = || match b.unsigned_abs().checked_mul(&(exp.unsigned_abs() >> 1)) {
+            Some(h) => d.cmp(&h),
+            None => Ordering::Less,
+        };
+        let (q, residue) = if d.is_zero() || !round_away(rounding, I::ZERO, is_neg, half) {
+            (I::ZERO, dividend)
+        } else {
+            // the residue `dividend - q * b * exp` has the opposite sign of
+            // `dividend`, and the magnitude `|b * exp| - |dividend|`
+            let r = b.unsigned_abs().checked_mul(&exp.unsigned_abs())? - d;
+            let r = I::from(r)?;
+            let q = if is_neg {
+                I::ZERO.checked_sub(&I::ONE)?
+            } else {
+                I::ONE
+            };
+            if dividend < I::ZERO {
+                (q, r)
+            } else {
+                (q, I::ZERO.checked_sub(&r)?)
+            }
+        };
+
+        self.residue = residue;
+        self.divisor = b;
+        self.divisor_exp = k;
+        Some(q)
+    }
+
+    // same with FpdecInner::checked_mul_ext() but with the residue
+    pub(crate) fn checked_mul(
+        &mut self,
+        a: I,
+        rhs: I,
+        diff_scale: i32,
+        rounding: Rounding,
+    ) -> Option<I> {
+        if diff_scale > 0 {
+            let exp = I::get_exp(diff_scale as usize)?;
+            self.calc_mul_div(a, rhs, exp, rounding)
+        } else {
+            // no rounding
+            a.checked_mul_ext(rhs, diff_scale, rounding)
+        }
+    }
+
+    // same with FpdecInner::checked_div_ext() but with the residue
+    pub(crate) fn checked_div(
+        &mut self,
+        a: I,
+        rhs: I,
+        diff_scale: i32,
+        rounding: Rounding,
+    ) -> Option<I> {
+        if diff_scale > 0 {
+            // a / (rhs * diff_exp), in one rounding, to keep the whole
+            // residue
+            let exp = I::get_exp(diff_scale as usize)?;
+            match rhs.checked_mul(&exp) {
+                Some(c) => self.calc_mul_div(a, I::ONE, c, rounding),
+                None => self.calc_div_big(a, rhs, diff_scale as u32, exp, rounding),
+            }
+        } else {
+            // a * diff_exp / rhs
+            let exp = I::get_exp(-diff_scale as usize)?;
+            self.calc_mul_div(a, exp, rhs, rounding)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulator() {
+        // the sum of results plus the residue equals to the exact sum
+        fn check(a: i128, b: i128, c: i128, rounding: Rounding) {
+            let mut acc = RoundingAccumulator::new();
+            let mut sum = 0;
+            for i in 1..=100 {
+                sum += acc.calc_mul_div(a, b, c, rounding).unwrap();
+                assert!(acc.residue().unsigned_abs() < c.unsigned_abs());
+
+                // (a * b * i) == sum * c + residue, in wrapping arithmetic
+                let exact = a.wrapping_mul(b).wrapping_mul(i);
+                assert_eq!(
+                    exact,
+                    sum.wrapping_mul(c).wrapping_add(acc.residue()),
+                    "{a} {b} {c} {rounding:?}"
+                );
+            }
+        }
+        for r in Rounding::ALL {
+            check(1, 1, 3, r);
+            check(-7, 3, 10, r);
+            check(12345, 6789, 10000, r);
+            check(12345, -6789, -9999, r);
+            check(i128::MAX / 3, 5, i128::MAX / 7, r); // 2-word path
+            check(i128::MIN / 3, 7, 10_i128.pow(30), r);
+        }
+
+        // residue is fed back
+        let mut acc = RoundingAccumulator::new();
+        let parts: [i32; 4] =
+            core::array::from_fn(|_| acc.checked_mul(1, 25, 2, Rounding::Round).unwrap());
+        assert_eq!(parts, [0, 1, 0, 0]);
+        assert!(acc.is_zero());
+        assert_eq!(acc.divisor(), 100);
+
+        // no rounding
+        assert_eq!(acc.checked_mul(3, 25, 0, Rounding::Round), Some(75));
+        assert_eq!(acc.checked_mul(3, 25, -1, Rounding::Round), Some(750));
+
+        // divisor mismatch, and acc is not changed
+        assert_eq!(acc.checked_mul(1, 25, 2, Rounding::Round), Some(0));
+        let old = acc;
+        assert_eq!(acc.checked_mul(1, 25, 1, Rounding::Round), None);
+        assert_eq!(acc.checked_div(1, 3, 0, Rounding::Round), None);
+        assert_eq!(acc, old);
+        acc.reset();
+        assert_eq!(acc.checked_div(1, 3, 0, Rounding::Round), Some(0));
+        assert_eq!(acc.checked_div(1, 3, 0, Rounding::Round), Some(1));
+        assert_eq!((acc.residue(), acc.divisor()), (-1, 3));
+        assert_eq!(acc.checked_div(100, 3, 1, Rounding::Round), None);
+
+        // division with scales, by the whole divisor 30
+        let mut acc = RoundingAccumulator::new();
+        assert_eq!(acc.checked_div(1000, 3, 1, Rounding::Floor), Some(33));
+        assert_eq!((acc.residue(), acc.divisor()), (10, 30));
+        assert_eq!(acc.checked_div(1000, 3, 1, Rounding::Floor), Some(33));
+        assert_eq!(acc.checked_div(1000, 3, 1, Rounding::Floor), Some(34));
+        assert!(acc.is_zero());
+        assert_eq!(acc.checked_div(1000, 0, 0, Rounding::Floor), None);
+        assert_eq!(acc.checked_div(1000, 0, 1, Rounding::Floor), None);
+
+        // division with scales in one rounding, even if `rhs * 10^diff`
+        // overflows, and the sum reconciles with the exact sum
+        for r in Rounding::ALL {
+            for (a, b, k) in [
+                (145, 10, 1),
+                (-145, 10, 1),
+                (1, 3, 1),
+                (100, i32::MAX, 2),
+                (i32::MAX, 7, 9),
+                (i32::MIN, -7, 9),
+                (-1_500_000_000, 3, 9),
+                (i32::MAX / 2 + 1, i32::MAX, 1),
+            ] {
+                let c = b as i128 * 10_i128.pow(k);
+                let mut acc = RoundingAccumulator::new();
+                let mut sum = 0_i128;
+                for i in 1..=20 {
+                    let Some(q) = acc.checked_div(a, b, k as i32, r) else {
+                        // only if the dividend or the new residue overflows
+                        let dividend = a as i128 + acc.residue() as i128;
+                        let q = dividend.checked_div_ext(c, 0, r).unwrap();
+                        let residue = dividend - q * c;
+                        assert!(
+                            i32::try_from(dividend).is_err() || i32::try_from(residue).is_err()
+                        );
+                        break;
+                    };
+                    if i == 1 {
+                        let exact = (a as i128).checked_div_ext(c, 0, r).unwrap();
+                        assert_eq!(q as i128, exact, "{a} {b} {k} {r:?}");
+                    }
+                    sum += q as i128;
+                    let residue = acc.residue() as i128;
+                    assert!(residue.unsigned_abs() < c.unsigned_abs());
+                    assert_eq!(a as i128 * i, sum * c + residue, "{a} {b} {k} {r:?}");
+                    let whole = acc.divisor() as i128 * 10_i128.pow(acc.divisor_exp());
+                    assert!(acc.is_zero() || whole == c);
+                }
+            }
+        }
+
+        // divisor mismatch with the big divisor
+        let mut acc = RoundingAccumulator::new();
+        assert_eq!(acc.checked_div(100, i32::MAX, 2, Rounding::Floor), Some(0));
+        assert_eq!((acc.residue(), acc.divisor_exp()), (100, 2));
+        assert_eq!(acc.checked_div(100, i32::MAX, 1, Rounding::Floor), None);
+        assert_eq!(acc.checked_mul(100, i32::MAX, 2, Rounding::Floor), None);
+        assert_eq!(acc.checked_div(100, i32::MAX, 0, Rounding::Floor), None);
+
+        // unsigned
+        let mut acc = RoundingAccumulator::new();
+        assert_eq!(acc.checked_div(2_u8, 3, 0, Rounding::Floor), Some(0));
+        assert_eq!(acc.checked_div(2_u8, 3, 0, Rounding::Floor), Some(1));
+        assert_eq!(acc.checked_div(2_u8, 3, 0, Rounding::Round), Some(1));
+        assert_eq!(acc.checked_div(2_u8, 3, 0, Rounding::Round), None); // negative residue
+        assert_eq!((acc.residue(), acc.divisor()), (0, 3));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::fpdec_inner::FpdecInner;
+
+/// Source of random bits for stochastic rounding.
+///
+/// Stochastic rounding rounds away from zero with probability equal to
+/// the fraction of the remainder, e.g. rounding `0.3` to `1` with probability
+/// `30%` and to `0` with `70%`. So the expectation of the result equals to
+/// the exact value, and the rounding errors do not accumulate in one
+/// direction in large aggregation.
+///
+/// Implement this trait for your random number generator. The result is
+/// deterministic if the generator is, e.g. [`SplitMix64`] with a fixed seed.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::StochasticRng;
+///
+/// // wrap a generator from other crates
+/// struct MyRng(u64);
+/// impl StochasticRng for MyRng {
+///     fn next_u64(&mut self) -> u64 {
+///         self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1);
+///         self.0
+///     }
+/// }
+/// ```
+pub trait StochasticRng {
+    /// Return the next random `u64`, whose bits are uniformly distributed.
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<R: StochasticRng + ?Sized> StochasticRng for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// A small and fast pseudo-random generator for [`StochasticRng`].
+///
+/// It is the SplitMix64 algorithm, which is deterministic given a seed.
+/// It is not cryptographically secure.
+///
+/// Examples:
+///
+/// ```
+/// use primitive_fixed_point_decimal::{SplitMix64, StochasticRng};
+///
+/// let mut a = SplitMix64::new(42);
+/// let mut b = SplitMix64::new(42);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Make a generator with the seed.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl StochasticRng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+// Return `true` with probability `r / d`, where `0 < r < d`.
+//
+// Compare the binary fraction of `r / d` with a random fraction, bit by
+// bit from the highest one. The first different bit decides which one is
+// less. So it is exact for any `d`, and consumes 2 bits on average.
+pub(crate) fn stochastic_away<U, R>(mut r: U, d: U, rng: &mut R) -> bool
+where
+    U: FpdecInner,
+    R: StochasticRng + ?Sized,
+{
+    loop {
+        let mut bits = rng.next_u64();
+        for _ in 0..u64::BITS {
+            // next bit of `r / d`, and avoid overflow of `r * 2`
+            let bit = r >= d - r;
+            r = if bit { r - (d - r) } else { r + r };
+
+            let random_bit = bits & 1 == 1;
+            bits >>= 1;
+            if bit != random_bit {
+                return bit;
+            }
+            if r.is_zero() {
+                // the rest bits of `r / d` are all 0
+                return false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stochastic_away() {
+        let mut rng = SplitMix64::new(1);
+        for (r, d) in [(1_u32, 2_u32), (1, 3), (2, 3), (1, 10), (7, 10), (1, 1000)] {
+            let n = 100_000;
+            let count = (0..n).filter(|_| stochastic_away(r, d, &mut rng)).count();
+            let expect = n * r as usize / d as usize;
+            assert!(count.abs_diff(expect) < n / 100, "{r}/{d}: {count}");
+        }
+
+        // big divisor
+        let d = u128::MAX;
+        let n = 10_000;
+        let count = (0..n)
+            .filter(|_| stochastic_away(d / 4, d, &mut rng))
+            .count();
+        assert!(count.abs_diff(n / 4) < n / 50, "{count}");
+
+        // deterministic
+        let mut rng1 = SplitMix64::new(7);
+        let mut rng2 = SplitMix64::new(7);
+        for _ in 0..100 {
+            assert_eq!(
+                stochastic_away(3_u8, 7, &mut rng1),
+                stochastic_away(3_u8, 7, &mut rng2)
+            );
+        }
+    }
+}